@@ -52,11 +52,12 @@ where
     /// Name defines wich model we should check
     /// for attrs
     ///
-    /// TODO: maybe Resource Id is not always Int(u32) ?
+    /// Resource Id is the id part of the path as is,
+    /// models can be selected by id or uuid
     async fn get_model(
         &self,
         resource_name: &str,
-        resource_id: u32,
+        resource_id: &str,
     ) -> Box<dyn ModelPermission<T, A>>;
 }
 
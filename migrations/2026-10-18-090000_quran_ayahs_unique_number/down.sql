@@ -0,0 +1 @@
+ALTER TABLE quran_ayahs DROP CONSTRAINT quran_ayahs_surah_number_unique;
@@ -0,0 +1,15 @@
+-- Close the gaps and remove the duplicates that the old
+-- count based numbering left behind
+UPDATE quran_ayahs
+SET ayah_number = numbered.row_number
+FROM (
+    SELECT id, ROW_NUMBER() OVER (PARTITION BY surah_id ORDER BY ayah_number, id) AS row_number
+    FROM quran_ayahs
+) AS numbered
+WHERE quran_ayahs.id = numbered.id;
+
+-- Deferred so the renumbering queries can shift a range of ayahs
+-- in a single transaction
+ALTER TABLE quran_ayahs
+    ADD CONSTRAINT quran_ayahs_surah_number_unique UNIQUE (surah_id, ayah_number)
+        DEFERRABLE INITIALLY DEFERRED;
@@ -0,0 +1 @@
+DROP TABLE quran_words_renditions;
@@ -0,0 +1,14 @@
+CREATE TABLE quran_words_renditions (
+    id serial NOT NULL,
+    uuid uuid DEFAULT uuid_generate_v4 () NOT NULL,
+    creator_user_id serial NOT NULL,
+    word_id serial NOT NULL,
+    script VARCHAR(50) NOT NULL,
+    text TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT word_rendition_id PRIMARY KEY (id),
+    CONSTRAINT fk_quran_word_rendition_creator_user_id FOREIGN KEY (creator_user_id) REFERENCES app_users (id),
+    CONSTRAINT fk_rendition_word FOREIGN KEY (word_id) REFERENCES quran_words (id) on delete cascade,
+    UNIQUE (word_id, script)
+);
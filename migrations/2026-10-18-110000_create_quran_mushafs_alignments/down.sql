@@ -0,0 +1 @@
+DROP TABLE quran_mushafs_alignments;
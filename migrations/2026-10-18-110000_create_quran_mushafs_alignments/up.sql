@@ -0,0 +1,25 @@
+CREATE TABLE quran_mushafs_alignments (
+    id serial NOT NULL,
+    uuid uuid DEFAULT uuid_generate_v4 () NOT NULL,
+    creator_user_id serial NOT NULL,
+    source_mushaf_id serial NOT NULL,
+    source_ayah_id serial NOT NULL,
+    source_word_from INT,
+    source_word_to INT,
+    target_mushaf_id serial NOT NULL,
+    target_ayah_id serial NOT NULL,
+    target_word_from INT,
+    target_word_to INT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT mushaf_alignment_id PRIMARY KEY (id),
+    CONSTRAINT fk_quran_mushaf_alignment_creator_user_id FOREIGN KEY (creator_user_id) REFERENCES app_users (id),
+    CONSTRAINT fk_alignment_source_mushaf FOREIGN KEY (source_mushaf_id) REFERENCES quran_mushafs (id) on delete cascade,
+    CONSTRAINT fk_alignment_source_ayah FOREIGN KEY (source_ayah_id) REFERENCES quran_ayahs (id) on delete cascade,
+    CONSTRAINT fk_alignment_target_mushaf FOREIGN KEY (target_mushaf_id) REFERENCES quran_mushafs (id) on delete cascade,
+    CONSTRAINT fk_alignment_target_ayah FOREIGN KEY (target_ayah_id) REFERENCES quran_ayahs (id) on delete cascade,
+    CONSTRAINT alignment_source_words CHECK ((source_word_from IS NULL) = (source_word_to IS NULL)),
+    CONSTRAINT alignment_target_words CHECK ((target_word_from IS NULL) = (target_word_to IS NULL))
+);
+
+CREATE INDEX quran_mushafs_alignments_mushafs ON quran_mushafs_alignments (source_mushaf_id, target_mushaf_id);
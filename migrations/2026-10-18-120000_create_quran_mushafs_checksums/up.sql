@@ -0,0 +1,17 @@
+CREATE TABLE quran_mushafs_checksums (
+    id serial NOT NULL,
+    uuid uuid DEFAULT uuid_generate_v4 () NOT NULL,
+    creator_user_id serial NOT NULL,
+    mushaf_id serial NOT NULL,
+    surah_number INT,
+    ayah_number INT,
+    checksum VARCHAR(64) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT mushaf_checksum_id PRIMARY KEY (id),
+    CONSTRAINT fk_quran_mushaf_checksum_creator_user_id FOREIGN KEY (creator_user_id) REFERENCES app_users (id),
+    CONSTRAINT fk_checksum_mushaf FOREIGN KEY (mushaf_id) REFERENCES quran_mushafs (id) on delete cascade,
+    CONSTRAINT checksum_ayah_surah CHECK (ayah_number IS NULL OR surah_number IS NOT NULL)
+);
+
+CREATE INDEX quran_mushafs_checksums_mushaf ON quran_mushafs_checksums (mushaf_id);
@@ -0,0 +1,17 @@
+CREATE TABLE quran_words_tajweed (
+    id serial NOT NULL,
+    uuid uuid DEFAULT uuid_generate_v4 () NOT NULL,
+    creator_user_id serial NOT NULL,
+    word_id serial NOT NULL,
+    rule VARCHAR(50) NOT NULL,
+    start_index INT NOT NULL,
+    end_index INT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT word_tajweed_id PRIMARY KEY (id),
+    CONSTRAINT fk_quran_word_tajweed_creator_user_id FOREIGN KEY (creator_user_id) REFERENCES app_users (id),
+    CONSTRAINT fk_tajweed_word FOREIGN KEY (word_id) REFERENCES quran_words (id) on delete cascade,
+    CONSTRAINT tajweed_range CHECK (start_index >= 0 AND end_index > start_index)
+);
+
+CREATE INDEX quran_words_tajweed_word ON quran_words_tajweed (word_id);
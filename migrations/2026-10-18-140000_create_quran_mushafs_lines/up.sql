@@ -0,0 +1,21 @@
+CREATE TABLE quran_mushafs_lines (
+    id serial NOT NULL,
+    uuid uuid DEFAULT uuid_generate_v4 () NOT NULL,
+    creator_user_id serial NOT NULL,
+    mushaf_id serial NOT NULL,
+    page INT NOT NULL,
+    line_number INT NOT NULL,
+    line_type VARCHAR(20) NOT NULL,
+    surah_number INT,
+    first_word_id INT,
+    last_word_id INT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT mushaf_line_id PRIMARY KEY (id),
+    CONSTRAINT fk_quran_mushaf_line_creator_user_id FOREIGN KEY (creator_user_id) REFERENCES app_users (id),
+    CONSTRAINT fk_line_mushaf FOREIGN KEY (mushaf_id) REFERENCES quran_mushafs (id) on delete cascade,
+    CONSTRAINT fk_line_first_word FOREIGN KEY (first_word_id) REFERENCES quran_words (id) on delete cascade,
+    CONSTRAINT fk_line_last_word FOREIGN KEY (last_word_id) REFERENCES quran_words (id) on delete cascade,
+    CONSTRAINT line_words CHECK ((first_word_id IS NULL) = (last_word_id IS NULL)),
+    UNIQUE (mushaf_id, page, line_number)
+);
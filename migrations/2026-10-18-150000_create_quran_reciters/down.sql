@@ -0,0 +1,3 @@
+DROP TABLE quran_reciters_segments;
+DROP TABLE quran_reciters_ayahs;
+DROP TABLE quran_reciters;
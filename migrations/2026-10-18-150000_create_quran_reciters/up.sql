@@ -0,0 +1,49 @@
+CREATE TABLE quran_reciters (
+    id serial NOT NULL,
+    uuid uuid DEFAULT uuid_generate_v4 () NOT NULL,
+    creator_user_id serial NOT NULL,
+    mushaf_id serial NOT NULL,
+    name VARCHAR(200) NOT NULL,
+    style VARCHAR(50),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT reciter_id PRIMARY KEY (id),
+    CONSTRAINT fk_quran_reciter_creator_user_id FOREIGN KEY (creator_user_id) REFERENCES app_users (id),
+    CONSTRAINT fk_reciter_mushaf FOREIGN KEY (mushaf_id) REFERENCES quran_mushafs (id) on delete cascade
+);
+
+CREATE TABLE quran_reciters_ayahs (
+    id serial NOT NULL,
+    uuid uuid DEFAULT uuid_generate_v4 () NOT NULL,
+    creator_user_id serial NOT NULL,
+    reciter_id serial NOT NULL,
+    ayah_id serial NOT NULL,
+    path TEXT NOT NULL,
+    duration_ms INT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT reciter_ayah_id PRIMARY KEY (id),
+    CONSTRAINT fk_quran_reciter_ayah_creator_user_id FOREIGN KEY (creator_user_id) REFERENCES app_users (id),
+    CONSTRAINT fk_reciter_ayah_reciter FOREIGN KEY (reciter_id) REFERENCES quran_reciters (id) on delete cascade,
+    CONSTRAINT fk_reciter_ayah_ayah FOREIGN KEY (ayah_id) REFERENCES quran_ayahs (id) on delete cascade,
+    UNIQUE (reciter_id, ayah_id)
+);
+
+CREATE TABLE quran_reciters_segments (
+    id serial NOT NULL,
+    uuid uuid DEFAULT uuid_generate_v4 () NOT NULL,
+    creator_user_id serial NOT NULL,
+    reciter_ayah_id serial NOT NULL,
+    word_id serial NOT NULL,
+    start_ms INT NOT NULL,
+    end_ms INT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT reciter_segment_id PRIMARY KEY (id),
+    CONSTRAINT fk_quran_reciter_segment_creator_user_id FOREIGN KEY (creator_user_id) REFERENCES app_users (id),
+    CONSTRAINT fk_segment_reciter_ayah FOREIGN KEY (reciter_ayah_id) REFERENCES quran_reciters_ayahs (id) on delete cascade,
+    CONSTRAINT fk_segment_word FOREIGN KEY (word_id) REFERENCES quran_words (id) on delete cascade,
+    CONSTRAINT segment_time CHECK (start_ms >= 0 AND end_ms >= start_ms)
+);
+
+CREATE INDEX quran_reciters_segments_reciter_ayah ON quran_reciters_segments (reciter_ayah_id);
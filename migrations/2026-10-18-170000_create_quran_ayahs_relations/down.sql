@@ -0,0 +1 @@
+DROP TABLE quran_ayahs_relations;
@@ -0,0 +1,19 @@
+CREATE TABLE quran_ayahs_relations (
+    id serial NOT NULL,
+    uuid uuid DEFAULT uuid_generate_v4 () NOT NULL,
+    creator_user_id serial NOT NULL,
+    ayah_id serial NOT NULL,
+    related_ayah_id serial NOT NULL,
+    relation_type VARCHAR(50) NOT NULL,
+    source TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT ayah_relation_id PRIMARY KEY (id),
+    CONSTRAINT fk_quran_ayah_relation_creator_user_id FOREIGN KEY (creator_user_id) REFERENCES app_users (id),
+    CONSTRAINT fk_relation_ayah FOREIGN KEY (ayah_id) REFERENCES quran_ayahs (id) on delete cascade,
+    CONSTRAINT fk_relation_related_ayah FOREIGN KEY (related_ayah_id) REFERENCES quran_ayahs (id) on delete cascade,
+    CONSTRAINT relation_not_self CHECK (ayah_id <> related_ayah_id),
+    UNIQUE (ayah_id, related_ayah_id, relation_type)
+);
+
+CREATE INDEX quran_ayahs_relations_related_ayah ON quran_ayahs_relations (related_ayah_id);
@@ -0,0 +1,4 @@
+ALTER TABLE quran_surahs
+    DROP CONSTRAINT quran_surahs_period_check,
+    DROP COLUMN revelation_order,
+    DROP COLUMN ruku_count;
@@ -0,0 +1,23 @@
+-- Period was free text, map the known spellings to the typed values
+UPDATE quran_surahs SET period = LOWER(TRIM(period)) WHERE period IS NOT NULL;
+UPDATE quran_surahs SET period = 'meccan' WHERE period IN ('makki', 'mecca', 'makkah');
+UPDATE quran_surahs SET period = 'medinan' WHERE period IN ('madani', 'medina', 'madinah');
+
+-- Other values are not dropped, they must be fixed by hand before the migration
+DO $$
+DECLARE
+    unmapped TEXT;
+BEGIN
+    SELECT string_agg(DISTINCT period, ', ') INTO unmapped
+    FROM quran_surahs
+    WHERE period NOT IN ('meccan', 'medinan');
+
+    IF unmapped IS NOT NULL THEN
+        RAISE EXCEPTION 'quran_surahs.period has unmapped values: %', unmapped;
+    END IF;
+END $$;
+
+ALTER TABLE quran_surahs
+    ADD CONSTRAINT quran_surahs_period_check CHECK (period IN ('meccan', 'medinan')),
+    ADD COLUMN revelation_order INT,
+    ADD COLUMN ruku_count INT;
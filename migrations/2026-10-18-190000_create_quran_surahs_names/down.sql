@@ -0,0 +1 @@
+DROP TABLE quran_surahs_names;
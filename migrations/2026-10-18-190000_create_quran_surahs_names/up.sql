@@ -0,0 +1,15 @@
+CREATE TABLE quran_surahs_names (
+    id serial NOT NULL,
+    uuid uuid DEFAULT uuid_generate_v4 () NOT NULL,
+    creator_user_id serial NOT NULL,
+    surah_id serial NOT NULL,
+    language VARCHAR(3) NOT NULL,
+    pronunciation TEXT,
+    transliteration TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT surah_name_id PRIMARY KEY (id),
+    CONSTRAINT fk_quran_surah_name_creator_user_id FOREIGN KEY (creator_user_id) REFERENCES app_users (id),
+    CONSTRAINT fk_surah_name_surah FOREIGN KEY (surah_id) REFERENCES quran_surahs (id) on delete cascade,
+    UNIQUE (surah_id, language)
+);
@@ -0,0 +1,2 @@
+DROP TABLE quran_plans_days;
+DROP TABLE quran_plans;
@@ -0,0 +1,36 @@
+CREATE TABLE quran_plans (
+    id serial NOT NULL,
+    uuid uuid DEFAULT uuid_generate_v4 () NOT NULL,
+    creator_user_id serial NOT NULL,
+    mushaf_id serial NOT NULL,
+    days INT NOT NULL,
+    snap VARCHAR(20) NOT NULL,
+    breaker_name VARCHAR(256),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT plan_id PRIMARY KEY (id),
+    CONSTRAINT fk_quran_plan_creator_user_id FOREIGN KEY (creator_user_id) REFERENCES app_users (id) on delete cascade,
+    CONSTRAINT fk_plan_mushaf FOREIGN KEY (mushaf_id) REFERENCES quran_mushafs (id) on delete cascade,
+    CONSTRAINT quran_plans_days_check CHECK (days > 0),
+    CONSTRAINT quran_plans_snap_check CHECK (snap IN ('ayah', 'surah', 'breaker'))
+);
+
+CREATE TABLE quran_plans_days (
+    id serial NOT NULL,
+    uuid uuid DEFAULT uuid_generate_v4 () NOT NULL,
+    creator_user_id serial NOT NULL,
+    plan_id serial NOT NULL,
+    day INT NOT NULL,
+    first_ayah_id serial NOT NULL,
+    last_ayah_id serial NOT NULL,
+    words INT NOT NULL,
+    completed_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT plan_day_id PRIMARY KEY (id),
+    CONSTRAINT fk_quran_plan_day_creator_user_id FOREIGN KEY (creator_user_id) REFERENCES app_users (id) on delete cascade,
+    CONSTRAINT fk_plan_day_plan FOREIGN KEY (plan_id) REFERENCES quran_plans (id) on delete cascade,
+    CONSTRAINT fk_plan_day_first_ayah FOREIGN KEY (first_ayah_id) REFERENCES quran_ayahs (id) on delete cascade,
+    CONSTRAINT fk_plan_day_last_ayah FOREIGN KEY (last_ayah_id) REFERENCES quran_ayahs (id) on delete cascade,
+    UNIQUE (plan_id, day)
+);
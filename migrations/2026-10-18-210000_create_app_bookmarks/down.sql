@@ -0,0 +1,2 @@
+DROP TABLE app_reading_positions;
+DROP TABLE app_bookmarks;
@@ -0,0 +1,36 @@
+-- Bookmarks are synced by the transaction of their last change, not by a time:
+-- a time is taken before the commit, so a later commit can have an older time
+CREATE TABLE app_bookmarks (
+    id serial NOT NULL,
+    uuid uuid DEFAULT uuid_generate_v4 () NOT NULL,
+    account_id serial NOT NULL,
+    ayah_id serial NOT NULL,
+    note TEXT,
+    tags TEXT[] NOT NULL DEFAULT '{}',
+    colour VARCHAR(20),
+    deleted_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    synced_transaction BIGINT NOT NULL DEFAULT txid_current(),
+    CONSTRAINT bookmark_id PRIMARY KEY (id),
+    CONSTRAINT fk_bookmark_account FOREIGN KEY (account_id) REFERENCES app_accounts (id) on delete cascade,
+    CONSTRAINT fk_bookmark_ayah FOREIGN KEY (ayah_id) REFERENCES quran_ayahs (id) on delete cascade,
+    UNIQUE (uuid)
+);
+
+CREATE INDEX app_bookmarks_account_synced_transaction ON app_bookmarks (account_id, synced_transaction);
+
+CREATE TABLE app_reading_positions (
+    id serial NOT NULL,
+    uuid uuid DEFAULT uuid_generate_v4 () NOT NULL,
+    account_id serial NOT NULL,
+    mushaf_id serial NOT NULL,
+    ayah_id serial NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT reading_position_id PRIMARY KEY (id),
+    CONSTRAINT fk_reading_position_account FOREIGN KEY (account_id) REFERENCES app_accounts (id) on delete cascade,
+    CONSTRAINT fk_reading_position_mushaf FOREIGN KEY (mushaf_id) REFERENCES quran_mushafs (id) on delete cascade,
+    CONSTRAINT fk_reading_position_ayah FOREIGN KEY (ayah_id) REFERENCES quran_ayahs (id) on delete cascade,
+    UNIQUE (account_id, mushaf_id)
+);
@@ -0,0 +1,2 @@
+DROP TABLE app_collections_ayahs;
+DROP TABLE app_collections;
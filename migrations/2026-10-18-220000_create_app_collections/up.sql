@@ -0,0 +1,35 @@
+CREATE TABLE app_collections (
+    id serial NOT NULL,
+    uuid uuid DEFAULT uuid_generate_v4 () NOT NULL,
+    creator_user_id serial NOT NULL,
+    owner_account_id serial NOT NULL,
+    organization_account_id INT REFERENCES app_accounts (id) on delete set null,
+    name VARCHAR(200) NOT NULL,
+    description TEXT,
+    public BOOLEAN NOT NULL DEFAULT false,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT collection_id PRIMARY KEY (id),
+    CONSTRAINT fk_collection_creator_user_id FOREIGN KEY (creator_user_id) REFERENCES app_users (id),
+    CONSTRAINT fk_collection_owner FOREIGN KEY (owner_account_id) REFERENCES app_accounts (id) on delete cascade,
+    UNIQUE (uuid)
+);
+
+CREATE TABLE app_collections_ayahs (
+    id serial NOT NULL,
+    uuid uuid DEFAULT uuid_generate_v4 () NOT NULL,
+    creator_user_id serial NOT NULL,
+    collection_id serial NOT NULL,
+    position INT NOT NULL,
+    first_ayah_id serial NOT NULL,
+    last_ayah_id serial NOT NULL,
+    note TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT collection_ayah_id PRIMARY KEY (id),
+    CONSTRAINT fk_collection_ayah_creator_user_id FOREIGN KEY (creator_user_id) REFERENCES app_users (id),
+    CONSTRAINT fk_collection_ayah_collection FOREIGN KEY (collection_id) REFERENCES app_collections (id) on delete cascade,
+    CONSTRAINT fk_collection_ayah_first_ayah FOREIGN KEY (first_ayah_id) REFERENCES quran_ayahs (id) on delete cascade,
+    CONSTRAINT fk_collection_ayah_last_ayah FOREIGN KEY (last_ayah_id) REFERENCES quran_ayahs (id) on delete cascade,
+    UNIQUE (collection_id, position)
+);
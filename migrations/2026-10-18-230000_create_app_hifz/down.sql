@@ -0,0 +1,3 @@
+DROP TABLE app_hifz_mistakes;
+DROP TABLE app_hifz_reviews;
+DROP TABLE app_hifz_ranges;
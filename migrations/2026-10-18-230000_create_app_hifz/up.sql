@@ -0,0 +1,48 @@
+CREATE TABLE app_hifz_ranges (
+    id serial NOT NULL,
+    uuid uuid DEFAULT uuid_generate_v4 () NOT NULL,
+    account_id serial NOT NULL,
+    first_ayah_id serial NOT NULL,
+    last_ayah_id serial NOT NULL,
+    repetitions INT NOT NULL DEFAULT 0,
+    interval_days INT NOT NULL DEFAULT 0,
+    ease_factor DOUBLE PRECISION NOT NULL DEFAULT 2.5,
+    due_on DATE NOT NULL DEFAULT CURRENT_DATE,
+    last_reviewed_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT hifz_range_id PRIMARY KEY (id),
+    CONSTRAINT fk_hifz_range_account FOREIGN KEY (account_id) REFERENCES app_accounts (id) on delete cascade,
+    CONSTRAINT fk_hifz_range_first_ayah FOREIGN KEY (first_ayah_id) REFERENCES quran_ayahs (id) on delete cascade,
+    CONSTRAINT fk_hifz_range_last_ayah FOREIGN KEY (last_ayah_id) REFERENCES quran_ayahs (id) on delete cascade,
+    UNIQUE (uuid)
+);
+
+CREATE INDEX app_hifz_ranges_account_due_on ON app_hifz_ranges (account_id, due_on);
+
+CREATE TABLE app_hifz_reviews (
+    id serial NOT NULL,
+    uuid uuid DEFAULT uuid_generate_v4 () NOT NULL,
+    range_id serial NOT NULL,
+    grade INT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT hifz_review_id PRIMARY KEY (id),
+    CONSTRAINT fk_hifz_review_range FOREIGN KEY (range_id) REFERENCES app_hifz_ranges (id) on delete cascade,
+    CONSTRAINT app_hifz_reviews_grade_check CHECK (grade BETWEEN 0 AND 5),
+    UNIQUE (uuid)
+);
+
+CREATE TABLE app_hifz_mistakes (
+    id serial NOT NULL,
+    review_id serial NOT NULL,
+    word_id serial NOT NULL,
+    mistakes INT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT hifz_mistake_id PRIMARY KEY (id),
+    CONSTRAINT fk_hifz_mistake_review FOREIGN KEY (review_id) REFERENCES app_hifz_reviews (id) on delete cascade,
+    CONSTRAINT fk_hifz_mistake_word FOREIGN KEY (word_id) REFERENCES quran_words (id) on delete cascade,
+    CONSTRAINT app_hifz_mistakes_mistakes_check CHECK (mistakes > 0),
+    UNIQUE (review_id, word_id)
+);
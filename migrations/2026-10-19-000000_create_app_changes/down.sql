@@ -0,0 +1,7 @@
+DROP TRIGGER quran_translations_ayahs_changes ON quran_translations_ayahs;
+DROP TRIGGER quran_ayahs_breakers_changes ON quran_ayahs_breakers;
+DROP TRIGGER quran_words_changes ON quran_words;
+DROP TRIGGER quran_ayahs_changes ON quran_ayahs;
+DROP TRIGGER quran_surahs_changes ON quran_surahs;
+DROP FUNCTION app_record_change;
+DROP TABLE app_changes;
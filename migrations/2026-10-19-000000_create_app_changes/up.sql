@@ -0,0 +1,63 @@
+-- Change feed of the synced tables
+--
+-- transaction_id orders the changes by their transaction, changes of a transaction
+-- are visible together after its commit
+CREATE TABLE app_changes (
+    id BIGSERIAL NOT NULL,
+    transaction_id BIGINT NOT NULL DEFAULT txid_current(),
+    table_name VARCHAR(64) NOT NULL,
+    operation VARCHAR(10) NOT NULL,
+    row_uuid uuid NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT change_id PRIMARY KEY (id),
+    CONSTRAINT app_changes_operation_check CHECK (operation IN ('insert', 'update', 'delete'))
+);
+
+CREATE INDEX app_changes_cursor ON app_changes (transaction_id, id);
+
+CREATE FUNCTION app_record_change() RETURNS trigger AS $$
+BEGIN
+    IF TG_OP = 'DELETE' THEN
+        INSERT INTO app_changes (table_name, operation, row_uuid)
+        VALUES (TG_TABLE_NAME, 'delete', OLD.uuid);
+
+        RETURN OLD;
+    END IF;
+
+    INSERT INTO app_changes (table_name, operation, row_uuid)
+    VALUES (TG_TABLE_NAME, lower(TG_OP), NEW.uuid);
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER quran_surahs_changes AFTER INSERT OR UPDATE OR DELETE ON quran_surahs
+    FOR EACH ROW EXECUTE FUNCTION app_record_change();
+
+CREATE TRIGGER quran_ayahs_changes AFTER INSERT OR UPDATE OR DELETE ON quran_ayahs
+    FOR EACH ROW EXECUTE FUNCTION app_record_change();
+
+CREATE TRIGGER quran_words_changes AFTER INSERT OR UPDATE OR DELETE ON quran_words
+    FOR EACH ROW EXECUTE FUNCTION app_record_change();
+
+CREATE TRIGGER quran_ayahs_breakers_changes AFTER INSERT OR UPDATE OR DELETE ON quran_ayahs_breakers
+    FOR EACH ROW EXECUTE FUNCTION app_record_change();
+
+CREATE TRIGGER quran_translations_ayahs_changes AFTER INSERT OR UPDATE OR DELETE ON quran_translations_ayahs
+    FOR EACH ROW EXECUTE FUNCTION app_record_change();
+
+-- The existing rows, so the first sync gets everything
+INSERT INTO app_changes (table_name, operation, row_uuid)
+SELECT 'quran_surahs', 'insert', uuid FROM quran_surahs ORDER BY id;
+
+INSERT INTO app_changes (table_name, operation, row_uuid)
+SELECT 'quran_ayahs', 'insert', uuid FROM quran_ayahs ORDER BY id;
+
+INSERT INTO app_changes (table_name, operation, row_uuid)
+SELECT 'quran_words', 'insert', uuid FROM quran_words ORDER BY id;
+
+INSERT INTO app_changes (table_name, operation, row_uuid)
+SELECT 'quran_ayahs_breakers', 'insert', uuid FROM quran_ayahs_breakers ORDER BY id;
+
+INSERT INTO app_changes (table_name, operation, row_uuid)
+SELECT 'quran_translations_ayahs', 'insert', uuid FROM quran_translations_ayahs ORDER BY id;
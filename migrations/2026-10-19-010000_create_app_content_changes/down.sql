@@ -0,0 +1,25 @@
+DROP TRIGGER app_phrase_translations_content_change ON app_phrase_translations;
+DROP TRIGGER app_phrases_content_change ON app_phrases;
+DROP TRIGGER app_user_names_content_change ON app_user_names;
+DROP TRIGGER app_accounts_content_change ON app_accounts;
+DROP TRIGGER quran_reciters_segments_content_change ON quran_reciters_segments;
+DROP TRIGGER quran_reciters_ayahs_content_change ON quran_reciters_ayahs;
+DROP TRIGGER quran_reciters_content_change ON quran_reciters;
+DROP TRIGGER quran_translations_ayahs_content_change ON quran_translations_ayahs;
+DROP TRIGGER quran_translations_content_change ON quran_translations;
+DROP TRIGGER quran_words_transliterations_content_change ON quran_words_transliterations;
+DROP TRIGGER quran_words_tajweed_content_change ON quran_words_tajweed;
+DROP TRIGGER quran_words_renditions_content_change ON quran_words_renditions;
+DROP TRIGGER quran_words_breakers_content_change ON quran_words_breakers;
+DROP TRIGGER quran_words_content_change ON quran_words;
+DROP TRIGGER quran_ayahs_relations_content_change ON quran_ayahs_relations;
+DROP TRIGGER quran_ayahs_breakers_content_change ON quran_ayahs_breakers;
+DROP TRIGGER quran_ayahs_content_change ON quran_ayahs;
+DROP TRIGGER quran_surahs_names_content_change ON quran_surahs_names;
+DROP TRIGGER quran_surahs_content_change ON quran_surahs;
+DROP TRIGGER quran_mushafs_lines_content_change ON quran_mushafs_lines;
+DROP TRIGGER quran_mushafs_checksums_content_change ON quran_mushafs_checksums;
+DROP TRIGGER quran_mushafs_alignments_content_change ON quran_mushafs_alignments;
+DROP TRIGGER quran_mushafs_content_change ON quran_mushafs;
+DROP FUNCTION app_record_content_change;
+DROP TABLE app_content_changes;
@@ -0,0 +1,91 @@
+-- Version of the public content, used for the ETags of the responses
+--
+-- The content version is derived from the transactions that changed the content,
+-- so the writers don't wait on a single version row
+--
+-- Every writing transaction inserts its own row once, the version is the newest
+-- transaction that is older than every running transaction (see http_cache.rs)
+CREATE TABLE app_content_changes (
+    transaction_id BIGINT NOT NULL DEFAULT txid_current(),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT content_change_id PRIMARY KEY (transaction_id)
+);
+
+INSERT INTO app_content_changes DEFAULT VALUES;
+
+CREATE FUNCTION app_record_content_change() RETURNS trigger AS $$
+BEGIN
+    INSERT INTO app_content_changes DEFAULT VALUES ON CONFLICT DO NOTHING;
+
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER quran_mushafs_content_change AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE ON quran_mushafs
+    FOR EACH STATEMENT EXECUTE FUNCTION app_record_content_change();
+
+CREATE TRIGGER quran_mushafs_alignments_content_change AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE ON quran_mushafs_alignments
+    FOR EACH STATEMENT EXECUTE FUNCTION app_record_content_change();
+
+CREATE TRIGGER quran_mushafs_checksums_content_change AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE ON quran_mushafs_checksums
+    FOR EACH STATEMENT EXECUTE FUNCTION app_record_content_change();
+
+CREATE TRIGGER quran_mushafs_lines_content_change AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE ON quran_mushafs_lines
+    FOR EACH STATEMENT EXECUTE FUNCTION app_record_content_change();
+
+CREATE TRIGGER quran_surahs_content_change AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE ON quran_surahs
+    FOR EACH STATEMENT EXECUTE FUNCTION app_record_content_change();
+
+CREATE TRIGGER quran_surahs_names_content_change AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE ON quran_surahs_names
+    FOR EACH STATEMENT EXECUTE FUNCTION app_record_content_change();
+
+CREATE TRIGGER quran_ayahs_content_change AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE ON quran_ayahs
+    FOR EACH STATEMENT EXECUTE FUNCTION app_record_content_change();
+
+CREATE TRIGGER quran_ayahs_breakers_content_change AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE ON quran_ayahs_breakers
+    FOR EACH STATEMENT EXECUTE FUNCTION app_record_content_change();
+
+CREATE TRIGGER quran_ayahs_relations_content_change AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE ON quran_ayahs_relations
+    FOR EACH STATEMENT EXECUTE FUNCTION app_record_content_change();
+
+CREATE TRIGGER quran_words_content_change AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE ON quran_words
+    FOR EACH STATEMENT EXECUTE FUNCTION app_record_content_change();
+
+CREATE TRIGGER quran_words_breakers_content_change AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE ON quran_words_breakers
+    FOR EACH STATEMENT EXECUTE FUNCTION app_record_content_change();
+
+CREATE TRIGGER quran_words_renditions_content_change AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE ON quran_words_renditions
+    FOR EACH STATEMENT EXECUTE FUNCTION app_record_content_change();
+
+CREATE TRIGGER quran_words_tajweed_content_change AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE ON quran_words_tajweed
+    FOR EACH STATEMENT EXECUTE FUNCTION app_record_content_change();
+
+CREATE TRIGGER quran_words_transliterations_content_change AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE ON quran_words_transliterations
+    FOR EACH STATEMENT EXECUTE FUNCTION app_record_content_change();
+
+CREATE TRIGGER quran_translations_content_change AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE ON quran_translations
+    FOR EACH STATEMENT EXECUTE FUNCTION app_record_content_change();
+
+CREATE TRIGGER quran_translations_ayahs_content_change AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE ON quran_translations_ayahs
+    FOR EACH STATEMENT EXECUTE FUNCTION app_record_content_change();
+
+CREATE TRIGGER quran_reciters_content_change AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE ON quran_reciters
+    FOR EACH STATEMENT EXECUTE FUNCTION app_record_content_change();
+
+CREATE TRIGGER quran_reciters_ayahs_content_change AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE ON quran_reciters_ayahs
+    FOR EACH STATEMENT EXECUTE FUNCTION app_record_content_change();
+
+CREATE TRIGGER quran_reciters_segments_content_change AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE ON quran_reciters_segments
+    FOR EACH STATEMENT EXECUTE FUNCTION app_record_content_change();
+
+CREATE TRIGGER app_accounts_content_change AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE ON app_accounts
+    FOR EACH STATEMENT EXECUTE FUNCTION app_record_content_change();
+
+CREATE TRIGGER app_user_names_content_change AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE ON app_user_names
+    FOR EACH STATEMENT EXECUTE FUNCTION app_record_content_change();
+
+CREATE TRIGGER app_phrases_content_change AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE ON app_phrases
+    FOR EACH STATEMENT EXECUTE FUNCTION app_record_content_change();
+
+CREATE TRIGGER app_phrase_translations_content_change AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE ON app_phrase_translations
+    FOR EACH STATEMENT EXECUTE FUNCTION app_record_content_change();
@@ -0,0 +1,197 @@
+use std::collections::HashMap;
+
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::models::QuranMushafAlignment;
+use crate::verse_key::{mushaf_ayah_ids, VerseKey};
+
+/// Inclusive range of the word positions in an ayah
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct WordRange {
+    pub from: i32,
+    pub to: i32,
+}
+
+impl WordRange {
+    /// Creates the range from the nullable table columns
+    pub fn from_fields(from: Option<i32>, to: Option<i32>) -> Option<Self> {
+        Some(Self {
+            from: from?,
+            to: to?,
+        })
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.from > 0 && self.from <= self.to
+    }
+}
+
+/// The place of (a part of) an ayah in the other mushaf
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AlignedVerse {
+    /// Words of the source ayah, None means the whole ayah
+    pub source_words: Option<WordRange>,
+
+    pub key: VerseKey,
+
+    /// Words of the target ayah, None means the whole ayah
+    pub words: Option<WordRange>,
+}
+
+/// Ayah mapping between two mushafs with different counting systems
+///
+/// Only the ayahs that are counted differently need to be aligned,
+/// every other ayah keeps its verse key in the other mushaf
+#[derive(Debug, Default)]
+pub struct Alignment {
+    verses: HashMap<VerseKey, Vec<AlignedVerse>>,
+}
+
+impl Alignment {
+    pub fn new<I>(rows: I) -> Self
+    where
+        I: IntoIterator<Item = (VerseKey, AlignedVerse)>,
+    {
+        let mut verses: HashMap<VerseKey, Vec<AlignedVerse>> = HashMap::new();
+
+        for (source, aligned) in rows {
+            verses.entry(source).or_default().push(aligned);
+        }
+
+        // Parts of a splitted ayah must be in the reading order
+        for parts in verses.values_mut() {
+            parts.sort_by_key(|part| (part.source_words, part.key));
+        }
+
+        Self { verses }
+    }
+
+    /// Loads the alignment from source mushaf to the target mushaf
+    ///
+    /// The alignment tables imported in the reverse direction are used too
+    pub fn load(
+        conn: &mut PgConnection,
+        source_mushaf: i32,
+        target_mushaf: i32,
+    ) -> QueryResult<Self> {
+        use crate::schema::quran_mushafs_alignments::dsl::{
+            quran_mushafs_alignments, source_mushaf_id, target_mushaf_id,
+        };
+
+        let rows: Vec<QuranMushafAlignment> = quran_mushafs_alignments
+            .filter(
+                source_mushaf_id
+                    .eq(source_mushaf)
+                    .and(target_mushaf_id.eq(target_mushaf)),
+            )
+            .or_filter(
+                source_mushaf_id
+                    .eq(target_mushaf)
+                    .and(target_mushaf_id.eq(source_mushaf)),
+            )
+            .get_results(conn)?;
+
+        if rows.is_empty() {
+            return Ok(Self::default());
+        }
+
+        let ayah_keys: HashMap<i32, VerseKey> = mushaf_ayah_ids(conn, source_mushaf)?
+            .into_iter()
+            .chain(mushaf_ayah_ids(conn, target_mushaf)?)
+            .map(|(key, id)| (id, key))
+            .collect();
+
+        Ok(Self::new(rows.into_iter().filter_map(|row| {
+            let source = WordRange::from_fields(row.source_word_from, row.source_word_to);
+            let target = WordRange::from_fields(row.target_word_from, row.target_word_to);
+            let source_key = *ayah_keys.get(&row.source_ayah_id)?;
+            let target_key = *ayah_keys.get(&row.target_ayah_id)?;
+
+            if row.source_mushaf_id == source_mushaf {
+                Some((
+                    source_key,
+                    AlignedVerse {
+                        source_words: source,
+                        key: target_key,
+                        words: target,
+                    },
+                ))
+            } else {
+                Some((
+                    target_key,
+                    AlignedVerse {
+                        source_words: target,
+                        key: source_key,
+                        words: source,
+                    },
+                ))
+            }
+        })))
+    }
+
+    /// Returns the verses of the target mushaf that the ayah is aligned to
+    pub fn translate(&self, key: VerseKey) -> Vec<AlignedVerse> {
+        match self.verses.get(&key) {
+            Some(parts) => parts.clone(),
+
+            None => vec![AlignedVerse {
+                source_words: None,
+                key,
+                words: None,
+            }],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(surah: i32, ayah: i32) -> VerseKey {
+        VerseKey { surah, ayah }
+    }
+
+    #[test]
+    fn test_translate_not_aligned_ayah() {
+        let alignment = Alignment::default();
+
+        assert_eq!(
+            alignment.translate(key(2, 255)),
+            vec![AlignedVerse {
+                source_words: None,
+                key: key(2, 255),
+                words: None
+            }]
+        );
+    }
+
+    #[test]
+    fn test_translate_splitted_ayah() {
+        let second = AlignedVerse {
+            source_words: Some(WordRange { from: 5, to: 9 }),
+            key: key(1, 7),
+            words: None,
+        };
+        let first = AlignedVerse {
+            source_words: Some(WordRange { from: 1, to: 4 }),
+            key: key(1, 6),
+            words: None,
+        };
+
+        let alignment = Alignment::new(vec![
+            (key(1, 6), second.clone()),
+            (key(1, 6), first.clone()),
+        ]);
+
+        assert_eq!(alignment.translate(key(1, 6)), vec![first, second]);
+    }
+
+    #[test]
+    fn test_word_range() {
+        assert_eq!(WordRange::from_fields(Some(1), None), None);
+        assert!(WordRange { from: 1, to: 1 }.is_valid());
+        assert!(!WordRange { from: 0, to: 1 }.is_valid());
+        assert!(!WordRange { from: 3, to: 2 }.is_valid());
+    }
+}
@@ -2,8 +2,8 @@ use std::net::SocketAddr;
 use std::sync::Arc;
 
 use crate::error::{RouterError, RouterErrorDetail};
-use crate::models::{Organization, User};
-use crate::select_model::SelectModel;
+use crate::models::{Collection, Organization, User};
+use crate::select_model::{SelectModel, SelectModelByUuid};
 use crate::DbPool;
 use actix_web::http::header::HeaderMap;
 use actix_web::http::Uri;
@@ -150,10 +150,7 @@ impl CheckPermission for AuthZController {
 
         // First get the required Resource as Model
         let model = self
-            .get_model(
-                &path.controller.unwrap().clone(),
-                path.id.unwrap().clone().parse().unwrap(),
-            )
+            .get_model(&path.controller.unwrap().clone(), &path.id.unwrap())
             .await;
 
         // We Got the model now we check every condition
@@ -198,17 +195,23 @@ impl GetModel<ModelAttrib, i32> for AuthZController {
     async fn get_model(
         &self,
         resource_name: &str,
-        resource_id: u32,
+        resource_id: &str,
     ) -> Box<dyn ModelPermission<ModelAttrib, i32>> {
         //let mut conn = self.db_pool.get().unwrap();
-        let resource_id = resource_id as i32;
 
         // Resource must have been impl the Model permission trait
         let model: Box<dyn ModelPermission<ModelAttrib, i32>> = match resource_name {
-            "user" => Box::new(User::from_id(self.db_pool.clone(), resource_id).await),
+            "user" => {
+                Box::new(User::from_id(self.db_pool.clone(), resource_id.parse().unwrap()).await)
+            }
+
+            "organization" => Box::new(
+                Organization::from_id(self.db_pool.clone(), resource_id.parse().unwrap()).await,
+            ),
 
-            "organization" => {
-                Box::new(Organization::from_id(self.db_pool.clone(), resource_id).await)
+            // Collections are selected by uuid
+            "collection" => {
+                Box::new(Option::<Collection>::from_uuid(self.db_pool.clone(), resource_id).await)
             }
 
             _ => todo!(),
@@ -384,6 +387,17 @@ impl ModelPermission<ModelAttrib, i32> for Organization {
     }
 }
 
+/// A collection that is not found has no owner
+#[async_trait]
+impl ModelPermission<ModelAttrib, i32> for Option<Collection> {
+    async fn get_attr(&self, name: ModelAttrib) -> Option<i32> {
+        match name {
+            ModelAttrib::Owner => self.as_ref().map(|c| c.owner_account_id),
+            ModelAttrib::Login => None,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::{Condition, Login, ModelAttrib, Owner};
@@ -393,10 +407,10 @@ mod tests {
         let login = Login {};
 
         // This should return true
-        assert_eq!(login.validate(None, Some("user"), ""), true);
+        assert!(login.validate(None, Some("user"), ""));
 
         // This should return false
-        assert_eq!(login.validate(None, None, ""), false);
+        assert!(!login.validate(None, None, ""));
     }
 
     #[test]
@@ -404,12 +418,12 @@ mod tests {
         let owner = Owner {};
 
         // This should return true
-        assert_eq!(owner.validate(None, Some("user"), ""), true);
+        assert!(owner.validate(None, Some("user"), ""));
 
         // This should return false
-        assert_eq!(owner.validate(None, None, ""), false);
+        assert!(!owner.validate(None, None, ""));
 
-        assert_eq!(owner.validate(Some(1), Some("1"), "true"), true);
+        assert!(owner.validate(Some(1), Some("1"), "true"));
     }
 
     #[test]
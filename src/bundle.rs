@@ -0,0 +1,614 @@
+//! Offline SQLite bundle of a mushaf
+//!
+//! The bundle is a self-contained database for the apps, with the mushaf text
+//! and its script renditions, the selected translations and optionally the breakers, phrases and surah names
+
+use std::{fmt::Display, fs, io, path::Path};
+
+use chrono::Utc;
+use diesel::prelude::*;
+use log::error;
+use rusqlite::params;
+use uuid::Uuid;
+
+use crate::error::RouterError;
+use crate::statistics::normalize_word;
+
+/// Version of the bundle tables, increased on every change of them
+pub const BUNDLE_SCHEMA_VERSION: u32 = 3;
+
+const SCHEMA: &str = "
+CREATE TABLE metadata (
+    key TEXT PRIMARY KEY,
+    value TEXT NOT NULL
+);
+
+CREATE TABLE surahs (
+    id INTEGER PRIMARY KEY,
+    uuid TEXT NOT NULL,
+    number INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    period TEXT,
+    revelation_order INTEGER
+);
+
+CREATE TABLE ayahs (
+    id INTEGER PRIMARY KEY,
+    uuid TEXT NOT NULL,
+    surah_id INTEGER NOT NULL REFERENCES surahs (id),
+    number INTEGER NOT NULL,
+    sajdah TEXT,
+    is_bismillah INTEGER NOT NULL,
+    bismillah_text TEXT
+);
+
+CREATE TABLE words (
+    id INTEGER PRIMARY KEY,
+    uuid TEXT NOT NULL,
+    ayah_id INTEGER NOT NULL REFERENCES ayahs (id),
+    position INTEGER NOT NULL,
+    word TEXT NOT NULL
+);
+
+CREATE TABLE words_renditions (
+    word_id INTEGER NOT NULL REFERENCES words (id),
+    script TEXT NOT NULL,
+    text TEXT NOT NULL,
+    PRIMARY KEY (word_id, script)
+);
+
+CREATE TABLE translations (
+    id INTEGER PRIMARY KEY,
+    uuid TEXT NOT NULL,
+    language TEXT NOT NULL,
+    source TEXT
+);
+
+CREATE TABLE translations_ayahs (
+    translation_id INTEGER NOT NULL REFERENCES translations (id),
+    ayah_id INTEGER NOT NULL REFERENCES ayahs (id),
+    text TEXT NOT NULL,
+    bismillah TEXT,
+    PRIMARY KEY (translation_id, ayah_id)
+);
+
+CREATE TABLE breakers (
+    ayah_id INTEGER NOT NULL REFERENCES ayahs (id),
+    name TEXT NOT NULL
+);
+
+CREATE TABLE surahs_names (
+    surah_id INTEGER NOT NULL REFERENCES surahs (id),
+    language TEXT NOT NULL,
+    pronunciation TEXT,
+    transliteration TEXT,
+    PRIMARY KEY (surah_id, language)
+);
+
+CREATE TABLE phrases (
+    phrase TEXT NOT NULL,
+    language TEXT NOT NULL,
+    text TEXT NOT NULL,
+    PRIMARY KEY (phrase, language)
+);
+
+-- Text of the ayahs and their translations, translation_id is null for the mushaf text
+-- The mushaf text is without the vowel and recitation marks, so the queries are matched
+-- without them (the queries must have their marks removed too)
+CREATE VIRTUAL TABLE ayahs_fts USING fts5 (
+    text,
+    ayah_id UNINDEXED,
+    translation_id UNINDEXED,
+    tokenize = 'unicode61'
+);
+";
+
+/// Created after the inserts, so the inserts are not slowed down by them
+const INDEXES: &str = "
+CREATE UNIQUE INDEX surahs_uuid ON surahs (uuid);
+CREATE UNIQUE INDEX surahs_number ON surahs (number);
+CREATE UNIQUE INDEX ayahs_uuid ON ayahs (uuid);
+CREATE UNIQUE INDEX ayahs_surah_number ON ayahs (surah_id, number);
+CREATE UNIQUE INDEX words_uuid ON words (uuid);
+CREATE UNIQUE INDEX words_ayah_position ON words (ayah_id, position);
+CREATE INDEX translations_ayahs_ayah ON translations_ayahs (ayah_id);
+CREATE INDEX breakers_name ON breakers (name, ayah_id);
+";
+
+/// id, uuid, number, name, period, revelation_order
+type SurahRow = (i32, Uuid, i32, String, Option<String>, Option<i32>);
+
+/// id, uuid, surah_id, number, sajdah, is_bismillah, bismillah_text
+type AyahRow = (i32, Uuid, i32, i32, Option<String>, bool, Option<String>);
+
+#[derive(Debug)]
+pub enum BundleError {
+    Database(diesel::result::Error),
+    Sqlite(rusqlite::Error),
+    Io(io::Error),
+}
+
+impl Display for BundleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Database(err) => write!(f, "Database error: {}", err),
+            Self::Sqlite(err) => write!(f, "SQLite error: {}", err),
+            Self::Io(err) => write!(f, "IO error: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for BundleError {}
+
+impl From<diesel::result::Error> for BundleError {
+    fn from(value: diesel::result::Error) -> Self {
+        Self::Database(value)
+    }
+}
+
+impl From<rusqlite::Error> for BundleError {
+    fn from(value: rusqlite::Error) -> Self {
+        Self::Sqlite(value)
+    }
+}
+
+impl From<io::Error> for BundleError {
+    fn from(value: io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+impl From<BundleError> for RouterError {
+    fn from(value: BundleError) -> Self {
+        match value {
+            BundleError::Database(err) => Self::from(err),
+
+            err => {
+                error!("InternalError: {}", err);
+
+                Self::from_predefined("INTERNAL_ERROR")
+            }
+        }
+    }
+}
+
+/// What the bundle contains, the mushaf text is always included
+#[derive(Debug, Clone, Default)]
+pub struct BundleOptions {
+    pub mushaf_id: i32,
+
+    /// Translations of the mushaf
+    pub translation_ids: Vec<i32>,
+    pub breakers: bool,
+    pub phrases: bool,
+    pub surah_names: bool,
+}
+
+/// Returns the ids of the translations of the mushaf by their uuid
+///
+/// Returns NotFound if a translation is not found or is of another mushaf,
+/// the repeated uuids are only counted once
+pub fn bundle_translation_ids(
+    conn: &mut PgConnection,
+    mushaf: i32,
+    uuids: &[Uuid],
+) -> QueryResult<Vec<i32>> {
+    use crate::schema::quran_translations::dsl::{id, mushaf_id, quran_translations, uuid};
+
+    let mut uuids = uuids.to_vec();
+    uuids.sort_unstable();
+    uuids.dedup();
+
+    let ids: Vec<i32> = quran_translations
+        .filter(mushaf_id.eq(mushaf))
+        .filter(uuid.eq_any(&uuids))
+        .select(id)
+        .get_results(conn)?;
+
+    if ids.len() != uuids.len() {
+        return Err(diesel::result::Error::NotFound);
+    }
+
+    Ok(ids)
+}
+
+/// Builds the bundle into the file of the path, the file is replaced if exists
+pub fn build_bundle(
+    conn: &mut PgConnection,
+    options: &BundleOptions,
+    path: &Path,
+) -> Result<(), BundleError> {
+    if path.exists() {
+        fs::remove_file(path)?;
+    }
+
+    let mut sqlite = rusqlite::Connection::open(path)?;
+    sqlite.execute_batch(SCHEMA)?;
+
+    let tx = sqlite.transaction()?;
+
+    // Every read sees the same snapshot, so a change while building can't mix in the bundle
+    conn.build_transaction()
+        .repeatable_read()
+        .read_only()
+        .run(|conn| {
+            insert_mushaf(conn, &tx, options.mushaf_id)?;
+            insert_renditions(conn, &tx, options.mushaf_id)?;
+            insert_translations(conn, &tx, options)?;
+
+            if options.breakers {
+                insert_breakers(conn, &tx, options.mushaf_id)?;
+            }
+
+            if options.surah_names {
+                insert_surah_names(conn, &tx, options.mushaf_id)?;
+            }
+
+            if options.phrases {
+                insert_phrases(conn, &tx)?;
+            }
+
+            Ok::<(), BundleError>(())
+        })?;
+
+    tx.commit()?;
+
+    sqlite.execute_batch(INDEXES)?;
+    sqlite.execute_batch("INSERT INTO ayahs_fts (ayahs_fts) VALUES ('optimize'); VACUUM;")?;
+
+    Ok(())
+}
+
+fn insert_mushaf(
+    conn: &mut PgConnection,
+    tx: &rusqlite::Transaction,
+    target_mushaf_id: i32,
+) -> Result<(), BundleError> {
+    use crate::schema::quran_ayahs::dsl::{
+        ayah_number, bismillah_text, id as ayah_id, is_bismillah, quran_ayahs, sajdah,
+        surah_id as ayah_surah_id, uuid as ayah_uuid,
+    };
+    use crate::schema::quran_mushafs::dsl::{
+        id as mushaf_id, name as mushaf_name, quran_mushafs, short_name, source,
+        uuid as mushaf_uuid,
+    };
+    use crate::schema::quran_surahs::dsl::{
+        id as surah_id, mushaf_id as surah_mushaf_id, name as surah_name, number as surah_number,
+        period, quran_surahs, revelation_order, uuid as surah_uuid,
+    };
+    use crate::schema::quran_words::dsl::{
+        ayah_id as word_ayah_id, id as word_id, quran_words, uuid as word_uuid, word,
+    };
+
+    let (m_uuid, m_short_name, m_name, m_source): (
+        Uuid,
+        Option<String>,
+        Option<String>,
+        Option<String>,
+    ) = quran_mushafs
+        .filter(mushaf_id.eq(target_mushaf_id))
+        .select((mushaf_uuid, short_name, mushaf_name, source))
+        .get_result(conn)?;
+
+    let mut metadata = tx.prepare("INSERT INTO metadata (key, value) VALUES (?1, ?2)")?;
+    for (key, value) in [
+        ("schema_version", Some(BUNDLE_SCHEMA_VERSION.to_string())),
+        ("mushaf_uuid", Some(m_uuid.to_string())),
+        ("mushaf_short_name", m_short_name),
+        ("mushaf_name", m_name),
+        ("mushaf_source", m_source),
+        ("created_at", Some(Utc::now().to_rfc3339())),
+    ] {
+        if let Some(value) = value {
+            metadata.execute(params![key, value])?;
+        }
+    }
+
+    let surahs: Vec<SurahRow> = quran_surahs
+        .filter(surah_mushaf_id.eq(target_mushaf_id))
+        .order(surah_number.asc())
+        .select((
+            surah_id,
+            surah_uuid,
+            surah_number,
+            surah_name,
+            period,
+            revelation_order,
+        ))
+        .get_results(conn)?;
+
+    let mut insert = tx.prepare(
+        "INSERT INTO surahs (id, uuid, number, name, period, revelation_order)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+    )?;
+    for (id, uuid, number, name, surah_period, order) in surahs {
+        insert.execute(params![
+            id,
+            uuid.to_string(),
+            number,
+            name,
+            surah_period,
+            order
+        ])?;
+    }
+
+    let ayahs: Vec<AyahRow> = quran_ayahs
+        .inner_join(quran_surahs)
+        .filter(surah_mushaf_id.eq(target_mushaf_id))
+        .order((surah_number.asc(), ayah_number.asc()))
+        .select((
+            ayah_id,
+            ayah_uuid,
+            ayah_surah_id,
+            ayah_number,
+            sajdah,
+            is_bismillah,
+            bismillah_text,
+        ))
+        .get_results(conn)?;
+
+    let mut insert = tx.prepare(
+        "INSERT INTO ayahs (id, uuid, surah_id, number, sajdah, is_bismillah, bismillah_text)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+    )?;
+    for (id, uuid, surah, number, ayah_sajdah, bismillah, bismillah_of_ayah) in ayahs {
+        insert.execute(params![
+            id,
+            uuid.to_string(),
+            surah,
+            number,
+            ayah_sajdah,
+            bismillah,
+            bismillah_of_ayah
+        ])?;
+    }
+
+    let words: Vec<(i32, Uuid, i32, String)> = quran_words
+        .inner_join(quran_ayahs.inner_join(quran_surahs))
+        .filter(surah_mushaf_id.eq(target_mushaf_id))
+        .order((surah_number.asc(), ayah_number.asc(), word_id.asc()))
+        .select((word_id, word_uuid, word_ayah_id, word))
+        .get_results(conn)?;
+
+    let mut insert = tx.prepare(
+        "INSERT INTO words (id, uuid, ayah_id, position, word) VALUES (?1, ?2, ?3, ?4, ?5)",
+    )?;
+    let mut insert_fts = tx.prepare("INSERT INTO ayahs_fts (text, ayah_id) VALUES (?1, ?2)")?;
+
+    // Words are in the order of the ayahs, the normalized text of an ayah
+    // is inserted when the words of the next ayah starts
+    let mut ayah: Option<(i32, Vec<String>)> = None;
+
+    for (id, uuid, word_ayah, text) in &words {
+        match &mut ayah {
+            Some((current, texts)) if current == word_ayah => texts.push(normalize_word(text)),
+
+            _ => {
+                if let Some((current, texts)) = ayah.take() {
+                    insert_fts.execute(params![texts.join(" "), current])?;
+                }
+
+                ayah = Some((*word_ayah, vec![normalize_word(text)]));
+            }
+        }
+
+        let position = ayah.as_ref().map(|(_, texts)| texts.len()).unwrap_or(1);
+
+        insert.execute(params![id, uuid.to_string(), word_ayah, position, text])?;
+    }
+
+    if let Some((current, texts)) = ayah {
+        insert_fts.execute(params![texts.join(" "), current])?;
+    }
+
+    Ok(())
+}
+
+fn insert_renditions(
+    conn: &mut PgConnection,
+    tx: &rusqlite::Transaction,
+    target_mushaf_id: i32,
+) -> Result<(), BundleError> {
+    use crate::schema::quran_ayahs::dsl::quran_ayahs;
+    use crate::schema::quran_surahs::dsl::{mushaf_id, quran_surahs};
+    use crate::schema::quran_words::dsl::quran_words;
+    use crate::schema::quran_words_renditions::dsl::{
+        quran_words_renditions, script, text, word_id,
+    };
+
+    let renditions: Vec<(i32, String, String)> = quran_words_renditions
+        .inner_join(quran_words.inner_join(quran_ayahs.inner_join(quran_surahs)))
+        .filter(mushaf_id.eq(target_mushaf_id))
+        .select((word_id, script, text))
+        .get_results(conn)?;
+
+    let mut insert =
+        tx.prepare("INSERT INTO words_renditions (word_id, script, text) VALUES (?1, ?2, ?3)")?;
+    for (word, rendition_script, rendition_text) in renditions {
+        insert.execute(params![word, rendition_script, rendition_text])?;
+    }
+
+    Ok(())
+}
+
+fn insert_translations(
+    conn: &mut PgConnection,
+    tx: &rusqlite::Transaction,
+    options: &BundleOptions,
+) -> Result<(), BundleError> {
+    use crate::schema::quran_translations::dsl::{
+        id as translation_id, language, mushaf_id, quran_translations, source,
+        uuid as translation_uuid,
+    };
+    use crate::schema::quran_translations_ayahs::dsl::{
+        ayah_id, bismillah, quran_translations_ayahs, text, translation_id as text_translation_id,
+    };
+
+    let translations: Vec<(i32, Uuid, String, Option<String>)> = quran_translations
+        .filter(mushaf_id.eq(options.mushaf_id))
+        .filter(translation_id.eq_any(&options.translation_ids))
+        .select((translation_id, translation_uuid, language, source))
+        .get_results(conn)?;
+
+    let mut insert = tx
+        .prepare("INSERT INTO translations (id, uuid, language, source) VALUES (?1, ?2, ?3, ?4)")?;
+    let mut insert_text = tx.prepare(
+        "INSERT INTO translations_ayahs (translation_id, ayah_id, text, bismillah)
+        VALUES (?1, ?2, ?3, ?4)",
+    )?;
+    let mut insert_fts =
+        tx.prepare("INSERT INTO ayahs_fts (text, ayah_id, translation_id) VALUES (?1, ?2, ?3)")?;
+
+    for (id, uuid, translation_language, translation_source) in translations {
+        insert.execute(params![
+            id,
+            uuid.to_string(),
+            translation_language,
+            translation_source
+        ])?;
+
+        // One translation at a time, so the memory is bounded to the largest translation
+        let texts: Vec<(i32, String, Option<String>)> = quran_translations_ayahs
+            .filter(text_translation_id.eq(id))
+            .select((ayah_id, text, bismillah))
+            .get_results(conn)?;
+
+        for (ayah, ayah_text, ayah_bismillah) in texts {
+            insert_text.execute(params![id, ayah, ayah_text, ayah_bismillah])?;
+            insert_fts.execute(params![ayah_text, ayah, id])?;
+        }
+    }
+
+    Ok(())
+}
+
+fn insert_breakers(
+    conn: &mut PgConnection,
+    tx: &rusqlite::Transaction,
+    target_mushaf_id: i32,
+) -> Result<(), BundleError> {
+    use crate::schema::quran_ayahs::dsl::quran_ayahs;
+    use crate::schema::quran_ayahs_breakers::dsl::{
+        ayah_id, name, owner_account_id, quran_ayahs_breakers,
+    };
+    use crate::schema::quran_surahs::dsl::{mushaf_id, quran_surahs};
+
+    // Breakers of the accounts are not bundled
+    let breakers: Vec<(i32, String)> = quran_ayahs_breakers
+        .inner_join(quran_ayahs.inner_join(quran_surahs))
+        .filter(mushaf_id.eq(target_mushaf_id))
+        .filter(owner_account_id.is_null())
+        .select((ayah_id, name))
+        .get_results(conn)?;
+
+    let mut insert = tx.prepare("INSERT INTO breakers (ayah_id, name) VALUES (?1, ?2)")?;
+    for (ayah, breaker_name) in breakers {
+        insert.execute(params![ayah, breaker_name])?;
+    }
+
+    Ok(())
+}
+
+fn insert_surah_names(
+    conn: &mut PgConnection,
+    tx: &rusqlite::Transaction,
+    target_mushaf_id: i32,
+) -> Result<(), BundleError> {
+    use crate::schema::quran_surahs::dsl::{mushaf_id, quran_surahs};
+    use crate::schema::quran_surahs_names::dsl::{
+        language, pronunciation, quran_surahs_names, surah_id, transliteration,
+    };
+
+    let names: Vec<(i32, String, Option<String>, Option<String>)> = quran_surahs_names
+        .inner_join(quran_surahs)
+        .filter(mushaf_id.eq(target_mushaf_id))
+        .select((surah_id, language, pronunciation, transliteration))
+        .get_results(conn)?;
+
+    let mut insert = tx.prepare(
+        "INSERT INTO surahs_names (surah_id, language, pronunciation, transliteration)
+        VALUES (?1, ?2, ?3, ?4)",
+    )?;
+    for (surah, name_language, name_pronunciation, name_transliteration) in names {
+        insert.execute(params![
+            surah,
+            name_language,
+            name_pronunciation,
+            name_transliteration
+        ])?;
+    }
+
+    Ok(())
+}
+
+fn insert_phrases(conn: &mut PgConnection, tx: &rusqlite::Transaction) -> Result<(), BundleError> {
+    use crate::schema::app_phrase_translations::dsl::{app_phrase_translations, language, text};
+    use crate::schema::app_phrases::dsl::{app_phrases, phrase};
+
+    let phrases: Vec<(String, String, String)> = app_phrase_translations
+        .inner_join(app_phrases)
+        .select((phrase, language, text))
+        .get_results(conn)?;
+
+    let mut insert =
+        tx.prepare("INSERT INTO phrases (phrase, language, text) VALUES (?1, ?2, ?3)")?;
+    for (phrase_name, phrase_language, phrase_text) in phrases {
+        insert.execute(params![phrase_name, phrase_language, phrase_text])?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bundle_schema() {
+        let sqlite = rusqlite::Connection::open_in_memory().unwrap();
+
+        sqlite.execute_batch(SCHEMA).unwrap();
+        sqlite.execute_batch(INDEXES).unwrap();
+
+        sqlite
+            .execute(
+                "INSERT INTO ayahs_fts (text, ayah_id) VALUES (?1, ?2)",
+                params!["In the name of Allah", 1],
+            )
+            .unwrap();
+
+        let ayah: i32 = sqlite
+            .query_row(
+                "SELECT ayah_id FROM ayahs_fts WHERE ayahs_fts MATCH 'name'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+
+        assert_eq!(ayah, 1);
+    }
+
+    #[test]
+    fn test_bundle_search_without_marks() {
+        let sqlite = rusqlite::Connection::open_in_memory().unwrap();
+        sqlite.execute_batch(SCHEMA).unwrap();
+
+        let text = ["بِسْمِ", "ٱللَّهِ"].map(normalize_word).join(" ");
+        sqlite
+            .execute(
+                "INSERT INTO ayahs_fts (text, ayah_id) VALUES (?1, ?2)",
+                params![text, 1],
+            )
+            .unwrap();
+
+        let ayah: i32 = sqlite
+            .query_row(
+                "SELECT ayah_id FROM ayahs_fts WHERE ayahs_fts MATCH 'بسم'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+
+        assert_eq!(ayah, 1);
+    }
+}
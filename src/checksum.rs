@@ -0,0 +1,589 @@
+use std::collections::BTreeMap;
+
+use diesel::prelude::*;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::error::RouterError;
+use crate::models::{NewQuranMushafChecksum, QuranMushafChecksum};
+use crate::verse_key::{mushaf_ayah_words, VerseKey};
+
+/// Rows per insert query, postgres can't bind more than 65535 params
+const STORE_CHUNK_SIZE: usize = 10_000;
+
+fn hash(content: &str) -> String {
+    format!("{:x}", Sha256::digest(content.as_bytes()))
+}
+
+/// Canonical checksum of an ayah, words are joined with a single space
+pub fn ayah_checksum(words: &[String]) -> String {
+    hash(&words.join(" "))
+}
+
+/// Hash of the `number:checksum` lines, a surah is checksummed
+/// by its ayahs and the mushaf by its surahs
+fn numbered_checksum<'a>(checksums: impl IntoIterator<Item = (i32, &'a str)>) -> String {
+    hash(
+        &checksums
+            .into_iter()
+            .map(|(number, checksum)| format!("{}:{}\n", number, checksum))
+            .collect::<String>(),
+    )
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DriftStatus {
+    /// Text of the ayah is changed
+    Changed,
+
+    /// Ayah is removed
+    Missing,
+
+    /// Ayah is not in the reference
+    Added,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DriftedAyah {
+    pub key: VerseKey,
+    pub status: DriftStatus,
+}
+
+/// Result of verifying the mushaf against the reference checksums
+#[derive(Debug, Serialize)]
+pub struct MushafVerification {
+    pub valid: bool,
+    pub checksum: String,
+    pub reference_checksum: String,
+    pub drifted_surahs: Vec<i32>,
+    pub drifted_ayahs: Vec<DriftedAyah>,
+}
+
+/// Checksums of the mushaf content
+///
+/// Surah checksum is the hash of its `ayah_number:checksum` lines and
+/// the mushaf checksum is the hash of the `surah_number:checksum` lines
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct MushafChecksums {
+    pub mushaf: String,
+    pub surahs: BTreeMap<i32, String>,
+    pub ayahs: BTreeMap<VerseKey, String>,
+}
+
+impl MushafChecksums {
+    /// Computes the checksums from the words of the ayahs
+    pub fn compute(ayahs: &BTreeMap<VerseKey, Vec<String>>) -> Self {
+        let mut surah_contents: BTreeMap<i32, String> = BTreeMap::new();
+        let mut ayah_checksums = BTreeMap::new();
+
+        for (key, words) in ayahs {
+            let checksum = ayah_checksum(words);
+
+            surah_contents
+                .entry(key.surah)
+                .or_default()
+                .push_str(&format!("{}:{}\n", key.ayah, checksum));
+
+            ayah_checksums.insert(*key, checksum);
+        }
+
+        let surahs: BTreeMap<i32, String> = surah_contents
+            .into_iter()
+            .map(|(number, content)| (number, hash(&content)))
+            .collect();
+
+        let mushaf = numbered_checksum(
+            surahs
+                .iter()
+                .map(|(number, checksum)| (*number, checksum.as_str())),
+        );
+
+        Self {
+            mushaf,
+            surahs,
+            ayahs: ayah_checksums,
+        }
+    }
+
+    /// Computes the checksums of the current mushaf content
+    pub fn current(conn: &mut PgConnection, target_mushaf_id: i32) -> QueryResult<Self> {
+        Ok(Self::compute(&mushaf_ayah_words(conn, target_mushaf_id)?))
+    }
+
+    /// Loads the stored reference checksums, None if the mushaf has no reference
+    pub fn reference(conn: &mut PgConnection, target_mushaf_id: i32) -> QueryResult<Option<Self>> {
+        use crate::schema::quran_mushafs_checksums::dsl::{mushaf_id, quran_mushafs_checksums};
+
+        let rows: Vec<QuranMushafChecksum> = quran_mushafs_checksums
+            .filter(mushaf_id.eq(target_mushaf_id))
+            .get_results(conn)?;
+
+        let mut result = Self::default();
+        let mut has_mushaf = false;
+
+        for row in rows {
+            match (row.surah_number, row.ayah_number) {
+                (Some(surah), Some(ayah)) => {
+                    result.ayahs.insert(VerseKey { surah, ayah }, row.checksum);
+                }
+
+                (Some(surah), None) => {
+                    result.surahs.insert(surah, row.checksum);
+                }
+
+                _ => {
+                    has_mushaf = true;
+                    result.mushaf = row.checksum;
+                }
+            }
+        }
+
+        Ok(has_mushaf.then_some(result))
+    }
+
+    /// Replaces the reference checksums of the mushaf
+    pub fn store(
+        &self,
+        conn: &mut PgConnection,
+        target_mushaf_id: i32,
+        creator_user_id: i32,
+    ) -> QueryResult<()> {
+        use crate::schema::quran_mushafs_checksums::dsl::{mushaf_id, quran_mushafs_checksums};
+
+        diesel::delete(quran_mushafs_checksums.filter(mushaf_id.eq(target_mushaf_id)))
+            .execute(conn)?;
+
+        let new_checksum = |surah_number, ayah_number, checksum| NewQuranMushafChecksum {
+            creator_user_id,
+            mushaf_id: target_mushaf_id,
+            surah_number,
+            ayah_number,
+            checksum,
+        };
+
+        let mut rows = vec![new_checksum(None, None, &self.mushaf)];
+
+        rows.extend(
+            self.surahs
+                .iter()
+                .map(|(number, checksum)| new_checksum(Some(*number), None, checksum)),
+        );
+
+        rows.extend(
+            self.ayahs
+                .iter()
+                .map(|(key, checksum)| new_checksum(Some(key.surah), Some(key.ayah), checksum)),
+        );
+
+        for chunk in rows.chunks(STORE_CHUNK_SIZE) {
+            diesel::insert_into(quran_mushafs_checksums)
+                .values(chunk)
+                .execute(conn)?;
+        }
+
+        Ok(())
+    }
+
+    /// Verifies the current checksums against this reference
+    pub fn verify(&self, current: &Self) -> MushafVerification {
+        let mut drifted_ayahs = vec![];
+
+        for (key, checksum) in &self.ayahs {
+            match current.ayahs.get(key) {
+                Some(current_checksum) if current_checksum == checksum => {}
+
+                Some(_) => drifted_ayahs.push(DriftedAyah {
+                    key: *key,
+                    status: DriftStatus::Changed,
+                }),
+
+                None => drifted_ayahs.push(DriftedAyah {
+                    key: *key,
+                    status: DriftStatus::Missing,
+                }),
+            }
+        }
+
+        drifted_ayahs.extend(
+            current
+                .ayahs
+                .keys()
+                .filter(|key| !self.ayahs.contains_key(key))
+                .map(|key| DriftedAyah {
+                    key: *key,
+                    status: DriftStatus::Added,
+                }),
+        );
+
+        drifted_ayahs.sort_by_key(|ayah| ayah.key);
+
+        let mut drifted_surahs: Vec<i32> = self
+            .surahs
+            .keys()
+            .chain(current.surahs.keys())
+            .filter(|number| self.surahs.get(number) != current.surahs.get(number))
+            .copied()
+            .collect();
+
+        drifted_surahs.sort();
+        drifted_surahs.dedup();
+
+        MushafVerification {
+            valid: self.mushaf == current.mushaf,
+            checksum: current.mushaf.clone(),
+            reference_checksum: self.mushaf.clone(),
+            drifted_surahs,
+            drifted_ayahs,
+        }
+    }
+}
+
+/// Reference checksums of a mushaf, changed in the transaction that changes its ayahs
+///
+/// Only the rows of the changed ayah are written, other ayahs keep their reference
+/// (renumbered ayahs are shifted), so an earlier drift stays detectable.
+/// A mushaf without a reference gets the reference of its whole content
+pub struct MushafReference {
+    mushaf_id: i32,
+    creator_user_id: i32,
+}
+
+impl MushafReference {
+    pub fn new(mushaf_id: i32, creator_user_id: i32) -> Self {
+        Self {
+            mushaf_id,
+            creator_user_id,
+        }
+    }
+
+    /// Stores the current content as the reference if the mushaf has none,
+    /// returns true if it is stored
+    fn store_if_missing(&self, conn: &mut PgConnection) -> QueryResult<bool> {
+        use crate::schema::quran_mushafs_checksums::dsl::{mushaf_id, quran_mushafs_checksums};
+
+        let has_reference: bool = diesel::select(diesel::dsl::exists(
+            quran_mushafs_checksums.filter(mushaf_id.eq(self.mushaf_id)),
+        ))
+        .get_result(conn)?;
+
+        if has_reference {
+            return Ok(false);
+        }
+
+        MushafChecksums::current(conn, self.mushaf_id)?.store(
+            conn,
+            self.mushaf_id,
+            self.creator_user_id,
+        )?;
+
+        Ok(true)
+    }
+
+    /// Adds `by` to the number of the surah ayahs from `from`
+    fn shift(&self, conn: &mut PgConnection, surah: i32, from: i32, by: i32) -> QueryResult<()> {
+        use crate::schema::quran_mushafs_checksums::dsl::{
+            ayah_number, mushaf_id, quran_mushafs_checksums, surah_number,
+        };
+
+        diesel::update(
+            quran_mushafs_checksums
+                .filter(mushaf_id.eq(self.mushaf_id))
+                .filter(surah_number.eq(surah))
+                .filter(ayah_number.ge(from)),
+        )
+        .set(ayah_number.eq(ayah_number + by))
+        .execute(conn)?;
+
+        Ok(())
+    }
+
+    /// Removes the ayah and closes its gap, returns its checksum
+    fn take(&self, conn: &mut PgConnection, key: VerseKey) -> QueryResult<Option<String>> {
+        use crate::schema::quran_mushafs_checksums::dsl::{
+            ayah_number, checksum, mushaf_id, quran_mushafs_checksums, surah_number,
+        };
+
+        let removed: Option<String> = diesel::delete(
+            quran_mushafs_checksums
+                .filter(mushaf_id.eq(self.mushaf_id))
+                .filter(surah_number.eq(key.surah))
+                .filter(ayah_number.eq(key.ayah)),
+        )
+        .returning(checksum)
+        .get_result(conn)
+        .optional()?;
+
+        self.shift(conn, key.surah, key.ayah + 1, -1)?;
+
+        Ok(removed)
+    }
+
+    /// Makes room for the ayah and inserts its checksum
+    fn put(&self, conn: &mut PgConnection, key: VerseKey, ayah_checksum: &str) -> QueryResult<()> {
+        use crate::schema::quran_mushafs_checksums::dsl::quran_mushafs_checksums;
+
+        self.shift(conn, key.surah, key.ayah, 1)?;
+
+        NewQuranMushafChecksum {
+            creator_user_id: self.creator_user_id,
+            mushaf_id: self.mushaf_id,
+            surah_number: Some(key.surah),
+            ayah_number: Some(key.ayah),
+            checksum: ayah_checksum,
+        }
+        .insert_into(quran_mushafs_checksums)
+        .execute(conn)?;
+
+        Ok(())
+    }
+
+    /// Computes the surah and the mushaf checksums again from the reference of the ayahs
+    fn refresh(&self, conn: &mut PgConnection, surah: i32) -> QueryResult<()> {
+        use crate::schema::quran_mushafs_checksums::dsl::{
+            ayah_number, checksum, mushaf_id, quran_mushafs_checksums, surah_number,
+        };
+
+        let ayahs: Vec<(i32, String)> = quran_mushafs_checksums
+            .filter(mushaf_id.eq(self.mushaf_id))
+            .filter(surah_number.eq(surah))
+            .filter(ayah_number.is_not_null())
+            .order(ayah_number.asc())
+            .select((ayah_number.assume_not_null(), checksum))
+            .get_results(conn)?;
+
+        diesel::delete(
+            quran_mushafs_checksums
+                .filter(mushaf_id.eq(self.mushaf_id))
+                .filter(surah_number.eq(surah))
+                .filter(ayah_number.is_null()),
+        )
+        .execute(conn)?;
+
+        // A surah without ayahs has no checksum
+        if !ayahs.is_empty() {
+            let surah_checksum = numbered_checksum(
+                ayahs
+                    .iter()
+                    .map(|(number, ayah_checksum)| (*number, ayah_checksum.as_str())),
+            );
+
+            NewQuranMushafChecksum {
+                creator_user_id: self.creator_user_id,
+                mushaf_id: self.mushaf_id,
+                surah_number: Some(surah),
+                ayah_number: None,
+                checksum: &surah_checksum,
+            }
+            .insert_into(quran_mushafs_checksums)
+            .execute(conn)?;
+        }
+
+        let surahs: Vec<(i32, String)> = quran_mushafs_checksums
+            .filter(mushaf_id.eq(self.mushaf_id))
+            .filter(surah_number.is_not_null())
+            .filter(ayah_number.is_null())
+            .order(surah_number.asc())
+            .select((surah_number.assume_not_null(), checksum))
+            .get_results(conn)?;
+
+        let mushaf_checksum = numbered_checksum(
+            surahs
+                .iter()
+                .map(|(number, surah_checksum)| (*number, surah_checksum.as_str())),
+        );
+
+        diesel::update(
+            quran_mushafs_checksums
+                .filter(mushaf_id.eq(self.mushaf_id))
+                .filter(surah_number.is_null()),
+        )
+        .set(checksum.eq(mushaf_checksum))
+        .execute(conn)?;
+
+        Ok(())
+    }
+
+    /// The ayah is inserted at the key, the ayahs after it are renumbered
+    ///
+    /// Must be called after the ayah is inserted
+    pub fn insert_ayah(
+        &self,
+        conn: &mut PgConnection,
+        key: VerseKey,
+        words: &[String],
+    ) -> QueryResult<()> {
+        if self.store_if_missing(conn)? {
+            return Ok(());
+        }
+
+        self.put(conn, key, &ayah_checksum(words))?;
+        self.refresh(conn, key.surah)
+    }
+
+    /// The ayah is deleted, the ayahs after it are renumbered
+    pub fn delete_ayah(&self, conn: &mut PgConnection, key: VerseKey) -> QueryResult<()> {
+        if self.store_if_missing(conn)? {
+            return Ok(());
+        }
+
+        self.take(conn, key)?;
+        self.refresh(conn, key.surah)
+    }
+
+    /// The ayah is moved to the `to` position of its surah, with its reference checksum
+    pub fn move_ayah(&self, conn: &mut PgConnection, from: VerseKey, to: i32) -> QueryResult<()> {
+        if from.ayah == to || self.store_if_missing(conn)? {
+            return Ok(());
+        }
+
+        let moved = self.take(conn, from)?;
+        let to = VerseKey {
+            surah: from.surah,
+            ayah: to,
+        };
+
+        match moved {
+            Some(moved) => self.put(conn, to, &moved)?,
+
+            // Ayah is not in the reference, only the others are renumbered
+            None => self.shift(conn, to.surah, to.ayah, 1)?,
+        }
+
+        self.refresh(conn, from.surah)
+    }
+
+    /// The text of the ayah is changed from `old_words` to `new_words`
+    ///
+    /// The ayah must match its reference before the change, so a drift is not accepted
+    pub fn change_ayah(
+        &self,
+        conn: &mut PgConnection,
+        key: VerseKey,
+        old_words: &[String],
+        new_words: &[String],
+    ) -> Result<(), RouterError> {
+        use crate::schema::quran_mushafs_checksums::dsl::{
+            ayah_number, checksum, mushaf_id, quran_mushafs_checksums, surah_number,
+        };
+
+        if self.store_if_missing(conn)? {
+            return Ok(());
+        }
+
+        let reference = quran_mushafs_checksums
+            .filter(mushaf_id.eq(self.mushaf_id))
+            .filter(surah_number.eq(key.surah))
+            .filter(ayah_number.eq(key.ayah));
+
+        let reference_checksum: Option<String> =
+            reference.select(checksum).get_result(conn).optional()?;
+
+        if reference_checksum != Some(ayah_checksum(old_words)) {
+            return Err(RouterError::from_predefined_with_detail(
+                "AYAH_CHECKSUM_DRIFTED",
+                &key.to_string(),
+            ));
+        }
+
+        diesel::update(reference)
+            .set(checksum.eq(ayah_checksum(new_words)))
+            .execute(conn)?;
+
+        self.refresh(conn, key.surah)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ayahs(content: &[(i32, i32, &str)]) -> BTreeMap<VerseKey, Vec<String>> {
+        content
+            .iter()
+            .map(|(surah, ayah, text)| {
+                (
+                    VerseKey {
+                        surah: *surah,
+                        ayah: *ayah,
+                    },
+                    text.split(' ').map(|w| w.to_string()).collect(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_verify_same_content() {
+        let reference = MushafChecksums::compute(&ayahs(&[(1, 1, "a b"), (1, 2, "c")]));
+        let current = MushafChecksums::compute(&ayahs(&[(1, 1, "a b"), (1, 2, "c")]));
+
+        let result = reference.verify(&current);
+
+        assert!(result.valid);
+        assert!(result.drifted_ayahs.is_empty());
+        assert!(result.drifted_surahs.is_empty());
+    }
+
+    #[test]
+    fn test_verify_drifted_content() {
+        let reference =
+            MushafChecksums::compute(&ayahs(&[(1, 1, "a b"), (1, 2, "c"), (2, 1, "d")]));
+        let current = MushafChecksums::compute(&ayahs(&[(1, 1, "a x"), (2, 1, "d"), (2, 2, "e")]));
+
+        let result = reference.verify(&current);
+
+        assert!(!result.valid);
+        assert_eq!(result.drifted_surahs, vec![1, 2]);
+        assert_eq!(
+            result.drifted_ayahs,
+            vec![
+                DriftedAyah {
+                    key: VerseKey { surah: 1, ayah: 1 },
+                    status: DriftStatus::Changed
+                },
+                DriftedAyah {
+                    key: VerseKey { surah: 1, ayah: 2 },
+                    status: DriftStatus::Missing
+                },
+                DriftedAyah {
+                    key: VerseKey { surah: 2, ayah: 2 },
+                    status: DriftStatus::Added
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_numbered_checksum_matches_compute() {
+        let checksums =
+            MushafChecksums::compute(&ayahs(&[(1, 1, "a b"), (1, 2, "c"), (2, 1, "d")]));
+
+        let surah = numbered_checksum(
+            checksums
+                .ayahs
+                .iter()
+                .filter(|(key, _)| key.surah == 1)
+                .map(|(key, checksum)| (key.ayah, checksum.as_str())),
+        );
+        assert_eq!(checksums.surahs[&1], surah);
+
+        let mushaf = numbered_checksum(
+            checksums
+                .surahs
+                .iter()
+                .map(|(number, checksum)| (*number, checksum.as_str())),
+        );
+        assert_eq!(checksums.mushaf, mushaf);
+    }
+
+    #[test]
+    fn test_word_boundaries_change_checksum() {
+        assert_ne!(
+            ayah_checksum(&["ab".to_string(), "c".to_string()]),
+            ayah_checksum(&["a".to_string(), "bc".to_string()])
+        );
+    }
+}
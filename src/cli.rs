@@ -0,0 +1,123 @@
+use std::io;
+use std::path::Path;
+
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::bundle::{build_bundle, bundle_translation_ids, BundleOptions};
+use crate::checksum::MushafChecksums;
+use crate::DbPool;
+
+/// Runs a command line command instead of the API server
+///
+/// Commands:
+///
+/// `verify-mushaf {mushaf uuid or short name}` lists the ayahs that
+/// are drifted from the reference checksums
+///
+/// `export-bundle {mushaf uuid or short name} {output file} [options]` builds the
+/// offline SQLite bundle of the mushaf, options are `--translation {uuid}` (repeatable),
+/// `--breakers`, `--phrases` and `--names`
+pub fn run(pool: DbPool, args: &[String]) -> io::Result<()> {
+    match args {
+        [command, mushaf] if command == "verify-mushaf" => verify_mushaf(pool, mushaf),
+
+        [command, mushaf, output, options @ ..] if command == "export-bundle" => {
+            export_bundle(pool, mushaf, output, options)
+        }
+
+        _ => Err(io::Error::other(format!(
+            "Unknown command: {}",
+            args.join(" ")
+        ))),
+    }
+}
+
+/// Returns the id of the mushaf by its uuid or short name
+fn find_mushaf(conn: &mut PgConnection, mushaf: &str) -> io::Result<i32> {
+    use crate::schema::quran_mushafs::dsl::{id as mushaf_id, quran_mushafs, short_name, uuid};
+
+    match mushaf.parse::<Uuid>() {
+        Ok(target_uuid) => quran_mushafs
+            .filter(uuid.eq(target_uuid))
+            .select(mushaf_id)
+            .get_result(conn),
+
+        Err(_) => quran_mushafs
+            .filter(short_name.eq(mushaf))
+            .select(mushaf_id)
+            .get_result(conn),
+    }
+    .map_err(io::Error::other)
+}
+
+fn verify_mushaf(pool: DbPool, mushaf: &str) -> io::Result<()> {
+    let mut conn = pool.get().map_err(io::Error::other)?;
+
+    let target_mushaf_id = find_mushaf(&mut conn, mushaf)?;
+
+    let Some(reference) =
+        MushafChecksums::reference(&mut conn, target_mushaf_id).map_err(io::Error::other)?
+    else {
+        return Err(io::Error::other("Mushaf has no reference checksum"));
+    };
+
+    let current =
+        MushafChecksums::current(&mut conn, target_mushaf_id).map_err(io::Error::other)?;
+
+    let result = reference.verify(&current);
+
+    for ayah in &result.drifted_ayahs {
+        println!("{} {:?}", ayah.key, ayah.status);
+    }
+
+    if !result.valid {
+        return Err(io::Error::other(format!(
+            "Mushaf is drifted, {} ayahs",
+            result.drifted_ayahs.len()
+        )));
+    }
+
+    println!("Mushaf is valid: {}", result.checksum);
+
+    Ok(())
+}
+
+fn export_bundle(pool: DbPool, mushaf: &str, output: &str, options: &[String]) -> io::Result<()> {
+    let mut conn = pool.get().map_err(io::Error::other)?;
+
+    let mut bundle = BundleOptions {
+        mushaf_id: find_mushaf(&mut conn, mushaf)?,
+        ..Default::default()
+    };
+
+    let mut translations = vec![];
+    let mut options = options.iter();
+
+    while let Some(option) = options.next() {
+        match option.as_str() {
+            "--translation" => {
+                let Some(uuid) = options.next() else {
+                    return Err(io::Error::other("--translation requires a uuid"));
+                };
+
+                translations.push(uuid.parse::<Uuid>().map_err(io::Error::other)?);
+            }
+
+            "--breakers" => bundle.breakers = true,
+            "--phrases" => bundle.phrases = true,
+            "--names" => bundle.surah_names = true,
+
+            _ => return Err(io::Error::other(format!("Unknown option: {}", option))),
+        }
+    }
+
+    bundle.translation_ids = bundle_translation_ids(&mut conn, bundle.mushaf_id, &translations)
+        .map_err(io::Error::other)?;
+
+    build_bundle(&mut conn, &bundle, Path::new(output)).map_err(io::Error::other)?;
+
+    println!("Bundle is created: {}", output);
+
+    Ok(())
+}
@@ -143,6 +143,64 @@ where
     }
 }
 
+/// Finds the difference between two ordered sequences
+///
+/// Unlike Difference the items are matched by their position, every item
+/// is paired with its index (in target for Remove, in new for Insert) and
+/// Update means the target item is substituted with the new one
+///
+/// Returns the shortest edit script in the order of the sequences
+pub fn sequence_diff<T>(target: &[T], new: &[T]) -> Vec<DifferenceResult<(usize, T)>>
+where
+    T: PartialEq + Clone,
+{
+    let (n, m) = (target.len(), new.len());
+
+    // distance[i][j] is the edit distance of target[i..] and new[j..]
+    let mut distance = vec![vec![0usize; m + 1]; n + 1];
+
+    for i in (0..=n).rev() {
+        for j in (0..=m).rev() {
+            distance[i][j] = if i == n {
+                m - j
+            } else if j == m {
+                n - i
+            } else if target[i] == new[j] {
+                distance[i + 1][j + 1]
+            } else {
+                1 + distance[i + 1][j + 1]
+                    .min(distance[i + 1][j])
+                    .min(distance[i][j + 1])
+            };
+        }
+    }
+
+    let mut result = vec![];
+    let (mut i, mut j) = (0, 0);
+
+    while i < n || j < m {
+        if i < n && j < m && target[i] == new[j] {
+            i += 1;
+            j += 1;
+        } else if i < n && j < m && distance[i][j] == distance[i + 1][j + 1] + 1 {
+            result.push(DifferenceResult::Update(
+                (i, target[i].clone()),
+                (j, new[j].clone()),
+            ));
+            i += 1;
+            j += 1;
+        } else if i < n && (j == m || distance[i][j] == distance[i + 1][j] + 1) {
+            result.push(DifferenceResult::Remove((i, target[i].clone())));
+            i += 1;
+        } else {
+            result.push(DifferenceResult::Insert((j, new[j].clone())));
+            j += 1;
+        }
+    }
+
+    result
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -282,4 +340,28 @@ mod tests {
 
         assert_eq!(expected, result);
     }
+
+    #[test]
+    fn test_sequence_diff() {
+        let target = vec!["a", "b", "c", "d"];
+        let new = vec!["a", "x", "c", "d", "e"];
+
+        let expected = vec![
+            DifferenceResult::Update((1, "b"), (1, "x")),
+            DifferenceResult::Insert((4, "e")),
+        ];
+
+        assert_eq!(expected, sequence_diff(&target, &new));
+    }
+
+    #[test]
+    fn test_sequence_diff_with_remove() {
+        let target = vec!["a", "b", "c"];
+        let new = vec!["a", "c"];
+
+        let expected = vec![DifferenceResult::Remove((1, "b"))];
+
+        assert_eq!(expected, sequence_diff(&target, &new));
+        assert!(sequence_diff(&new, &new).is_empty());
+    }
 }
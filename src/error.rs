@@ -6,6 +6,7 @@ use actix_web::{
 use auth_z::ParsedPath;
 use diesel::{
     prelude::*,
+    r2d2::PoolError,
     result::{DatabaseErrorKind, Error as DieselError},
 };
 use ipnetwork::IpNetwork;
@@ -317,6 +318,14 @@ impl From<DatabaseErrorKind> for RouterError {
     }
 }
 
+impl From<PoolError> for RouterError {
+    fn from(value: PoolError) -> Self {
+        error!("InternalError: {}", value);
+
+        Self::from_predefined("INTERNAL_ERROR")
+    }
+}
+
 impl From<UuidError> for RouterError {
     fn from(_value: UuidError) -> Self {
         Self::from_predefined("UUID_ERROR")
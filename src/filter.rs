@@ -1,22 +1,17 @@
 use serde::{Deserialize, Serialize};
 
 /// Order the result list by ASC or DESC
-#[derive(Deserialize, Serialize, Clone)]
+#[derive(Deserialize, Serialize, Clone, Default)]
 #[serde(rename_all = "lowercase")]
 pub enum Order {
     // ASC
+    #[default]
     Asc,
 
     // DESC
     Desc,
 }
 
-impl Default for Order {
-    fn default() -> Self {
-        Self::Asc
-    }
-}
-
 pub trait Filters {
     fn sort(&self) -> Option<String>;
     fn order(&self) -> Option<Order>;
@@ -0,0 +1,356 @@
+//! HTTP caching of the public content
+//!
+//! `CacheControl` sets the `Cache-Control` policy of a scope, `ContentETag` tags the responses
+//! with the content version, and answers the matching `If-None-Match` with 304
+//! before the handler is called. Requests with a token are never cached.
+
+use std::{
+    env,
+    future::{ready, Ready},
+    rc::Rc,
+};
+
+use actix_web::{
+    body::{EitherBody, MessageBody},
+    dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
+    http::{
+        header::{self, EntityTag, HeaderValue, IfNoneMatch},
+        Method,
+    },
+    web, Error, HttpMessage, HttpRequest, HttpResponse,
+};
+use diesel::dsl::sql;
+use diesel::prelude::*;
+use diesel::sql_types::BigInt;
+use futures_util::future::LocalBoxFuture;
+use log::error;
+use sha2::{Digest, Sha256};
+
+use crate::DbPool;
+
+const DEFAULT_PUBLIC_MAX_AGE: u32 = 300;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CachePolicy {
+    /// Never stored by the clients or the proxies
+    NoStore,
+
+    /// Stored by the proxies and CDNs, then revalidated with the ETag
+    Public { max_age: u32 },
+}
+
+impl CachePolicy {
+    /// Public policy, max age is `PUBLIC_CACHE_MAX_AGE` seconds (5 minutes by default)
+    pub fn public_from_env() -> Self {
+        let max_age = env::var("PUBLIC_CACHE_MAX_AGE")
+            .ok()
+            .and_then(|max_age| max_age.parse().ok())
+            .unwrap_or(DEFAULT_PUBLIC_MAX_AGE);
+
+        Self::Public { max_age }
+    }
+
+    fn header_value(&self) -> HeaderValue {
+        match self {
+            Self::NoStore => HeaderValue::from_static("no-store"),
+            Self::Public { max_age } => {
+                HeaderValue::from_str(&format!("public, max-age={}, must-revalidate", max_age))
+                    .unwrap()
+            }
+        }
+    }
+}
+
+/// Returns true if the response of the request can be shared between the clients
+fn is_cacheable_request(req: &HttpRequest) -> bool {
+    matches!(*req.method(), Method::GET | Method::HEAD)
+        && !req.headers().contains_key(header::AUTHORIZATION)
+}
+
+/// Sets the policy to the responses that don't have their own `Cache-Control`
+///
+/// Public policy is only set to the cacheable requests, the rest are left
+/// to the policy of the outer scope
+#[derive(Clone)]
+pub struct CacheControl {
+    policy: CachePolicy,
+}
+
+impl CacheControl {
+    pub fn new(policy: CachePolicy) -> Self {
+        Self { policy }
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for CacheControl
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Transform = CacheControlMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(CacheControlMiddleware {
+            service: Rc::new(service),
+            policy: self.policy,
+        }))
+    }
+}
+
+pub struct CacheControlMiddleware<S> {
+    service: Rc<S>,
+    policy: CachePolicy,
+}
+
+impl<S, B> Service<ServiceRequest> for CacheControlMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = Rc::clone(&self.service);
+        let policy = self.policy;
+        let cacheable = is_cacheable_request(req.request());
+
+        Box::pin(async move {
+            let mut res = service.call(req).await?;
+
+            let applies = match policy {
+                CachePolicy::NoStore => true,
+                CachePolicy::Public { .. } => {
+                    cacheable && (res.status().is_success() || res.status().as_u16() == 304)
+                }
+            };
+
+            if applies && !res.headers().contains_key(header::CACHE_CONTROL) {
+                res.headers_mut()
+                    .insert(header::CACHE_CONTROL, policy.header_value());
+            }
+
+            Ok(res)
+        })
+    }
+}
+
+/// Content version that the request is answered with,
+/// handlers get it from the request data of the tagged scopes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ContentVersion(pub i64);
+
+/// Returns the current version of the public content
+///
+/// The version is the newest transaction that changed the content and is older than
+/// every running transaction, so a change committed later always gets a newer version
+pub fn content_version(conn: &mut PgConnection) -> QueryResult<i64> {
+    use crate::schema::app_content_changes::dsl::{app_content_changes, transaction_id};
+
+    let version: Option<i64> = app_content_changes
+        .filter(transaction_id.lt(sql::<BigInt>("txid_snapshot_xmin(txid_current_snapshot())")))
+        .select(diesel::dsl::max(transaction_id))
+        .get_result(conn)?;
+
+    Ok(version.unwrap_or_default())
+}
+
+/// Strong ETag of the response of the request at the content version
+///
+/// Same version and request (path, query and the negotiated format) has the same response
+fn content_etag(version: i64, req: &HttpRequest) -> EntityTag {
+    let accept = req
+        .headers()
+        .get(header::ACCEPT)
+        .map(|accept| accept.as_bytes())
+        .unwrap_or_default();
+
+    let mut hasher = Sha256::new();
+    hasher.update(version.to_be_bytes());
+    hasher.update(req.path().as_bytes());
+    hasher.update(b"?");
+    hasher.update(req.query_string().as_bytes());
+    hasher.update(b"\n");
+    hasher.update(accept);
+
+    let hash = hasher.finalize();
+    let tag = hash[..16]
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect::<String>();
+
+    EntityTag::new_strong(tag)
+}
+
+/// Returns true if the client has the representation of the tag
+fn is_not_modified(req: &HttpRequest, etag: &EntityTag) -> bool {
+    match req.get_header::<IfNoneMatch>() {
+        Some(IfNoneMatch::Items(tags)) => tags.iter().any(|tag| tag.weak_eq(etag)),
+
+        // `*` is not answered, the handler may choose to not have a representation
+        _ => false,
+    }
+}
+
+/// Tags the responses with the content version
+///
+/// Handlers that their response is not only made of the content
+/// (e.g. random or dated) opt out with `Cache-Control: no-store`
+#[derive(Clone, Default)]
+pub struct ContentETag;
+
+impl<S, B> Transform<S, ServiceRequest> for ContentETag
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Transform = ContentETagMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(ContentETagMiddleware {
+            service: Rc::new(service),
+        }))
+    }
+}
+
+pub struct ContentETagMiddleware<S> {
+    service: Rc<S>,
+}
+
+impl<S, B> Service<ServiceRequest> for ContentETagMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = Rc::clone(&self.service);
+        let pool = req.app_data::<web::Data<DbPool>>().cloned();
+
+        Box::pin(async move {
+            let (Some(pool), true) = (pool, is_cacheable_request(req.request())) else {
+                return Ok(service.call(req).await?.map_into_left_body());
+            };
+
+            let version = web::block(move || content_version(&mut pool.get().unwrap()))
+                .await
+                .unwrap();
+
+            // Without the version the response is not tagged
+            let etag = match version {
+                Ok(version) => {
+                    req.extensions_mut().insert(ContentVersion(version));
+
+                    content_etag(version, req.request())
+                }
+
+                Err(err) => {
+                    error!("Can't get the content version: {}", err);
+
+                    return Ok(service.call(req).await?.map_into_left_body());
+                }
+            };
+
+            if is_not_modified(req.request(), &etag) {
+                let res = HttpResponse::NotModified()
+                    .insert_header(header::ETag(etag))
+                    .insert_header((header::VARY, "Accept"))
+                    .finish();
+
+                return Ok(req.into_response(res).map_into_right_body());
+            }
+
+            let mut res = service.call(req).await?;
+
+            let opted_out = res
+                .headers()
+                .get(header::CACHE_CONTROL)
+                .and_then(|value| value.to_str().ok())
+                .is_some_and(|value| value.contains("no-store"));
+
+            if res.status().is_success() && !opted_out {
+                res.headers_mut().insert(
+                    header::ETAG,
+                    HeaderValue::from_str(&etag.to_string()).unwrap(),
+                );
+            }
+
+            Ok(res.map_into_left_body())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::test::TestRequest;
+
+    #[test]
+    fn test_content_etag() {
+        let req = TestRequest::with_uri("/surah?mushaf=hafs").to_http_request();
+        let etag = content_etag(7, &req);
+
+        assert_eq!(etag, content_etag(7, &req));
+        assert_ne!(etag, content_etag(8, &req));
+
+        let other = TestRequest::with_uri("/surah?mushaf=hafs")
+            .insert_header((header::ACCEPT, "text/csv"))
+            .to_http_request();
+        assert_ne!(etag, content_etag(7, &other));
+    }
+
+    #[test]
+    fn test_is_not_modified() {
+        let etag = EntityTag::new_strong("abc".to_string());
+
+        let req = TestRequest::default()
+            .insert_header((header::IF_NONE_MATCH, "\"xyz\", W/\"abc\""))
+            .to_http_request();
+        assert!(is_not_modified(&req, &etag));
+
+        let req = TestRequest::default()
+            .insert_header((header::IF_NONE_MATCH, "\"xyz\""))
+            .to_http_request();
+        assert!(!is_not_modified(&req, &etag));
+        assert!(!is_not_modified(
+            &TestRequest::default().to_http_request(),
+            &etag
+        ));
+    }
+
+    #[test]
+    fn test_cache_policy() {
+        assert_eq!(
+            CachePolicy::Public { max_age: 60 }.header_value(),
+            "public, max-age=60, must-revalidate"
+        );
+
+        let req = TestRequest::default()
+            .insert_header((header::AUTHORIZATION, "token"))
+            .to_http_request();
+        assert!(!is_cacheable_request(&req));
+        assert!(is_cacheable_request(
+            &TestRequest::default().to_http_request()
+        ));
+    }
+}
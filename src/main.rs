@@ -1,7 +1,7 @@
 use crate::error::PreDefinedResponseError;
 use actix_cors::Cors;
 use actix_web::web::PathConfig;
-use actix_web::{middleware, web, App, HttpServer};
+use actix_web::{web, App, HttpServer};
 use authz::AuthZController;
 use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
 
@@ -12,31 +12,47 @@ use diesel::r2d2::{ConnectionManager, Pool};
 use dotenvy::dotenv;
 use email::EmailManager;
 use error::{path_error_handler, PreDefinedResponseErrors};
+use http_cache::{CacheControl, CachePolicy, ContentETag};
 use lettre::transport::smtp::authentication::Credentials;
 use log::LevelFilter;
 use log4rs::append::file::FileAppender;
 use log4rs::config::{Appender, Root};
 use log4rs::encode::pattern::PatternEncoder;
 use log4rs::Config;
+use ndjson::StreamLimit;
+use negotiation::Negotiate;
+use statistics::StatisticsCache;
 use std::collections::HashMap;
 use std::error::Error;
-use std::io::ErrorKind;
 use std::sync::OnceLock;
 use std::{env, io};
 use token_checker::UserIdFromToken;
+use view_cache::ViewCache;
 
+mod alignment;
 mod authz;
+mod bundle;
+mod checksum;
+mod cli;
 mod datetime;
 mod email;
 mod error;
 mod filter;
+mod http_cache;
 pub mod models;
 mod models_filter;
+mod ndjson;
+mod negotiation;
 mod routers;
 mod schema;
 mod select_model;
+mod similarity;
+mod statistics;
 mod token_checker;
+mod transliteration;
 mod validate;
+mod verse_key;
+mod view_cache;
 
 mod difference;
 mod macros;
@@ -44,14 +60,25 @@ mod macros;
 use routers::account::logout;
 use routers::account::send_code;
 use routers::account::verify;
+use routers::cache::cache_metrics;
+use routers::collection::{
+    collection_add, collection_delete, collection_edit, collection_list, collection_view,
+};
 use routers::error::errors_list;
+use routers::hifz::{hifz_add, hifz_delete, hifz_due, hifz_list, hifz_review, hifz_view};
 use routers::organization::{add, delete, edit, list, name, view};
 use routers::permission::{
     add_permission, delete_permission, edit_permission, permissions_list, view_permission,
 };
 use routers::phrase::{add_phrase, delete_phrase, edit_phrase, phrase_list, view_phrase};
-use routers::profile::{profile_edit, profile_view};
-use routers::quran::{ayah::*, mushaf::*, surah::*, word::*};
+use routers::plan::{plan_add, plan_day_edit, plan_delete, plan_list, plan_view};
+use routers::profile::{
+    bookmark_delete, bookmark_list, bookmark_modify, position_list, position_modify, profile_edit,
+    profile_view,
+};
+use routers::quran::{ayah::*, mushaf::*, relation::*, surah::*, word::*};
+use routers::reciter::*;
+use routers::sync::sync_changes;
 use routers::translation::*;
 use routers::user::{add_user, delete_user, edit_user, users_list, view_user};
 
@@ -118,15 +145,13 @@ async fn main() -> std::io::Result<()> {
     let Ok(json) =
         serde_json::from_str::<HashMap<String, PreDefinedResponseError>>(FIXED_ERROR_JSON)
     else {
-        return Err(io::Error::new(
-            ErrorKind::Other,
+        return Err(io::Error::other(
             "Failed to parse fixed_error_responses json!",
         ));
     };
 
     let Ok(()) = FIXED_ERROR_RESPONSES.set(PreDefinedResponseErrors { errors: json }) else {
-        return Err(io::Error::new(
-            ErrorKind::Other,
+        return Err(io::Error::other(
             "Could't set to static FIXED_ERROR_RESPONSES",
         ));
     };
@@ -139,22 +164,42 @@ async fn main() -> std::io::Result<()> {
 
     run_migrations(&mut pool.get().unwrap()).unwrap();
 
+    // Run the command instead of the server, if there is one
+    let args: Vec<String> = env::args().skip(1).collect();
+    if !args.is_empty() {
+        return cli::run(pool, &args);
+    }
+
     let mailer = create_emailer();
 
     let user_id_from_token = UserIdFromToken::new(pool.clone());
 
     let auth_z_controller = AuthZController::new(pool.clone());
 
+    let audio_base = AudioBase::from_env();
+
+    // Shared between the workers
+    let statistics_cache = web::Data::new(StatisticsCache::new());
+    let view_cache = web::Data::new(ViewCache::from_env());
+    let stream_limit = web::Data::new(StreamLimit::from_env());
+
+    // Public content can be cached by the CDNs, everything else is not stored
+    let public_cache = CachePolicy::public_from_env();
+
     HttpServer::new(move || {
         // Set All to the cors
         let cors = Cors::permissive();
 
         App::new()
             .wrap(cors)
-            .wrap(middleware::DefaultHeaders::new().add(("Cache-Control", "no-cache")))
+            .wrap(CacheControl::new(CachePolicy::NoStore))
             .app_data(PathConfig::default().error_handler(path_error_handler))
             .app_data(web::Data::new(pool.clone()))
             .app_data(web::Data::new(mailer.clone()))
+            .app_data(web::Data::new(audio_base.clone()))
+            .app_data(statistics_cache.clone())
+            .app_data(view_cache.clone())
+            .app_data(stream_limit.clone())
             .service(
                 web::scope("/account")
                     .route("/sendCode", web::post().to(send_code::send_code))
@@ -167,8 +212,15 @@ async fn main() -> std::io::Result<()> {
             )
             .service(
                 web::scope("/surah")
+                    .wrap(Negotiate)
+                    .wrap(ContentETag)
+                    .wrap(CacheControl::new(public_cache))
                     .route("", web::get().to(surah_list::surah_list))
                     .route("/{surah_uuid}", web::get().to(surah_view::surah_view))
+                    .route(
+                        "/{surah_uuid}/statistics",
+                        web::get().to(surah_statistics_view::surah_statistics_view),
+                    )
                     .service(
                         web::resource("")
                             .wrap(AuthZ::new(auth_z_controller.clone()))
@@ -182,10 +234,20 @@ async fn main() -> std::io::Result<()> {
                             .wrap(TokenAuth::new(user_id_from_token.clone(), false))
                             .route(web::post().to(surah_edit::surah_edit))
                             .route(web::delete().to(surah_delete::surah_delete)),
+                    )
+                    .service(
+                        web::resource("/name/{surah_uuid}")
+                            .wrap(AuthZ::new(auth_z_controller.clone()))
+                            .wrap(TokenAuth::new(user_id_from_token.clone(), true))
+                            .route(web::post().to(surah_name_modify::surah_name_modify))
+                            .route(web::delete().to(surah_name_delete::surah_name_delete)),
                     ),
             )
             .service(
                 web::scope("/translation")
+                    .wrap(Negotiate)
+                    .wrap(ContentETag)
+                    .wrap(CacheControl::new(public_cache))
                     .route("", web::get().to(translation_list::translation_list))
                     .route(
                         "/{translation_uuid}",
@@ -225,9 +287,83 @@ async fn main() -> std::io::Result<()> {
                             ),
                     ),
             )
+            .service(
+                web::scope("/relation")
+                    .wrap(ContentETag)
+                    .wrap(CacheControl::new(public_cache))
+                    .route("", web::get().to(relation_list::relation_list))
+                    .route("/{relation_uuid}", web::get().to(relation_view::relation_view))
+                    .service(
+                        web::resource("")
+                            .wrap(AuthZ::new(auth_z_controller.clone()))
+                            .wrap(TokenAuth::new(user_id_from_token.clone(), true))
+                            .route(web::post().to(relation_add::relation_add)),
+                    )
+                    .service(
+                        web::resource("/{relation_uuid}")
+                            .wrap(AuthZ::new(auth_z_controller.clone()))
+                            .wrap(TokenAuth::new(user_id_from_token.clone(), false))
+                            .route(web::post().to(relation_edit::relation_edit))
+                            .route(web::delete().to(relation_delete::relation_delete)),
+                    )
+                    .service(
+                        web::resource("/import/{mushaf_uuid}")
+                            .wrap(AuthZ::new(auth_z_controller.clone()))
+                            .wrap(TokenAuth::new(user_id_from_token.clone(), true))
+                            .route(web::post().to(relation_import::relation_import)),
+                    )
+                    .service(
+                        web::resource("/similar/{translation_uuid}")
+                            .wrap(AuthZ::new(auth_z_controller.clone()))
+                            .wrap(TokenAuth::new(user_id_from_token.clone(), true))
+                            .route(web::post().to(relation_similar::relation_similar)),
+                    ),
+            )
+            .service(
+                web::scope("/reciter")
+                    .wrap(ContentETag)
+                    .wrap(CacheControl::new(public_cache))
+                    .route("", web::get().to(reciter_list::reciter_list))
+                    .route("/{reciter_uuid}", web::get().to(reciter_view::reciter_view))
+                    .route(
+                        "/audio/{reciter_uuid}",
+                        web::get().to(reciter_audio_view::reciter_audio_view),
+                    )
+                    .service(
+                        web::resource("")
+                            .wrap(AuthZ::new(auth_z_controller.clone()))
+                            .wrap(TokenAuth::new(user_id_from_token.clone(), true))
+                            .route(web::post().to(reciter_add::reciter_add)),
+                    )
+                    .service(
+                        web::resource("/{reciter_uuid}")
+                            .wrap(AuthZ::new(auth_z_controller.clone()))
+                            .wrap(TokenAuth::new(user_id_from_token.clone(), false))
+                            .route(web::post().to(reciter_edit::reciter_edit))
+                            .route(web::delete().to(reciter_delete::reciter_delete)),
+                    )
+                    .service(
+                        web::resource("/audio/{reciter_uuid}")
+                            .wrap(AuthZ::new(auth_z_controller.clone()))
+                            .wrap(TokenAuth::new(user_id_from_token.clone(), true))
+                            .route(web::post().to(reciter_audio_modify::reciter_audio_modify))
+                            .route(web::delete().to(reciter_audio_delete::reciter_audio_delete)),
+                    )
+                    .service(
+                        web::resource("/timing/{reciter_uuid}")
+                            .wrap(AuthZ::new(auth_z_controller.clone()))
+                            .wrap(TokenAuth::new(user_id_from_token.clone(), true))
+                            .route(web::post().to(reciter_timing_import::reciter_timing_import)),
+                    ),
+            )
             .service(
                 web::scope("/ayah")
+                    .wrap(Negotiate)
+                    .wrap(ContentETag)
+                    .wrap(CacheControl::new(public_cache))
                     .route("", web::get().to(ayah_list::ayah_list))
+                    .route("/random", web::get().to(ayah_random::ayah_random))
+                    .route("/daily", web::get().to(ayah_daily::ayah_daily))
                     .route("/{ayah_uuid}", web::get().to(ayah_view::ayah_view))
                     .service(
                         web::resource("")
@@ -241,10 +377,18 @@ async fn main() -> std::io::Result<()> {
                             .wrap(TokenAuth::new(user_id_from_token.clone(), true))
                             .route(web::post().to(ayah_edit::ayah_edit))
                             .route(web::delete().to(ayah_delete::ayah_delete)),
+                    )
+                    .service(
+                        web::resource("/move/{ayah_uuid}")
+                            .wrap(AuthZ::new(auth_z_controller.clone()))
+                            .wrap(TokenAuth::new(user_id_from_token.clone(), true))
+                            .route(web::post().to(ayah_move::ayah_move)),
                     ),
             )
             .service(
                 web::scope("/word")
+                    .wrap(ContentETag)
+                    .wrap(CacheControl::new(public_cache))
                     .route("/{word_uuid}", web::get().to(word_view::word_view))
                     .service(
                         web::resource("")
@@ -258,12 +402,55 @@ async fn main() -> std::io::Result<()> {
                             .wrap(TokenAuth::new(user_id_from_token.clone(), false))
                             .route(web::post().to(word_edit::word_edit))
                             .route(web::delete().to(word_delete::word_delete)),
+                    )
+                    .service(
+                        web::resource("/script/{word_uuid}")
+                            .wrap(AuthZ::new(auth_z_controller.clone()))
+                            .wrap(TokenAuth::new(user_id_from_token.clone(), true))
+                            .route(web::post().to(word_script_modify::word_script_modify))
+                            .route(web::delete().to(word_script_delete::word_script_delete)),
+                    )
+                    .service(
+                        web::resource("/transliteration/{word_uuid}")
+                            .wrap(AuthZ::new(auth_z_controller.clone()))
+                            .wrap(TokenAuth::new(user_id_from_token.clone(), true))
+                            .route(
+                                web::post()
+                                    .to(word_transliteration_modify::word_transliteration_modify),
+                            )
+                            .route(
+                                web::delete()
+                                    .to(word_transliteration_delete::word_transliteration_delete),
+                            ),
                     ),
             )
             .service(
                 web::scope("/mushaf")
+                    .wrap(ContentETag)
+                    .wrap(CacheControl::new(public_cache))
                     .route("", web::get().to(mushaf_list::mushaf_list))
+                    .route("/compare", web::get().to(mushaf_compare::mushaf_compare))
+                    .route(
+                        "/checksum/{mushaf_uuid}",
+                        web::get().to(mushaf_checksum_verify::mushaf_checksum_verify),
+                    )
                     .route("/{mushaf_uuid}", web::get().to(mushaf_view::mushaf_view))
+                    .route(
+                        "/{mushaf_uuid}/page/{page}",
+                        web::get().to(mushaf_page_view::mushaf_page_view),
+                    )
+                    .route(
+                        "/{mushaf_uuid}/index/{index}",
+                        web::get().to(mushaf_index_view::mushaf_index_view),
+                    )
+                    .route(
+                        "/{mushaf_uuid}/statistics",
+                        web::get().to(mushaf_statistics_view::mushaf_statistics_view),
+                    )
+                    .route(
+                        "/alignment/{mushaf_uuid}",
+                        web::get().to(mushaf_alignment_view::mushaf_alignment_view),
+                    )
                     .service(
                         web::resource("")
                             .wrap(AuthZ::new(auth_z_controller.clone()))
@@ -276,6 +463,42 @@ async fn main() -> std::io::Result<()> {
                             .wrap(TokenAuth::new(user_id_from_token.clone(), false))
                             .route(web::post().to(mushaf_edit::mushaf_edit))
                             .route(web::delete().to(mushaf_delete::mushaf_delete)),
+                    )
+                    .service(
+                        web::resource("/script/{mushaf_uuid}")
+                            .wrap(AuthZ::new(auth_z_controller.clone()))
+                            .wrap(TokenAuth::new(user_id_from_token.clone(), true))
+                            .route(web::post().to(mushaf_script_import::mushaf_script_import)),
+                    )
+                    .service(
+                        web::resource("/alignment/{mushaf_uuid}")
+                            .wrap(AuthZ::new(auth_z_controller.clone()))
+                            .wrap(TokenAuth::new(user_id_from_token.clone(), true))
+                            .route(web::post().to(mushaf_alignment_import::mushaf_alignment_import)),
+                    )
+                    .service(
+                        web::resource("/bundle/{mushaf_uuid}")
+                            .wrap(AuthZ::new(auth_z_controller.clone()))
+                            .wrap(TokenAuth::new(user_id_from_token.clone(), true))
+                            .route(web::get().to(mushaf_bundle_export::mushaf_bundle_export)),
+                    )
+                    .service(
+                        web::resource("/checksum/{mushaf_uuid}")
+                            .wrap(AuthZ::new(auth_z_controller.clone()))
+                            .wrap(TokenAuth::new(user_id_from_token.clone(), true))
+                            .route(web::post().to(mushaf_checksum_add::mushaf_checksum_add)),
+                    )
+                    .service(
+                        web::resource("/tajweed/{mushaf_uuid}")
+                            .wrap(AuthZ::new(auth_z_controller.clone()))
+                            .wrap(TokenAuth::new(user_id_from_token.clone(), true))
+                            .route(web::post().to(mushaf_tajweed_import::mushaf_tajweed_import)),
+                    )
+                    .service(
+                        web::resource("/layout/{mushaf_uuid}")
+                            .wrap(AuthZ::new(auth_z_controller.clone()))
+                            .wrap(TokenAuth::new(user_id_from_token.clone(), true))
+                            .route(web::post().to(mushaf_layout_import::mushaf_layout_import)),
                     ),
             )
             .service(
@@ -292,7 +515,73 @@ async fn main() -> std::io::Result<()> {
                 web::scope("/profile")
                     .wrap(TokenAuth::new(user_id_from_token.clone(), true))
                     .route("", web::get().to(profile_view::profile_view))
-                    .route("", web::post().to(profile_edit::profile_edit)),
+                    .route("", web::post().to(profile_edit::profile_edit))
+                    .route("/bookmark", web::get().to(bookmark_list::bookmark_list))
+                    .route(
+                        "/bookmark",
+                        web::post().to(bookmark_modify::bookmark_modify),
+                    )
+                    .route(
+                        "/bookmark/{bookmark_uuid}",
+                        web::delete().to(bookmark_delete::bookmark_delete),
+                    )
+                    .route("/position", web::get().to(position_list::position_list))
+                    .route(
+                        "/position",
+                        web::post().to(position_modify::position_modify),
+                    ),
+            )
+            .service(
+                web::scope("/plan")
+                    .wrap(TokenAuth::new(user_id_from_token.clone(), true))
+                    .route("", web::get().to(plan_list::plan_list))
+                    .route("", web::post().to(plan_add::plan_add))
+                    .route("/{plan_uuid}", web::get().to(plan_view::plan_view))
+                    .route("/{plan_uuid}", web::delete().to(plan_delete::plan_delete))
+                    .route(
+                        "/{plan_uuid}/{day}",
+                        web::post().to(plan_day_edit::plan_day_edit),
+                    ),
+            )
+            .service(
+                web::scope("/sync").route("/changes", web::get().to(sync_changes::sync_changes)),
+            )
+            .service(
+                web::scope("/hifz")
+                    .wrap(TokenAuth::new(user_id_from_token.clone(), true))
+                    .route("", web::get().to(hifz_list::hifz_list))
+                    .route("", web::post().to(hifz_add::hifz_add))
+                    .route("/due", web::get().to(hifz_due::hifz_due))
+                    .route("/{range_uuid}", web::get().to(hifz_view::hifz_view))
+                    .route("/{range_uuid}", web::delete().to(hifz_delete::hifz_delete))
+                    .route(
+                        "/{range_uuid}/review",
+                        web::post().to(hifz_review::hifz_review),
+                    ),
+            )
+            .service(
+                web::scope("/collection")
+                    // Every account can create its own collections
+                    .service(
+                        web::resource("")
+                            .wrap(TokenAuth::new(user_id_from_token.clone(), true))
+                            .route(web::get().to(collection_list::collection_list))
+                            .route(web::post().to(collection_add::collection_add)),
+                    )
+                    // Public collections are visible without the token
+                    .service(
+                        web::resource("/{collection_uuid}")
+                            .guard(actix_web::guard::Get())
+                            .wrap(TokenAuth::new(user_id_from_token.clone(), false))
+                            .route(web::get().to(collection_view::collection_view)),
+                    )
+                    .service(
+                        web::resource("/{collection_uuid}")
+                            .wrap(AuthZ::new(auth_z_controller.clone()))
+                            .wrap(TokenAuth::new(user_id_from_token.clone(), true))
+                            .route(web::post().to(collection_edit::collection_edit))
+                            .route(web::delete().to(collection_delete::collection_delete)),
+                    ),
             )
             .service(
                 web::scope("/organization")
@@ -330,8 +619,15 @@ async fn main() -> std::io::Result<()> {
                         web::delete().to(delete_permission::delete_permission),
                     ),
             )
+            .service(
+                web::scope("/cache")
+                    .wrap(AuthZ::new(auth_z_controller.clone()))
+                    .wrap(TokenAuth::new(user_id_from_token.clone(), true))
+                    .route("", web::get().to(cache_metrics)),
+            )
             .service(
                 web::scope("/error")
+                    .wrap(Negotiate)
                     .wrap(AuthZ::new(auth_z_controller.clone()))
                     .wrap(TokenAuth::new(user_id_from_token.clone(), true))
                     .route("", web::get().to(errors_list)),
@@ -338,6 +338,10 @@ pub struct QuranSurah {
     pub created_at: NaiveDateTime,
     #[serde(skip_serializing)]
     pub updated_at: NaiveDateTime,
+
+    /// Position of the surah in the revelation order
+    pub revelation_order: Option<i32>,
+    pub ruku_count: Option<i32>,
 }
 
 #[derive(Insertable)]
@@ -352,6 +356,55 @@ pub struct NewQuranSurah {
     pub name_translation_phrase: Option<String>,
     pub name_transliteration: Option<String>,
     pub search_terms: Option<Vec<Option<String>>>,
+    pub revelation_order: Option<i32>,
+    pub ruku_count: Option<i32>,
+}
+
+#[derive(
+    Deserialize,
+    Serialize,
+    Clone,
+    Validate,
+    Identifiable,
+    Queryable,
+    Debug,
+    Associations,
+    Selectable,
+    PartialEq,
+    Eq,
+    Hash,
+)]
+#[diesel(table_name = quran_surahs_names)]
+#[diesel(belongs_to(QuranSurah, foreign_key = surah_id))]
+pub struct QuranSurahName {
+    #[serde(skip_serializing)]
+    pub id: i32,
+    pub uuid: Uuid,
+
+    #[serde(skip_serializing)]
+    pub creator_user_id: i32,
+
+    #[serde(skip_serializing)]
+    pub surah_id: i32,
+
+    pub language: String,
+    pub pronunciation: Option<String>,
+    pub transliteration: Option<String>,
+
+    #[serde(skip_serializing)]
+    pub created_at: NaiveDateTime,
+    #[serde(skip_serializing)]
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = quran_surahs_names)]
+pub struct NewQuranSurahName<'a> {
+    pub creator_user_id: i32,
+    pub surah_id: i32,
+    pub language: &'a str,
+    pub pronunciation: Option<&'a str>,
+    pub transliteration: Option<&'a str>,
 }
 
 #[derive(Deserialize, Serialize, Clone, Validate, Identifiable, Queryable, Selectable, Debug)]
@@ -697,6 +750,55 @@ pub struct QuranAyahBreaker {
     pub updated_at: NaiveDateTime,
 }
 
+#[derive(
+    Deserialize,
+    Serialize,
+    Clone,
+    Validate,
+    Identifiable,
+    Queryable,
+    Debug,
+    Selectable,
+    PartialEq,
+    Eq,
+    Hash,
+)]
+#[diesel(table_name = quran_ayahs_relations)]
+pub struct QuranAyahRelation {
+    #[serde(skip_serializing)]
+    pub id: i32,
+    pub uuid: Uuid,
+
+    #[serde(skip_serializing)]
+    pub creator_user_id: i32,
+
+    #[serde(skip_serializing)]
+    pub ayah_id: i32,
+
+    #[serde(skip_serializing)]
+    pub related_ayah_id: i32,
+
+    pub relation_type: String,
+
+    /// Where the relation comes from, e.g. a tafsir
+    pub source: Option<String>,
+
+    #[serde(skip_serializing)]
+    pub created_at: NaiveDateTime,
+    #[serde(skip_serializing)]
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = quran_ayahs_relations)]
+pub struct NewQuranAyahRelation<'a> {
+    pub creator_user_id: i32,
+    pub ayah_id: i32,
+    pub related_ayah_id: i32,
+    pub relation_type: String,
+    pub source: Option<&'a str>,
+}
+
 #[derive(
     Deserialize,
     Serialize,
@@ -734,3 +836,902 @@ pub struct QuranWordBreaker {
     #[serde(skip_serializing)]
     pub updated_at: NaiveDateTime,
 }
+
+#[derive(
+    Deserialize,
+    Serialize,
+    Clone,
+    Validate,
+    Identifiable,
+    Queryable,
+    Debug,
+    Associations,
+    Selectable,
+    PartialEq,
+    Eq,
+    Hash,
+)]
+#[diesel(table_name = quran_words_renditions)]
+#[diesel(belongs_to(QuranWord, foreign_key = word_id))]
+pub struct QuranWordRendition {
+    #[serde(skip_serializing)]
+    pub id: i32,
+    pub uuid: Uuid,
+
+    #[serde(skip_serializing)]
+    pub creator_user_id: i32,
+
+    #[serde(skip_serializing)]
+    pub word_id: i32,
+
+    pub script: String,
+    pub text: String,
+
+    #[serde(skip_serializing)]
+    pub created_at: NaiveDateTime,
+    #[serde(skip_serializing)]
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = quran_words_renditions)]
+pub struct NewQuranWordRendition<'a> {
+    pub creator_user_id: i32,
+    pub word_id: i32,
+    pub script: &'a str,
+    pub text: &'a str,
+}
+
+#[derive(
+    Deserialize,
+    Serialize,
+    Clone,
+    Validate,
+    Identifiable,
+    Queryable,
+    Debug,
+    Selectable,
+    PartialEq,
+    Eq,
+    Hash,
+)]
+#[diesel(table_name = quran_mushafs_alignments)]
+pub struct QuranMushafAlignment {
+    #[serde(skip_serializing)]
+    pub id: i32,
+    pub uuid: Uuid,
+
+    #[serde(skip_serializing)]
+    pub creator_user_id: i32,
+
+    #[serde(skip_serializing)]
+    pub source_mushaf_id: i32,
+    #[serde(skip_serializing)]
+    pub source_ayah_id: i32,
+    pub source_word_from: Option<i32>,
+    pub source_word_to: Option<i32>,
+
+    #[serde(skip_serializing)]
+    pub target_mushaf_id: i32,
+    #[serde(skip_serializing)]
+    pub target_ayah_id: i32,
+    pub target_word_from: Option<i32>,
+    pub target_word_to: Option<i32>,
+
+    #[serde(skip_serializing)]
+    pub created_at: NaiveDateTime,
+    #[serde(skip_serializing)]
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = quran_mushafs_alignments)]
+pub struct NewQuranMushafAlignment {
+    pub creator_user_id: i32,
+    pub source_mushaf_id: i32,
+    pub source_ayah_id: i32,
+    pub source_word_from: Option<i32>,
+    pub source_word_to: Option<i32>,
+    pub target_mushaf_id: i32,
+    pub target_ayah_id: i32,
+    pub target_word_from: Option<i32>,
+    pub target_word_to: Option<i32>,
+}
+
+#[derive(
+    Deserialize,
+    Serialize,
+    Clone,
+    Validate,
+    Identifiable,
+    Queryable,
+    Debug,
+    Associations,
+    Selectable,
+    PartialEq,
+    Eq,
+    Hash,
+)]
+#[diesel(table_name = quran_mushafs_checksums)]
+#[diesel(belongs_to(QuranMushaf, foreign_key = mushaf_id))]
+pub struct QuranMushafChecksum {
+    #[serde(skip_serializing)]
+    pub id: i32,
+    pub uuid: Uuid,
+
+    #[serde(skip_serializing)]
+    pub creator_user_id: i32,
+
+    #[serde(skip_serializing)]
+    pub mushaf_id: i32,
+
+    /// None for the whole mushaf checksum
+    pub surah_number: Option<i32>,
+
+    /// None for the whole surah checksum
+    pub ayah_number: Option<i32>,
+
+    pub checksum: String,
+
+    #[serde(skip_serializing)]
+    pub created_at: NaiveDateTime,
+    #[serde(skip_serializing)]
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = quran_mushafs_checksums)]
+pub struct NewQuranMushafChecksum<'a> {
+    pub creator_user_id: i32,
+    pub mushaf_id: i32,
+    pub surah_number: Option<i32>,
+    pub ayah_number: Option<i32>,
+    pub checksum: &'a str,
+}
+
+#[derive(
+    Deserialize,
+    Serialize,
+    Clone,
+    Validate,
+    Identifiable,
+    Queryable,
+    Debug,
+    Associations,
+    Selectable,
+    PartialEq,
+    Eq,
+    Hash,
+)]
+#[diesel(table_name = quran_words_tajweed)]
+#[diesel(belongs_to(QuranWord, foreign_key = word_id))]
+pub struct QuranWordTajweed {
+    #[serde(skip_serializing)]
+    pub id: i32,
+    pub uuid: Uuid,
+
+    #[serde(skip_serializing)]
+    pub creator_user_id: i32,
+
+    #[serde(skip_serializing)]
+    pub word_id: i32,
+
+    pub rule: String,
+
+    /// Character offset in the word text
+    pub start_index: i32,
+
+    /// Exclusive character offset in the word text
+    pub end_index: i32,
+
+    #[serde(skip_serializing)]
+    pub created_at: NaiveDateTime,
+    #[serde(skip_serializing)]
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = quran_words_tajweed)]
+pub struct NewQuranWordTajweed {
+    pub creator_user_id: i32,
+    pub word_id: i32,
+    pub rule: String,
+    pub start_index: i32,
+    pub end_index: i32,
+}
+
+#[derive(
+    Deserialize,
+    Serialize,
+    Clone,
+    Validate,
+    Identifiable,
+    Queryable,
+    Debug,
+    Associations,
+    Selectable,
+    PartialEq,
+    Eq,
+    Hash,
+)]
+#[diesel(table_name = quran_words_transliterations)]
+#[diesel(belongs_to(QuranWord, foreign_key = word_id))]
+pub struct QuranWordTransliteration {
+    #[serde(skip_serializing)]
+    pub id: i32,
+    pub uuid: Uuid,
+
+    #[serde(skip_serializing)]
+    pub creator_user_id: i32,
+
+    #[serde(skip_serializing)]
+    pub word_id: i32,
+
+    /// Curated transliteration, replaces the generated one
+    pub text: String,
+
+    #[serde(skip_serializing)]
+    pub created_at: NaiveDateTime,
+    #[serde(skip_serializing)]
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = quran_words_transliterations)]
+pub struct NewQuranWordTransliteration<'a> {
+    pub creator_user_id: i32,
+    pub word_id: i32,
+    pub text: &'a str,
+}
+
+#[derive(
+    Deserialize,
+    Serialize,
+    Clone,
+    Validate,
+    Identifiable,
+    Queryable,
+    Debug,
+    Associations,
+    Selectable,
+    PartialEq,
+    Eq,
+    Hash,
+)]
+#[diesel(table_name = quran_mushafs_lines)]
+#[diesel(belongs_to(QuranMushaf, foreign_key = mushaf_id))]
+pub struct QuranMushafLine {
+    #[serde(skip_serializing)]
+    pub id: i32,
+    pub uuid: Uuid,
+
+    #[serde(skip_serializing)]
+    pub creator_user_id: i32,
+
+    #[serde(skip_serializing)]
+    pub mushaf_id: i32,
+
+    pub page: i32,
+    pub line_number: i32,
+    pub line_type: String,
+
+    /// Surah of the surah header and bismillah lines
+    pub surah_number: Option<i32>,
+
+    #[serde(skip_serializing)]
+    pub first_word_id: Option<i32>,
+    #[serde(skip_serializing)]
+    pub last_word_id: Option<i32>,
+
+    #[serde(skip_serializing)]
+    pub created_at: NaiveDateTime,
+    #[serde(skip_serializing)]
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = quran_mushafs_lines)]
+pub struct NewQuranMushafLine {
+    pub creator_user_id: i32,
+    pub mushaf_id: i32,
+    pub page: i32,
+    pub line_number: i32,
+    pub line_type: String,
+    pub surah_number: Option<i32>,
+    pub first_word_id: Option<i32>,
+    pub last_word_id: Option<i32>,
+}
+
+#[derive(
+    Deserialize,
+    Serialize,
+    Clone,
+    Validate,
+    Identifiable,
+    Queryable,
+    Debug,
+    Associations,
+    Selectable,
+    PartialEq,
+    Eq,
+    Hash,
+)]
+#[diesel(table_name = quran_reciters)]
+#[diesel(belongs_to(QuranMushaf, foreign_key = mushaf_id))]
+pub struct QuranReciter {
+    #[serde(skip_serializing)]
+    pub id: i32,
+    pub uuid: Uuid,
+
+    #[serde(skip_serializing)]
+    pub creator_user_id: i32,
+
+    #[serde(skip_serializing)]
+    pub mushaf_id: i32,
+
+    pub name: String,
+
+    /// Recitation style, e.g. murattal, mujawwad
+    pub style: Option<String>,
+
+    #[serde(skip_serializing)]
+    pub created_at: NaiveDateTime,
+    #[serde(skip_serializing)]
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = quran_reciters)]
+pub struct NewQuranReciter {
+    pub creator_user_id: i32,
+    pub mushaf_id: i32,
+    pub name: String,
+    pub style: Option<String>,
+}
+
+#[derive(
+    Deserialize,
+    Serialize,
+    Clone,
+    Validate,
+    Identifiable,
+    Queryable,
+    Debug,
+    Associations,
+    Selectable,
+    PartialEq,
+    Eq,
+    Hash,
+)]
+#[diesel(table_name = quran_reciters_ayahs)]
+#[diesel(belongs_to(QuranReciter, foreign_key = reciter_id))]
+#[diesel(belongs_to(QuranAyah, foreign_key = ayah_id))]
+pub struct QuranReciterAyah {
+    #[serde(skip_serializing)]
+    pub id: i32,
+    pub uuid: Uuid,
+
+    #[serde(skip_serializing)]
+    pub creator_user_id: i32,
+
+    #[serde(skip_serializing)]
+    pub reciter_id: i32,
+
+    #[serde(skip_serializing)]
+    pub ayah_id: i32,
+
+    /// File path or url, relative to the audio base
+    pub path: String,
+    pub duration_ms: Option<i32>,
+
+    #[serde(skip_serializing)]
+    pub created_at: NaiveDateTime,
+    #[serde(skip_serializing)]
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = quran_reciters_ayahs)]
+pub struct NewQuranReciterAyah<'a> {
+    pub creator_user_id: i32,
+    pub reciter_id: i32,
+    pub ayah_id: i32,
+    pub path: &'a str,
+    pub duration_ms: Option<i32>,
+}
+
+#[derive(
+    Deserialize,
+    Serialize,
+    Clone,
+    Validate,
+    Identifiable,
+    Queryable,
+    Debug,
+    Associations,
+    Selectable,
+    PartialEq,
+    Eq,
+    Hash,
+)]
+#[diesel(table_name = quran_reciters_segments)]
+#[diesel(belongs_to(QuranReciterAyah, foreign_key = reciter_ayah_id))]
+pub struct QuranReciterSegment {
+    #[serde(skip_serializing)]
+    pub id: i32,
+    pub uuid: Uuid,
+
+    #[serde(skip_serializing)]
+    pub creator_user_id: i32,
+
+    #[serde(skip_serializing)]
+    pub reciter_ayah_id: i32,
+
+    #[serde(skip_serializing)]
+    pub word_id: i32,
+
+    pub start_ms: i32,
+    pub end_ms: i32,
+
+    #[serde(skip_serializing)]
+    pub created_at: NaiveDateTime,
+    #[serde(skip_serializing)]
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = quran_reciters_segments)]
+pub struct NewQuranReciterSegment {
+    pub creator_user_id: i32,
+    pub reciter_ayah_id: i32,
+    pub word_id: i32,
+    pub start_ms: i32,
+    pub end_ms: i32,
+}
+
+#[derive(
+    Deserialize,
+    Serialize,
+    Clone,
+    Validate,
+    Identifiable,
+    Queryable,
+    Debug,
+    Associations,
+    Selectable,
+    PartialEq,
+    Eq,
+    Hash,
+)]
+#[diesel(table_name = quran_plans)]
+#[diesel(belongs_to(QuranMushaf, foreign_key = mushaf_id))]
+pub struct QuranPlan {
+    #[serde(skip_serializing)]
+    pub id: i32,
+    pub uuid: Uuid,
+
+    #[serde(skip_serializing)]
+    pub creator_user_id: i32,
+
+    #[serde(skip_serializing)]
+    pub mushaf_id: i32,
+
+    pub days: i32,
+    pub snap: String,
+    pub breaker_name: Option<String>,
+
+    pub created_at: NaiveDateTime,
+    #[serde(skip_serializing)]
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = quran_plans)]
+pub struct NewQuranPlan {
+    pub creator_user_id: i32,
+    pub mushaf_id: i32,
+    pub days: i32,
+    pub snap: String,
+    pub breaker_name: Option<String>,
+}
+
+#[derive(
+    Deserialize,
+    Serialize,
+    Clone,
+    Validate,
+    Identifiable,
+    Queryable,
+    Debug,
+    Associations,
+    Selectable,
+    PartialEq,
+    Eq,
+    Hash,
+)]
+#[diesel(table_name = quran_plans_days)]
+#[diesel(belongs_to(QuranPlan, foreign_key = plan_id))]
+pub struct QuranPlanDay {
+    #[serde(skip_serializing)]
+    pub id: i32,
+    pub uuid: Uuid,
+
+    #[serde(skip_serializing)]
+    pub creator_user_id: i32,
+
+    #[serde(skip_serializing)]
+    pub plan_id: i32,
+
+    pub day: i32,
+
+    #[serde(skip_serializing)]
+    pub first_ayah_id: i32,
+    #[serde(skip_serializing)]
+    pub last_ayah_id: i32,
+
+    pub words: i32,
+    pub completed_at: Option<NaiveDateTime>,
+
+    #[serde(skip_serializing)]
+    pub created_at: NaiveDateTime,
+    #[serde(skip_serializing)]
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = quran_plans_days)]
+pub struct NewQuranPlanDay {
+    pub creator_user_id: i32,
+    pub plan_id: i32,
+    pub day: i32,
+    pub first_ayah_id: i32,
+    pub last_ayah_id: i32,
+    pub words: i32,
+}
+
+#[derive(
+    Deserialize,
+    Serialize,
+    Clone,
+    Validate,
+    Identifiable,
+    Queryable,
+    Debug,
+    Associations,
+    Selectable,
+    PartialEq,
+    Eq,
+    Hash,
+)]
+#[diesel(table_name = app_bookmarks)]
+#[diesel(belongs_to(Account, foreign_key = account_id))]
+pub struct Bookmark {
+    #[serde(skip_serializing)]
+    pub id: i32,
+    pub uuid: Uuid,
+
+    #[serde(skip_serializing)]
+    pub account_id: i32,
+
+    #[serde(skip_serializing)]
+    pub ayah_id: i32,
+
+    pub note: Option<String>,
+    pub tags: Vec<Option<String>>,
+    pub colour: Option<String>,
+
+    /// Deleted bookmarks are kept, so the other devices can sync the delete
+    pub deleted_at: Option<NaiveDateTime>,
+
+    #[serde(skip_serializing)]
+    pub created_at: NaiveDateTime,
+
+    /// Time of the last change on the device, the last change wins
+    pub updated_at: NaiveDateTime,
+
+    /// Transaction of the last change, devices sync the changes since it
+    #[serde(skip_serializing)]
+    pub synced_transaction: i64,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = app_bookmarks)]
+pub struct NewBookmark {
+    /// Generated by the database if not set
+    pub uuid: Option<Uuid>,
+    pub account_id: i32,
+    pub ayah_id: i32,
+    pub note: Option<String>,
+    pub tags: Vec<Option<String>>,
+    pub colour: Option<String>,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(
+    Deserialize,
+    Serialize,
+    Clone,
+    Validate,
+    Identifiable,
+    Queryable,
+    Debug,
+    Associations,
+    Selectable,
+    PartialEq,
+    Eq,
+    Hash,
+)]
+#[diesel(table_name = app_reading_positions)]
+#[diesel(belongs_to(Account, foreign_key = account_id))]
+pub struct ReadingPosition {
+    #[serde(skip_serializing)]
+    pub id: i32,
+    pub uuid: Uuid,
+
+    #[serde(skip_serializing)]
+    pub account_id: i32,
+
+    #[serde(skip_serializing)]
+    pub mushaf_id: i32,
+
+    #[serde(skip_serializing)]
+    pub ayah_id: i32,
+
+    #[serde(skip_serializing)]
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = app_reading_positions)]
+pub struct NewReadingPosition {
+    pub account_id: i32,
+    pub mushaf_id: i32,
+    pub ayah_id: i32,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(
+    Deserialize,
+    Serialize,
+    Clone,
+    Validate,
+    Identifiable,
+    Queryable,
+    Debug,
+    Selectable,
+    PartialEq,
+    Eq,
+    Hash,
+)]
+#[diesel(table_name = app_collections)]
+pub struct Collection {
+    #[serde(skip_serializing)]
+    pub id: i32,
+    pub uuid: Uuid,
+
+    #[serde(skip_serializing)]
+    pub creator_user_id: i32,
+
+    #[serde(skip_serializing)]
+    pub owner_account_id: i32,
+
+    /// Members of the organization can view the collection
+    #[serde(skip_serializing)]
+    pub organization_account_id: Option<i32>,
+
+    pub name: String,
+    pub description: Option<String>,
+
+    /// Anyone with the link can view the collection
+    pub public: bool,
+
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = app_collections)]
+pub struct NewCollection {
+    pub creator_user_id: i32,
+    pub owner_account_id: i32,
+    pub organization_account_id: Option<i32>,
+    pub name: String,
+    pub description: Option<String>,
+    pub public: bool,
+}
+
+#[derive(
+    Deserialize,
+    Serialize,
+    Clone,
+    Validate,
+    Identifiable,
+    Queryable,
+    Debug,
+    Associations,
+    Selectable,
+    PartialEq,
+    Eq,
+    Hash,
+)]
+#[diesel(table_name = app_collections_ayahs)]
+#[diesel(belongs_to(Collection, foreign_key = collection_id))]
+pub struct CollectionAyah {
+    #[serde(skip_serializing)]
+    pub id: i32,
+    pub uuid: Uuid,
+
+    #[serde(skip_serializing)]
+    pub creator_user_id: i32,
+
+    #[serde(skip_serializing)]
+    pub collection_id: i32,
+
+    pub position: i32,
+
+    #[serde(skip_serializing)]
+    pub first_ayah_id: i32,
+    #[serde(skip_serializing)]
+    pub last_ayah_id: i32,
+
+    pub note: Option<String>,
+
+    #[serde(skip_serializing)]
+    pub created_at: NaiveDateTime,
+    #[serde(skip_serializing)]
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = app_collections_ayahs)]
+pub struct NewCollectionAyah {
+    pub creator_user_id: i32,
+    pub collection_id: i32,
+    pub position: i32,
+    pub first_ayah_id: i32,
+    pub last_ayah_id: i32,
+    pub note: Option<String>,
+}
+
+#[derive(
+    Deserialize,
+    Serialize,
+    Clone,
+    Identifiable,
+    Queryable,
+    Debug,
+    Associations,
+    Selectable,
+    PartialEq,
+)]
+#[diesel(table_name = app_hifz_ranges)]
+#[diesel(belongs_to(Account, foreign_key = account_id))]
+pub struct HifzRange {
+    #[serde(skip_serializing)]
+    pub id: i32,
+    pub uuid: Uuid,
+
+    #[serde(skip_serializing)]
+    pub account_id: i32,
+
+    #[serde(skip_serializing)]
+    pub first_ayah_id: i32,
+    #[serde(skip_serializing)]
+    pub last_ayah_id: i32,
+
+    /// Number of the successful reviews in a row
+    pub repetitions: i32,
+
+    /// Days between the last review and the next one
+    pub interval_days: i32,
+    pub ease_factor: f64,
+    pub due_on: NaiveDate,
+    pub last_reviewed_at: Option<NaiveDateTime>,
+
+    #[serde(skip_serializing)]
+    pub created_at: NaiveDateTime,
+    #[serde(skip_serializing)]
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = app_hifz_ranges)]
+pub struct NewHifzRange {
+    pub account_id: i32,
+    pub first_ayah_id: i32,
+    pub last_ayah_id: i32,
+    pub due_on: NaiveDate,
+}
+
+#[derive(
+    Deserialize,
+    Serialize,
+    Clone,
+    Identifiable,
+    Queryable,
+    Debug,
+    Associations,
+    Selectable,
+    PartialEq,
+    Eq,
+    Hash,
+)]
+#[diesel(table_name = app_hifz_reviews)]
+#[diesel(belongs_to(HifzRange, foreign_key = range_id))]
+pub struct HifzReview {
+    #[serde(skip_serializing)]
+    pub id: i32,
+    pub uuid: Uuid,
+
+    #[serde(skip_serializing)]
+    pub range_id: i32,
+
+    /// SM-2 grade, 0 (forgotten) to 5 (perfect)
+    pub grade: i32,
+
+    pub created_at: NaiveDateTime,
+    #[serde(skip_serializing)]
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = app_hifz_reviews)]
+pub struct NewHifzReview {
+    pub range_id: i32,
+    pub grade: i32,
+}
+
+#[derive(
+    Deserialize,
+    Serialize,
+    Clone,
+    Identifiable,
+    Queryable,
+    Debug,
+    Associations,
+    Selectable,
+    PartialEq,
+    Eq,
+    Hash,
+)]
+#[diesel(table_name = app_hifz_mistakes)]
+#[diesel(belongs_to(HifzReview, foreign_key = review_id))]
+pub struct HifzMistake {
+    #[serde(skip_serializing)]
+    pub id: i32,
+
+    #[serde(skip_serializing)]
+    pub review_id: i32,
+
+    #[serde(skip_serializing)]
+    pub word_id: i32,
+
+    pub mistakes: i32,
+
+    #[serde(skip_serializing)]
+    pub created_at: NaiveDateTime,
+    #[serde(skip_serializing)]
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = app_hifz_mistakes)]
+pub struct NewHifzMistake {
+    pub review_id: i32,
+    pub word_id: i32,
+    pub mistakes: i32,
+}
+
+#[derive(Serialize, Clone, Identifiable, Queryable, Debug, Selectable, PartialEq, Eq, Hash)]
+#[diesel(table_name = app_changes)]
+pub struct Change {
+    pub id: i64,
+
+    /// Changes are ordered by the transaction, then the id
+    pub transaction_id: i64,
+    pub table_name: String,
+    pub operation: String,
+    pub row_uuid: Uuid,
+    pub created_at: NaiveDateTime,
+}
@@ -1,7 +1,10 @@
-use crate::models::{ErrorLog, QuranAyah, QuranMushaf, QuranSurah, QuranWord, Translation};
+use crate::models::{
+    ErrorLog, QuranAyah, QuranMushaf, QuranReciter, QuranSurah, QuranWord, Translation,
+};
 use crate::schema::app_error_logs::BoxedQuery as AppErrorBoxedQuery;
 use crate::schema::quran_ayahs::BoxedQuery as AyahBoxedQuery;
 use crate::schema::quran_mushafs::BoxedQuery as MushafBoxedQuery;
+use crate::schema::quran_reciters::BoxedQuery as ReciterBoxedQuery;
 use crate::schema::quran_surahs::BoxedQuery as SurahBoxedQuery;
 use crate::schema::quran_translations::BoxedQuery as TranslationBoxed;
 use crate::schema::quran_words::BoxedQuery as WordBoxedQuery;
@@ -49,6 +52,15 @@ impl Filter for QuranSurah {
                     Order::Desc => quran_surahs.order(number.desc()).internal_into_boxed(),
                 }),
 
+                "revelationOrder" => Ok(match filters.order().unwrap_or_default() {
+                    Order::Asc => quran_surahs
+                        .order(revelation_order.asc())
+                        .internal_into_boxed(),
+                    Order::Desc => quran_surahs
+                        .order(revelation_order.desc())
+                        .internal_into_boxed(),
+                }),
+
                 "createTime" => Ok(match filters.order().unwrap_or_default() {
                     Order::Asc => quran_surahs.order(created_at.asc()).internal_into_boxed(),
                     Order::Desc => quran_surahs.order(created_at.desc()).internal_into_boxed(),
@@ -320,3 +332,47 @@ impl Filter for ErrorLog {
         Ok(_query)
     }
 }
+
+impl Filter for QuranReciter {
+    type Output = Result<ReciterBoxedQuery<'static, Pg>, RouterError>;
+
+    fn filter(filters: Box<dyn Filters>) -> Self::Output {
+        use crate::schema::quran_reciters::dsl::*;
+
+        let mut _query = quran_reciters.into_boxed();
+
+        _query = match filters.sort() {
+            Some(sort_str) => match sort_str.as_str() {
+                "name" => Ok(match filters.order().unwrap_or_default() {
+                    Order::Asc => quran_reciters.order(name.asc()).internal_into_boxed(),
+                    Order::Desc => quran_reciters.order(name.desc()).internal_into_boxed(),
+                }),
+
+                "createTime" => Ok(match filters.order().unwrap_or_default() {
+                    Order::Asc => quran_reciters.order(created_at.asc()).internal_into_boxed(),
+                    Order::Desc => quran_reciters.order(created_at.desc()).internal_into_boxed(),
+                }),
+
+                "updateTime" => Ok(match filters.order().unwrap_or_default() {
+                    Order::Asc => quran_reciters.order(updated_at.asc()).internal_into_boxed(),
+                    Order::Desc => quran_reciters.order(updated_at.desc()).internal_into_boxed(),
+                }),
+
+                _ => Err(RouterError::from_predefined(
+                    "FILTER_SORT_VALUE_NOT_DEFINED",
+                )),
+            },
+
+            None => Ok(quran_reciters.order(name.asc()).internal_into_boxed()),
+        }?;
+
+        _query = match filters.to() {
+            Some(limit) => _query
+                .limit(limit as i64)
+                .offset(filters.from().unwrap_or_default() as i64),
+            None => _query.offset(filters.from().unwrap_or_default() as i64),
+        };
+
+        Ok(_query)
+    }
+}
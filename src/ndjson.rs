@@ -0,0 +1,199 @@
+//! Streaming of the large lists as newline delimited JSON
+//!
+//! Rows are serialized and sent line by line while they are read from the database,
+//! the reader waits while the client is slower, so the memory is bounded to a few lines.
+//! A stream holds its connection until the client reads it all, so the running streams
+//! are limited to `NDJSON_MAX_STREAMS` (4 by default)
+
+use std::{
+    env, io,
+    panic::{catch_unwind, AssertUnwindSafe},
+    pin::Pin,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+};
+
+use actix_web::{http::header, rt::task::spawn_blocking, web::Bytes, HttpRequest, HttpResponse};
+use futures_core::Stream;
+use log::error;
+use serde::Serialize;
+use tokio::sync::mpsc;
+
+use crate::error::RouterError;
+
+pub const NDJSON_CONTENT_TYPE: &str = "application/x-ndjson";
+
+/// Number of the lines that are read but not sent to the client yet
+const BUFFERED_LINES: usize = 64;
+
+const DEFAULT_MAX_STREAMS: usize = 4;
+
+/// Limit of the streams that are running at the same time, shared between the workers
+#[derive(Debug)]
+pub struct StreamLimit {
+    max: usize,
+    running: Arc<AtomicUsize>,
+}
+
+impl StreamLimit {
+    pub fn new(max: usize) -> Self {
+        Self {
+            max,
+            running: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    pub fn from_env() -> Self {
+        let max = env::var("NDJSON_MAX_STREAMS")
+            .ok()
+            .and_then(|max| max.parse().ok())
+            .unwrap_or(DEFAULT_MAX_STREAMS);
+
+        Self::new(max)
+    }
+
+    /// Returns the permit of a new stream, None if the limit is reached
+    fn acquire(&self) -> Option<StreamPermit> {
+        self.running
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |running| {
+                (running < self.max).then_some(running + 1)
+            })
+            .ok()?;
+
+        Some(StreamPermit {
+            running: self.running.clone(),
+        })
+    }
+}
+
+/// A running stream, the stream is finished when it is dropped
+struct StreamPermit {
+    running: Arc<AtomicUsize>,
+}
+
+impl Drop for StreamPermit {
+    fn drop(&mut self) {
+        self.running.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+/// Returns true if the client accepts the ndjson responses
+pub fn accepts_ndjson(req: &HttpRequest) -> bool {
+    req.headers()
+        .get_all(header::ACCEPT)
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .filter_map(|media| media.split(';').next())
+        .any(|media| media.trim().eq_ignore_ascii_case(NDJSON_CONTENT_TYPE))
+}
+
+pub struct NdjsonWriter {
+    sender: mpsc::Sender<io::Result<Bytes>>,
+}
+
+impl NdjsonWriter {
+    /// Sends the item as a line
+    ///
+    /// Returns false if the client is disconnected, the rest is not needed
+    pub fn write<T: Serialize>(&self, item: &T) -> bool {
+        let mut line = match serde_json::to_vec(item) {
+            Ok(line) => line,
+            Err(err) => {
+                return self
+                    .sender
+                    .blocking_send(Err(io::Error::other(err)))
+                    .is_ok()
+            }
+        };
+
+        line.push(b'\n');
+
+        self.sender.blocking_send(Ok(Bytes::from(line))).is_ok()
+    }
+}
+
+struct NdjsonStream {
+    receiver: mpsc::Receiver<io::Result<Bytes>>,
+}
+
+impl Stream for NdjsonStream {
+    type Item = io::Result<Bytes>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.receiver.poll_recv(cx)
+    }
+}
+
+/// Returns a streaming response of the lines that `produce` writes
+///
+/// `produce` runs on the blocking thread pool, an error (or a panic) after the first
+/// line aborts the response, so the client doesn't take a partial list as complete
+pub fn stream_ndjson<F>(limit: &StreamLimit, produce: F) -> Result<HttpResponse, RouterError>
+where
+    F: FnOnce(&NdjsonWriter) -> Result<(), RouterError> + Send + 'static,
+{
+    let Some(permit) = limit.acquire() else {
+        return Err(RouterError::from_predefined("STREAM_LIMIT_REACHED"));
+    };
+
+    let (sender, receiver) = mpsc::channel(BUFFERED_LINES);
+
+    spawn_blocking(move || {
+        let _permit = permit;
+        let writer = NdjsonWriter { sender };
+
+        let err = match catch_unwind(AssertUnwindSafe(|| produce(&writer))) {
+            Ok(Ok(())) => return,
+            Ok(Err(err)) => err.to_string(),
+            Err(_) => "producer panicked".to_string(),
+        };
+
+        error!("Stream is aborted: {}", err);
+
+        let _ = writer.sender.blocking_send(Err(io::Error::other(err)));
+    });
+
+    Ok(HttpResponse::Ok()
+        .content_type(NDJSON_CONTENT_TYPE)
+        .streaming(NdjsonStream { receiver }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::test::TestRequest;
+
+    #[test]
+    fn test_accepts_ndjson() {
+        let req = TestRequest::default()
+            .insert_header((
+                header::ACCEPT,
+                "application/json, application/x-ndjson;q=0.9",
+            ))
+            .to_http_request();
+        assert!(accepts_ndjson(&req));
+
+        let req = TestRequest::default()
+            .insert_header((header::ACCEPT, "application/json"))
+            .to_http_request();
+        assert!(!accepts_ndjson(&req));
+        assert!(!accepts_ndjson(&TestRequest::default().to_http_request()));
+    }
+
+    #[test]
+    fn test_stream_limit() {
+        let limit = StreamLimit::new(2);
+
+        let first = limit.acquire();
+        let second = limit.acquire();
+        assert!(first.is_some() && second.is_some());
+        assert!(limit.acquire().is_none());
+
+        // A finished stream makes room for the next one
+        drop(first);
+        assert!(limit.acquire().is_some());
+    }
+}
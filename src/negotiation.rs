@@ -0,0 +1,355 @@
+//! Content negotiation of the JSON responses
+//!
+//! Handlers always respond with JSON, the `Negotiate` middleware re-encodes
+//! the successful JSON responses to MessagePack or CSV if the client asked for it,
+//! with the `format` query parameter (json, msgpack, csv) or the `Accept` header.
+
+use std::{
+    future::{ready, Ready},
+    rc::Rc,
+};
+
+use actix_web::{
+    body::{self, BoxBody, EitherBody, MessageBody},
+    dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
+    error::ErrorInternalServerError,
+    http::{
+        header::{self, HeaderValue},
+        Uri,
+    },
+    Error, HttpMessage, HttpRequest,
+};
+use futures_util::future::LocalBoxFuture;
+use serde_json::Value;
+
+const JSON_CONTENT_TYPE: &str = "application/json";
+pub const MSGPACK_CONTENT_TYPE: &str = "application/msgpack";
+pub const CSV_CONTENT_TYPE: &str = "text/csv; charset=utf-8";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResponseFormat {
+    Json,
+    MessagePack,
+    Csv,
+}
+
+impl ResponseFormat {
+    /// Format of the `format` query parameter
+    fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "json" => Some(Self::Json),
+            "msgpack" | "messagepack" => Some(Self::MessagePack),
+            "csv" => Some(Self::Csv),
+            _ => None,
+        }
+    }
+
+    /// Format of a media type of the `Accept` header
+    fn from_media_type(media: &str) -> Option<Self> {
+        match media.trim().to_ascii_lowercase().as_str() {
+            "application/json" | "application/*" | "*/*" => Some(Self::Json),
+            "application/msgpack" | "application/x-msgpack" | "application/vnd.msgpack" => {
+                Some(Self::MessagePack)
+            }
+            "text/csv" => Some(Self::Csv),
+            _ => None,
+        }
+    }
+
+    /// The requested format, the query parameter is preferred over the `Accept` header
+    ///
+    /// Media types are ranked by their quality, JSON is the default
+    pub fn from_request(req: &HttpRequest) -> Self {
+        if let Some(format) = format_param(req.query_string()) {
+            return format;
+        }
+
+        req.get_header::<header::Accept>()
+            .and_then(|accept| {
+                accept
+                    .ranked()
+                    .iter()
+                    .find_map(|media| Self::from_media_type(media.essence_str()))
+            })
+            .unwrap_or(Self::Json)
+    }
+
+    fn content_type(&self) -> &'static str {
+        match self {
+            Self::Json => JSON_CONTENT_TYPE,
+            Self::MessagePack => MSGPACK_CONTENT_TYPE,
+            Self::Csv => CSV_CONTENT_TYPE,
+        }
+    }
+
+    /// Encodes the JSON value with this format
+    pub fn encode(&self, value: &Value) -> Result<Vec<u8>, String> {
+        match self {
+            Self::Json => serde_json::to_vec(value).map_err(|err| err.to_string()),
+            Self::MessagePack => rmp_serde::to_vec_named(value).map_err(|err| err.to_string()),
+            Self::Csv => to_csv(value),
+        }
+    }
+}
+
+/// The first response format of the `format` query parameters,
+/// the other values are the `format` of the handlers (e.g. text or word)
+fn format_param(query: &str) -> Option<ResponseFormat> {
+    query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .filter(|(key, _)| *key == "format")
+        .find_map(|(_, value)| ResponseFormat::from_name(value))
+}
+
+/// Removes the response `format` from the query, so it's not mistaken
+/// for the `format` parameter of the handlers (e.g. text or word)
+fn strip_format_param(query: &str) -> String {
+    query
+        .split('&')
+        .filter(|pair| {
+            !matches!(pair.split_once('='),
+                Some(("format", value)) if ResponseFormat::from_name(value).is_some())
+        })
+        .collect::<Vec<&str>>()
+        .join("&")
+}
+
+/// Returns the rows of the value
+///
+/// A list is a row per item, a view is written as the rows of its only list
+/// (e.g. the ayahs of a translation), otherwise as a single row
+fn csv_rows(value: &Value) -> Vec<&Value> {
+    match value {
+        Value::Array(items) => items.iter().collect(),
+
+        Value::Object(fields) => {
+            let mut lists = fields.values().filter(
+                |field| matches!(field, Value::Array(items) if items.iter().all(Value::is_object)),
+            );
+
+            match (lists.next(), lists.next()) {
+                (Some(Value::Array(items)), None) if !items.is_empty() => items.iter().collect(),
+                _ => vec![value],
+            }
+        }
+
+        _ => vec![value],
+    }
+}
+
+/// Flattens the nested objects to `parent.child` columns,
+/// the lists are kept as JSON in their column
+fn flatten(prefix: &str, value: &Value, columns: &mut Vec<(String, String)>) {
+    match value {
+        Value::Object(fields) => {
+            for (key, field) in fields {
+                let column = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", prefix, key)
+                };
+
+                flatten(&column, field, columns);
+            }
+        }
+
+        Value::Null => columns.push((prefix.to_string(), String::new())),
+        Value::String(text) => columns.push((prefix.to_string(), text.clone())),
+        other => columns.push((prefix.to_string(), other.to_string())),
+    }
+}
+
+/// Writes the value as CSV, headers are the columns of every row in order
+fn to_csv(value: &Value) -> Result<Vec<u8>, String> {
+    let rows: Vec<Vec<(String, String)>> = csv_rows(value)
+        .into_iter()
+        .map(|row| {
+            let mut columns = vec![];
+
+            match row {
+                Value::Object(_) => flatten("", row, &mut columns),
+                other => flatten("value", other, &mut columns),
+            }
+
+            columns
+        })
+        .collect();
+
+    let mut headers: Vec<&str> = vec![];
+    for (column, _) in rows.iter().flatten() {
+        if !headers.contains(&column.as_str()) {
+            headers.push(column);
+        }
+    }
+
+    let mut writer = csv::Writer::from_writer(vec![]);
+    writer
+        .write_record(&headers)
+        .map_err(|err| err.to_string())?;
+
+    for row in &rows {
+        let record = headers.iter().map(|header| {
+            row.iter()
+                .find(|(column, _)| column == header)
+                .map(|(_, value)| value.as_str())
+                .unwrap_or("")
+        });
+
+        writer.write_record(record).map_err(|err| err.to_string())?;
+    }
+
+    writer.into_inner().map_err(|err| err.to_string())
+}
+
+/// Re-encodes the JSON responses to the requested format
+#[derive(Clone, Default)]
+pub struct Negotiate;
+
+impl<S, B> Transform<S, ServiceRequest> for Negotiate
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Transform = NegotiateMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(NegotiateMiddleware {
+            service: Rc::new(service),
+        }))
+    }
+}
+
+pub struct NegotiateMiddleware<S> {
+    service: Rc<S>,
+}
+
+impl<S, B> Service<ServiceRequest> for NegotiateMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, mut req: ServiceRequest) -> Self::Future {
+        let service = Rc::clone(&self.service);
+        let format = ResponseFormat::from_request(req.request());
+
+        if format_param(req.query_string()).is_some() {
+            let query = strip_format_param(req.query_string());
+            let path_and_query = if query.is_empty() {
+                req.path().to_string()
+            } else {
+                format!("{}?{}", req.path(), query)
+            };
+
+            if let Ok(uri) = path_and_query.parse::<Uri>() {
+                req.head_mut().uri = uri;
+            }
+        }
+
+        Box::pin(async move {
+            let mut res = service.call(req).await?;
+
+            res.headers_mut()
+                .append(header::VARY, HeaderValue::from_static("Accept"));
+
+            let is_json = res
+                .headers()
+                .get(header::CONTENT_TYPE)
+                .and_then(|value| value.to_str().ok())
+                .is_some_and(|value| value.starts_with(JSON_CONTENT_TYPE));
+
+            if format == ResponseFormat::Json || !is_json || !res.status().is_success() {
+                return Ok(res.map_into_left_body());
+            }
+
+            let (req, res) = res.into_parts();
+            let (mut res, body) = res.into_parts();
+
+            let bytes = body::to_bytes(body)
+                .await
+                .map_err(|err| ErrorInternalServerError(err.into().to_string()))?;
+
+            let encoded = serde_json::from_slice::<Value>(&bytes)
+                .map_err(|err| err.to_string())
+                .and_then(|value| format.encode(&value))
+                .map_err(ErrorInternalServerError)?;
+
+            res.headers_mut().insert(
+                header::CONTENT_TYPE,
+                HeaderValue::from_static(format.content_type()),
+            );
+
+            let res = res.set_body(BoxBody::new(encoded));
+
+            Ok(ServiceResponse::new(req, res).map_into_right_body())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::test::TestRequest;
+    use serde_json::json;
+
+    #[test]
+    fn test_response_format() {
+        let req = TestRequest::default()
+            .insert_header((header::ACCEPT, "text/csv;q=0.5, application/msgpack"))
+            .to_http_request();
+        assert_eq!(
+            ResponseFormat::from_request(&req),
+            ResponseFormat::MessagePack
+        );
+
+        let req = TestRequest::with_uri("/ayah?mushaf=hafs&format=csv")
+            .insert_header((header::ACCEPT, "application/msgpack"))
+            .to_http_request();
+        assert_eq!(ResponseFormat::from_request(&req), ResponseFormat::Csv);
+
+        let req = TestRequest::with_uri("/ayah?format=word").to_http_request();
+        assert_eq!(ResponseFormat::from_request(&req), ResponseFormat::Json);
+
+        let req = TestRequest::with_uri("/ayah?format=word&format=csv").to_http_request();
+        assert_eq!(ResponseFormat::from_request(&req), ResponseFormat::Csv);
+        assert_eq!(strip_format_param(req.query_string()), "format=word");
+
+        assert_eq!(
+            strip_format_param("mushaf=hafs&format=csv&format=word"),
+            "mushaf=hafs&format=word"
+        );
+    }
+
+    #[test]
+    fn test_to_csv() {
+        let list = json!([
+            {"number": 1, "names": {"arabic": "الفاتحة"}, "terms": ["a", "b"]},
+            {"number": 2, "period": null, "text": "x, y"}
+        ]);
+
+        assert_eq!(
+            String::from_utf8(to_csv(&list).unwrap()).unwrap(),
+            "names.arabic,number,terms,period,text\n\
+             الفاتحة,1,\"[\"\"a\"\",\"\"b\"\"]\",,\n\
+             ,2,,,\"x, y\"\n"
+        );
+
+        let view = json!({"language": "en", "ayahs": [{"number": 1}, {"number": 2}]});
+        assert_eq!(
+            String::from_utf8(to_csv(&view).unwrap()).unwrap(),
+            "number\n1\n2\n"
+        );
+    }
+}
@@ -0,0 +1,7 @@
+use crate::view_cache::{ViewCache, ViewCacheMetrics};
+use actix_web::web;
+
+/// Returns the metrics of the cached views
+pub async fn cache_metrics(view_cache: web::Data<ViewCache>) -> web::Json<ViewCacheMetrics> {
+    web::Json(view_cache.metrics())
+}
@@ -0,0 +1,73 @@
+use crate::error::RouterError;
+use crate::models::{Collection, NewCollection, NewCollectionAyah};
+use crate::validate::validate;
+use crate::DbPool;
+use actix_web::web;
+use diesel::prelude::*;
+
+use super::{grant_owner_permissions, resolve_items, shared_organization, SimpleCollection};
+
+/// Add's a new collection for the account
+///
+/// Collection is private unless it is public or shared with an organization,
+/// the account is granted to edit and delete its own collections
+pub async fn collection_add(
+    new_collection: web::Json<SimpleCollection>,
+    pool: web::Data<DbPool>,
+    data: web::ReqData<u32>,
+) -> Result<&'static str, RouterError> {
+    use crate::schema::app_collections::dsl::app_collections;
+    use crate::schema::app_collections_ayahs::dsl::app_collections_ayahs;
+    use crate::schema::app_users::dsl::{account_id as user_acc_id, app_users, id as user_id};
+
+    let new_collection = new_collection.into_inner();
+    let data = data.into_inner() as i32;
+
+    validate(&new_collection)?;
+
+    web::block(move || {
+        let mut conn = pool.get().unwrap();
+
+        let user: i32 = app_users
+            .filter(user_acc_id.eq(data))
+            .select(user_id)
+            .get_result(&mut conn)?;
+
+        let organization = shared_organization(&mut conn, data, new_collection.organization_uuid)?;
+        let items = resolve_items(&mut conn, new_collection.items)?;
+
+        conn.transaction(|conn| {
+            let collection: Collection = NewCollection {
+                creator_user_id: user,
+                owner_account_id: data,
+                organization_account_id: organization,
+                name: new_collection.name,
+                description: new_collection.description,
+                public: new_collection.public,
+            }
+            .insert_into(app_collections)
+            .get_result(conn)?;
+
+            let items: Vec<NewCollectionAyah> = items
+                .into_iter()
+                .enumerate()
+                .map(|(index, (first, last, note))| NewCollectionAyah {
+                    creator_user_id: user,
+                    collection_id: collection.id,
+                    position: index as i32 + 1,
+                    first_ayah_id: first,
+                    last_ayah_id: last,
+                    note,
+                })
+                .collect();
+
+            items.insert_into(app_collections_ayahs).execute(conn)?;
+
+            grant_owner_permissions(conn, data, user)?;
+
+            Ok("Added")
+        })
+    })
+    .await
+    .unwrap()
+}
@@ -0,0 +1,43 @@
+use crate::error::RouterError;
+use crate::DbPool;
+use actix_web::web;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use super::not_owned_error;
+
+/// Delete's the collection with its items
+///
+/// Only the owner can delete the collection, this is checked by the AuthZ owner condition
+/// and the delete is filtered by the owner
+pub async fn collection_delete(
+    path: web::Path<Uuid>,
+    pool: web::Data<DbPool>,
+    data: web::ReqData<u32>,
+) -> Result<&'static str, RouterError> {
+    use crate::schema::app_collections::dsl::{
+        app_collections, owner_account_id, uuid as collection_uuid,
+    };
+
+    let target_collection_uuid = path.into_inner();
+    let data = data.into_inner() as i32;
+
+    web::block(move || {
+        let mut conn = pool.get().unwrap();
+
+        let deleted = diesel::delete(
+            app_collections
+                .filter(collection_uuid.eq(target_collection_uuid))
+                .filter(owner_account_id.eq(data)),
+        )
+        .execute(&mut conn)?;
+
+        if deleted == 0 {
+            return Err(not_owned_error(&mut conn, target_collection_uuid));
+        }
+
+        Ok("Deleted")
+    })
+    .await
+    .unwrap()
+}
@@ -0,0 +1,89 @@
+use chrono::Utc;
+
+use crate::error::RouterError;
+use crate::models::{Collection, CollectionAyah, NewCollectionAyah};
+use crate::validate::validate;
+use crate::DbPool;
+use actix_web::web;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use super::{not_owned_error, resolve_items, shared_organization, SimpleCollection};
+
+/// Edit's the collection, the items are replaced with the new ones
+///
+/// Only the owner can edit the collection, this is checked by the AuthZ owner condition
+/// and the update is filtered by the owner
+pub async fn collection_edit(
+    path: web::Path<Uuid>,
+    new_collection: web::Json<SimpleCollection>,
+    pool: web::Data<DbPool>,
+    data: web::ReqData<u32>,
+) -> Result<&'static str, RouterError> {
+    use crate::schema::app_collections::dsl::{
+        app_collections, description, name, organization_account_id, owner_account_id, public,
+        updated_at, uuid as collection_uuid,
+    };
+    use crate::schema::app_collections_ayahs::dsl::app_collections_ayahs;
+    use crate::schema::app_users::dsl::{account_id as user_acc_id, app_users, id as user_id};
+
+    let target_collection_uuid = path.into_inner();
+    let new_collection = new_collection.into_inner();
+    let data = data.into_inner() as i32;
+
+    validate(&new_collection)?;
+
+    web::block(move || {
+        let mut conn = pool.get().unwrap();
+
+        let user: i32 = app_users
+            .filter(user_acc_id.eq(data))
+            .select(user_id)
+            .get_result(&mut conn)?;
+
+        let organization = shared_organization(&mut conn, data, new_collection.organization_uuid)?;
+        let items = resolve_items(&mut conn, new_collection.items)?;
+
+        conn.transaction(|conn| {
+            let collection: Option<Collection> = diesel::update(
+                app_collections
+                    .filter(collection_uuid.eq(target_collection_uuid))
+                    .filter(owner_account_id.eq(data)),
+            )
+            .set((
+                name.eq(new_collection.name),
+                description.eq(new_collection.description),
+                public.eq(new_collection.public),
+                organization_account_id.eq(organization),
+                updated_at.eq(Utc::now().naive_utc()),
+            ))
+            .get_result(conn)
+            .optional()?;
+
+            let Some(collection) = collection else {
+                return Err(not_owned_error(conn, target_collection_uuid));
+            };
+
+            diesel::delete(CollectionAyah::belonging_to(&collection)).execute(conn)?;
+
+            let items: Vec<NewCollectionAyah> = items
+                .into_iter()
+                .enumerate()
+                .map(|(index, (first, last, note))| NewCollectionAyah {
+                    creator_user_id: user,
+                    collection_id: collection.id,
+                    position: index as i32 + 1,
+                    first_ayah_id: first,
+                    last_ayah_id: last,
+                    note,
+                })
+                .collect();
+
+            items.insert_into(app_collections_ayahs).execute(conn)?;
+
+            Ok("Edited")
+        })
+    })
+    .await
+    .unwrap()
+}
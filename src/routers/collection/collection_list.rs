@@ -0,0 +1,79 @@
+use std::collections::HashMap;
+
+use crate::error::RouterError;
+use crate::models::Collection;
+use crate::DbPool;
+use actix_web::web;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use super::CollectionListItem;
+
+/// Returns the collections of the account
+/// and the collections shared with its organizations
+pub async fn collection_list(
+    pool: web::Data<DbPool>,
+    data: web::ReqData<u32>,
+) -> Result<web::Json<Vec<CollectionListItem>>, RouterError> {
+    use crate::schema::app_accounts::dsl::{app_accounts, id as acc_id, uuid as acc_uuid};
+    use crate::schema::app_collections::dsl::{
+        app_collections, organization_account_id, owner_account_id, updated_at,
+    };
+    use crate::schema::app_employees::dsl::{app_employees, employee_account_id, org_account_id};
+    use crate::schema::app_organizations::dsl::{
+        account_id as org_acc_id, app_organizations, owner_account_id as org_owner_account_id,
+    };
+
+    let data = data.into_inner() as i32;
+
+    web::block(move || {
+        let mut conn = pool.get().unwrap();
+
+        let mut organizations: Vec<i32> = app_employees
+            .filter(employee_account_id.eq(data))
+            .select(org_account_id)
+            .get_results(&mut conn)?;
+
+        organizations.extend(
+            app_organizations
+                .filter(org_owner_account_id.eq(data))
+                .select(org_acc_id)
+                .get_results::<i32>(&mut conn)?,
+        );
+
+        let collections: Vec<Collection> = app_collections
+            .filter(
+                owner_account_id
+                    .eq(data)
+                    .or(organization_account_id.eq_any(&organizations)),
+            )
+            .order(updated_at.desc())
+            .get_results(&mut conn)?;
+
+        let organization_uuids: HashMap<i32, Uuid> = app_accounts
+            .filter(acc_id.eq_any(collections.iter().filter_map(|c| c.organization_account_id)))
+            .select((acc_id, acc_uuid))
+            .get_results::<(i32, Uuid)>(&mut conn)?
+            .into_iter()
+            .collect();
+
+        Ok(web::Json(
+            collections
+                .into_iter()
+                .map(|collection| CollectionListItem {
+                    uuid: collection.uuid,
+                    organization_uuid: collection
+                        .organization_account_id
+                        .and_then(|id| organization_uuids.get(&id).copied()),
+                    owned: collection.owner_account_id == data,
+                    name: collection.name,
+                    description: collection.description,
+                    public: collection.public,
+                    updated_at: collection.updated_at,
+                })
+                .collect(),
+        ))
+    })
+    .await
+    .unwrap()
+}
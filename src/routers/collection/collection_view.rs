@@ -0,0 +1,76 @@
+use crate::error::RouterError;
+use crate::models::Collection;
+use crate::DbPool;
+use actix_web::web;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use super::{collection_items, is_organization_member, CollectionView};
+
+/// Returns the collection with its items
+///
+/// Public collections are visible to everyone, private ones only to the owner
+/// and the members of the organization it's shared with
+pub async fn collection_view(
+    path: web::Path<Uuid>,
+    pool: web::Data<DbPool>,
+    data: Option<web::ReqData<u32>>,
+) -> Result<web::Json<CollectionView>, RouterError> {
+    use crate::schema::app_accounts::dsl::{app_accounts, id as acc_id, uuid as acc_uuid};
+    use crate::schema::app_collections::dsl::{app_collections, uuid as collection_uuid};
+
+    let target_collection_uuid = path.into_inner();
+    let account = data.map(|data| data.into_inner() as i32);
+
+    web::block(move || {
+        let mut conn = pool.get().unwrap();
+
+        let collection: Collection = app_collections
+            .filter(collection_uuid.eq(target_collection_uuid))
+            .get_result(&mut conn)?;
+
+        let owned = account == Some(collection.owner_account_id);
+
+        let visible = collection.public
+            || owned
+            || match (account, collection.organization_account_id) {
+                (Some(account), Some(organization)) => {
+                    is_organization_member(&mut conn, account, organization)?
+                }
+
+                _ => false,
+            };
+
+        // Private collections are not found for the others
+        if !visible {
+            return Err(RouterError::from_predefined("NOT_FOUND"));
+        }
+
+        let organization_uuid: Option<Uuid> = match collection.organization_account_id {
+            Some(organization) => Some(
+                app_accounts
+                    .filter(acc_id.eq(organization))
+                    .select(acc_uuid)
+                    .get_result(&mut conn)?,
+            ),
+
+            None => None,
+        };
+
+        let items = collection_items(&mut conn, &collection)?;
+
+        Ok(web::Json(CollectionView {
+            uuid: collection.uuid,
+            name: collection.name,
+            description: collection.description,
+            public: collection.public,
+            organization_uuid,
+            owned,
+            created_at: collection.created_at,
+            updated_at: collection.updated_at,
+            items,
+        }))
+    })
+    .await
+    .unwrap()
+}
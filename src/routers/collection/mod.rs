@@ -0,0 +1,292 @@
+pub mod collection_add;
+pub mod collection_delete;
+pub mod collection_edit;
+pub mod collection_list;
+pub mod collection_view;
+
+use std::collections::HashMap;
+
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::error::RouterError;
+use crate::models::{
+    Collection, CollectionAyah, NewPermission, NewPermissionCondition, Permission,
+};
+use crate::verse_key::{ayah_positions, range_is_valid, AyahPosition, VerseKey};
+
+/// An ayah or a range of ayahs of the collection
+#[derive(Deserialize, Validate)]
+pub struct SimpleCollectionItem {
+    pub ayah_uuid: Uuid,
+
+    /// Last ayah of the range, the range is a single ayah if not set
+    pub last_ayah_uuid: Option<Uuid>,
+
+    #[validate(length(min = 1))]
+    pub note: Option<String>,
+}
+
+/// The request body for POST /collection and POST /collection/{uuid}
+#[derive(Deserialize, Validate)]
+pub struct SimpleCollection {
+    #[validate(length(min = 1, max = 200))]
+    pub name: String,
+
+    pub description: Option<String>,
+
+    /// Anyone with the link can view the collection
+    #[serde(default)]
+    pub public: bool,
+
+    /// Share the collection with the members of this organization
+    pub organization_uuid: Option<Uuid>,
+
+    /// Items in order
+    #[validate]
+    pub items: Vec<SimpleCollectionItem>,
+}
+
+#[derive(Serialize)]
+pub struct CollectionItemView {
+    pub uuid: Uuid,
+    pub first_ayah: VerseKey,
+    pub last_ayah: VerseKey,
+    pub note: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct CollectionListItem {
+    pub uuid: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub public: bool,
+    pub organization_uuid: Option<Uuid>,
+
+    /// Collection is owned by the account, not shared with it
+    pub owned: bool,
+    pub updated_at: NaiveDateTime,
+}
+
+/// The response type for GET /collection/{uuid}
+#[derive(Serialize)]
+pub struct CollectionView {
+    pub uuid: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub public: bool,
+    pub organization_uuid: Option<Uuid>,
+    pub owned: bool,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+    pub items: Vec<CollectionItemView>,
+}
+
+/// Resolves the ayah uuids of the items to (first_ayah_id, last_ayah_id, note)
+pub fn resolve_items(
+    conn: &mut PgConnection,
+    items: Vec<SimpleCollectionItem>,
+) -> Result<Vec<(i32, i32, Option<String>)>, RouterError> {
+    use crate::schema::quran_ayahs::dsl::{ayah_number, id as ayah_id, quran_ayahs, uuid};
+    use crate::schema::quran_surahs::dsl::{mushaf_id, number as surah_number, quran_surahs};
+
+    let uuids: Vec<Uuid> = items
+        .iter()
+        .flat_map(|item| [Some(item.ayah_uuid), item.last_ayah_uuid])
+        .flatten()
+        .collect();
+
+    let ayahs: HashMap<Uuid, (i32, AyahPosition)> = quran_ayahs
+        .inner_join(quran_surahs)
+        .filter(uuid.eq_any(uuids))
+        .select((uuid, ayah_id, mushaf_id, surah_number, ayah_number))
+        .get_results::<(Uuid, i32, i32, i32, i32)>(conn)?
+        .into_iter()
+        .map(|(ayah_uuid, id, mushaf, surah, ayah)| {
+            (
+                ayah_uuid,
+                (
+                    id,
+                    AyahPosition {
+                        mushaf_id: mushaf,
+                        key: VerseKey { surah, ayah },
+                    },
+                ),
+            )
+        })
+        .collect();
+
+    items
+        .into_iter()
+        .map(|item| {
+            let last_uuid = item.last_ayah_uuid.unwrap_or(item.ayah_uuid);
+
+            let (Some(first), Some(last)) = (ayahs.get(&item.ayah_uuid), ayahs.get(&last_uuid))
+            else {
+                return Err(RouterError::from_predefined("NOT_FOUND"));
+            };
+
+            if !range_is_valid(first.1, last.1) {
+                return Err(RouterError::from_predefined("COLLECTION_RANGE_NOT_VALID"));
+            }
+
+            Ok((first.0, last.0, item.note))
+        })
+        .collect()
+}
+
+/// Account is the owner or an employee of the organization
+pub fn is_organization_member(
+    conn: &mut PgConnection,
+    account: i32,
+    organization_account: i32,
+) -> QueryResult<bool> {
+    use crate::schema::app_employees::dsl::{app_employees, employee_account_id, org_account_id};
+    use crate::schema::app_organizations::dsl::{
+        account_id as org_acc_id, app_organizations, owner_account_id,
+    };
+
+    let owner: i64 = app_organizations
+        .filter(org_acc_id.eq(organization_account))
+        .filter(owner_account_id.eq(account))
+        .count()
+        .get_result(conn)?;
+
+    if owner > 0 {
+        return Ok(true);
+    }
+
+    let employee: i64 = app_employees
+        .filter(org_account_id.eq(organization_account))
+        .filter(employee_account_id.eq(account))
+        .count()
+        .get_result(conn)?;
+
+    Ok(employee > 0)
+}
+
+/// Returns the account id of the organization to share the collection with
+///
+/// The account must be a member of the organization
+pub fn shared_organization(
+    conn: &mut PgConnection,
+    account: i32,
+    organization_uuid: Option<Uuid>,
+) -> Result<Option<i32>, RouterError> {
+    use crate::schema::app_accounts::dsl::{account_type, app_accounts, id as acc_id, uuid};
+
+    let Some(organization_uuid) = organization_uuid else {
+        return Ok(None);
+    };
+
+    let organization: i32 = app_accounts
+        .filter(uuid.eq(organization_uuid))
+        .filter(account_type.eq("organization"))
+        .select(acc_id)
+        .get_result(conn)?;
+
+    if !is_organization_member(conn, account, organization)? {
+        return Err(RouterError::from_predefined("AUTHZ_PERMISSION_DENIED"));
+    }
+
+    Ok(Some(organization))
+}
+
+/// Actions of the collection that the AuthZ allows to the owner only
+const OWNER_ACTIONS: [&str; 2] = ["edit", "delete"];
+
+/// Grants the account the collection actions with the owner condition, if it doesn't have them
+///
+/// AuthZ denies an action without a permission, so the owner couldn't edit its own collections
+pub fn grant_owner_permissions(
+    conn: &mut PgConnection,
+    account: i32,
+    user: i32,
+) -> QueryResult<()> {
+    use crate::schema::app_permission_conditions::dsl::app_permission_conditions;
+    use crate::schema::app_permissions::dsl::{account_id, action, app_permissions, object};
+
+    for owner_action in OWNER_ACTIONS {
+        let granted: bool = diesel::select(diesel::dsl::exists(
+            app_permissions
+                .filter(account_id.eq(account))
+                .filter(object.eq("collection"))
+                .filter(action.eq(owner_action)),
+        ))
+        .get_result(conn)?;
+
+        if granted {
+            continue;
+        }
+
+        let permission: Permission = NewPermission {
+            creator_user_id: user,
+            account_id: account,
+            object: &"collection".to_string(),
+            action: &owner_action.to_string(),
+        }
+        .insert_into(app_permissions)
+        .get_result(conn)?;
+
+        NewPermissionCondition {
+            creator_user_id: user,
+            permission_id: permission.id,
+            name: "isOwner".to_string(),
+            value: "true".to_string(),
+        }
+        .insert_into(app_permission_conditions)
+        .execute(conn)?;
+    }
+
+    Ok(())
+}
+
+/// Error of a collection that is not changed by the owner filter
+///
+/// NOT_FOUND if there is no collection with the uuid, otherwise the account is not its owner
+pub fn not_owned_error(conn: &mut PgConnection, target_collection_uuid: Uuid) -> RouterError {
+    use crate::schema::app_collections::dsl::{app_collections, uuid as collection_uuid};
+
+    let exists = diesel::select(diesel::dsl::exists(
+        app_collections.filter(collection_uuid.eq(target_collection_uuid)),
+    ))
+    .get_result::<bool>(conn);
+
+    match exists {
+        Ok(true) => RouterError::from_predefined("AUTHZ_PERMISSION_DENIED"),
+        Ok(false) => RouterError::from_predefined("NOT_FOUND"),
+        Err(err) => RouterError::from(err),
+    }
+}
+
+/// Returns the items of the collection in order
+pub fn collection_items(
+    conn: &mut PgConnection,
+    collection: &Collection,
+) -> QueryResult<Vec<CollectionItemView>> {
+    use crate::schema::app_collections_ayahs::dsl::position;
+
+    let items: Vec<CollectionAyah> = CollectionAyah::belonging_to(collection)
+        .order(position.asc())
+        .get_results(conn)?;
+
+    let positions = ayah_positions(
+        conn,
+        items.iter().flat_map(|i| [i.first_ayah_id, i.last_ayah_id]),
+    )?;
+
+    Ok(items
+        .into_iter()
+        .filter_map(|item| {
+            Some(CollectionItemView {
+                uuid: item.uuid,
+                first_ayah: positions.get(&item.first_ayah_id)?.key,
+                last_ayah: positions.get(&item.last_ayah_id)?.key,
+                note: item.note,
+            })
+        })
+        .collect())
+}
@@ -0,0 +1,62 @@
+use chrono::{Days, Utc};
+use diesel::prelude::*;
+
+use crate::error::RouterError;
+use crate::models::NewHifzRange;
+use crate::verse_key::{ayah_positions, range_is_valid};
+use crate::DbPool;
+use actix_web::web;
+
+use super::SimpleHifzRange;
+
+/// Marks a range of ayahs as memorized by the account
+///
+/// The first review is due tomorrow
+pub async fn hifz_add(
+    account: web::ReqData<u32>,
+    pool: web::Data<DbPool>,
+    new_range: web::Json<SimpleHifzRange>,
+) -> Result<&'static str, RouterError> {
+    use crate::schema::app_hifz_ranges::dsl::app_hifz_ranges;
+    use crate::schema::quran_ayahs::dsl::{id as ayah_id, quran_ayahs, uuid as ayah_uuid};
+
+    let account = account.into_inner() as i32;
+    let new_range = new_range.into_inner();
+
+    web::block(move || {
+        let mut conn = pool.get().unwrap();
+
+        let first: i32 = quran_ayahs
+            .filter(ayah_uuid.eq(new_range.ayah_uuid))
+            .select(ayah_id)
+            .get_result(&mut conn)?;
+
+        let last: i32 = match new_range.last_ayah_uuid {
+            Some(last_uuid) => quran_ayahs
+                .filter(ayah_uuid.eq(last_uuid))
+                .select(ayah_id)
+                .get_result(&mut conn)?,
+
+            None => first,
+        };
+
+        let positions = ayah_positions(&mut conn, [first, last])?;
+
+        if !range_is_valid(positions[&first], positions[&last]) {
+            return Err(RouterError::from_predefined("HIFZ_RANGE_NOT_VALID"));
+        }
+
+        NewHifzRange {
+            account_id: account,
+            first_ayah_id: first,
+            last_ayah_id: last,
+            due_on: Utc::now().date_naive() + Days::new(1),
+        }
+        .insert_into(app_hifz_ranges)
+        .execute(&mut conn)?;
+
+        Ok("Added")
+    })
+    .await
+    .unwrap()
+}
@@ -0,0 +1,37 @@
+use diesel::prelude::*;
+
+use crate::error::RouterError;
+use crate::DbPool;
+use actix_web::web;
+use uuid::Uuid;
+
+/// Delete's a memorized range with its reviews
+pub async fn hifz_delete(
+    path: web::Path<Uuid>,
+    account: web::ReqData<u32>,
+    pool: web::Data<DbPool>,
+) -> Result<&'static str, RouterError> {
+    use crate::schema::app_hifz_ranges::dsl::{account_id, app_hifz_ranges, uuid as range_uuid};
+
+    let target_range_uuid = path.into_inner();
+    let account = account.into_inner() as i32;
+
+    web::block(move || {
+        let mut conn = pool.get().unwrap();
+
+        let deleted = diesel::delete(
+            app_hifz_ranges
+                .filter(range_uuid.eq(target_range_uuid))
+                .filter(account_id.eq(account)),
+        )
+        .execute(&mut conn)?;
+
+        if deleted == 0 {
+            return Err(RouterError::from_predefined("NOT_FOUND"));
+        }
+
+        Ok("Deleted")
+    })
+    .await
+    .unwrap()
+}
@@ -0,0 +1,35 @@
+use chrono::Utc;
+use diesel::prelude::*;
+
+use crate::error::RouterError;
+use crate::models::HifzRange;
+use crate::DbPool;
+use actix_web::web;
+
+use super::{range_views, HifzDueQuery, HifzRangeView};
+
+/// Returns the ranges that must be reviewed today, the overdue ones first
+pub async fn hifz_due(
+    account: web::ReqData<u32>,
+    pool: web::Data<DbPool>,
+    web::Query(query): web::Query<HifzDueQuery>,
+) -> Result<web::Json<Vec<HifzRangeView>>, RouterError> {
+    use crate::schema::app_hifz_ranges::dsl::{account_id, app_hifz_ranges, due_on, id};
+
+    let account = account.into_inner() as i32;
+    let today = query.date.unwrap_or_else(|| Utc::now().date_naive());
+
+    web::block(move || {
+        let mut conn = pool.get().unwrap();
+
+        let ranges: Vec<HifzRange> = app_hifz_ranges
+            .filter(account_id.eq(account))
+            .filter(due_on.le(today))
+            .order((due_on.asc(), id.asc()))
+            .get_results(&mut conn)?;
+
+        Ok(web::Json(range_views(&mut conn, ranges)?))
+    })
+    .await
+    .unwrap()
+}
@@ -0,0 +1,31 @@
+use diesel::prelude::*;
+
+use crate::error::RouterError;
+use crate::models::HifzRange;
+use crate::DbPool;
+use actix_web::web;
+
+use super::{range_views, HifzRangeView};
+
+/// Returns the memorized ranges of the account, the nearest review first
+pub async fn hifz_list(
+    account: web::ReqData<u32>,
+    pool: web::Data<DbPool>,
+) -> Result<web::Json<Vec<HifzRangeView>>, RouterError> {
+    use crate::schema::app_hifz_ranges::dsl::{account_id, app_hifz_ranges, due_on, id};
+
+    let account = account.into_inner() as i32;
+
+    web::block(move || {
+        let mut conn = pool.get().unwrap();
+
+        let ranges: Vec<HifzRange> = app_hifz_ranges
+            .filter(account_id.eq(account))
+            .order((due_on.asc(), id.asc()))
+            .get_results(&mut conn)?;
+
+        Ok(web::Json(range_views(&mut conn, ranges)?))
+    })
+    .await
+    .unwrap()
+}
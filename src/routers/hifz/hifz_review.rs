@@ -0,0 +1,121 @@
+use std::collections::HashMap;
+
+use chrono::{Days, Utc};
+use diesel::prelude::*;
+
+use crate::error::RouterError;
+use crate::models::{HifzRange, HifzReview, NewHifzMistake, NewHifzReview};
+use crate::validate::validate;
+use crate::verse_key::{ayah_positions, range_contains};
+use crate::DbPool;
+use actix_web::web;
+use uuid::Uuid;
+
+use super::{Schedule, SimpleHifzReview};
+
+/// Records a review of the memorized range and schedules the next one
+///
+/// Mistakes must be on the words of the range
+pub async fn hifz_review(
+    path: web::Path<Uuid>,
+    account: web::ReqData<u32>,
+    pool: web::Data<DbPool>,
+    new_review: web::Json<SimpleHifzReview>,
+) -> Result<&'static str, RouterError> {
+    use crate::schema::app_hifz_mistakes::dsl::app_hifz_mistakes;
+    use crate::schema::app_hifz_ranges::dsl::{
+        account_id, app_hifz_ranges, due_on, ease_factor, interval_days, last_reviewed_at,
+        repetitions, updated_at, uuid as range_uuid,
+    };
+    use crate::schema::app_hifz_reviews::dsl::app_hifz_reviews;
+    use crate::schema::quran_words::dsl::{
+        ayah_id as word_ayah_id, id as word_id, quran_words, uuid as word_uuid,
+    };
+
+    let target_range_uuid = path.into_inner();
+    let account = account.into_inner() as i32;
+    let new_review = new_review.into_inner();
+
+    validate(&new_review)?;
+
+    web::block(move || {
+        let mut conn = pool.get().unwrap();
+
+        // Mistakes of the same word are added together
+        let mut mistakes: HashMap<Uuid, i32> = HashMap::new();
+        for mistake in &new_review.mistakes {
+            *mistakes.entry(mistake.word_uuid).or_default() += mistake.count;
+        }
+
+        let words: Vec<(Uuid, i32, i32)> = quran_words
+            .filter(word_uuid.eq_any(mistakes.keys()))
+            .select((word_uuid, word_id, word_ayah_id))
+            .get_results(&mut conn)?;
+
+        if words.len() != mistakes.len() {
+            return Err(RouterError::from_predefined("NOT_FOUND"));
+        }
+
+        conn.transaction(|conn| {
+            let range: HifzRange = app_hifz_ranges
+                .filter(range_uuid.eq(target_range_uuid))
+                .filter(account_id.eq(account))
+                .for_update()
+                .get_result(conn)?;
+
+            let positions = ayah_positions(
+                conn,
+                words
+                    .iter()
+                    .map(|(_, _, ayah)| *ayah)
+                    .chain([range.first_ayah_id, range.last_ayah_id]),
+            )?;
+
+            let first = positions[&range.first_ayah_id];
+            let last = positions[&range.last_ayah_id];
+
+            if !words
+                .iter()
+                .all(|(_, _, ayah)| range_contains(first, last, positions[ayah]))
+            {
+                return Err(RouterError::from_predefined("HIFZ_WORD_NOT_IN_RANGE"));
+            }
+
+            let now = Utc::now().naive_utc();
+            let schedule = Schedule::from_range(&range).review(new_review.grade);
+
+            diesel::update(&range)
+                .set((
+                    repetitions.eq(schedule.repetitions),
+                    interval_days.eq(schedule.interval_days),
+                    ease_factor.eq(schedule.ease_factor),
+                    due_on.eq(now.date() + Days::new(schedule.interval_days as u64)),
+                    last_reviewed_at.eq(now),
+                    updated_at.eq(now),
+                ))
+                .execute(conn)?;
+
+            let review: HifzReview = NewHifzReview {
+                range_id: range.id,
+                grade: new_review.grade,
+            }
+            .insert_into(app_hifz_reviews)
+            .get_result(conn)?;
+
+            let new_mistakes: Vec<NewHifzMistake> = words
+                .iter()
+                .map(|(uuid, id, _)| NewHifzMistake {
+                    review_id: review.id,
+                    word_id: *id,
+                    mistakes: mistakes[uuid],
+                })
+                .collect();
+
+            new_mistakes.insert_into(app_hifz_mistakes).execute(conn)?;
+
+            Ok("Added")
+        })
+    })
+    .await
+    .unwrap()
+}
@@ -0,0 +1,66 @@
+use diesel::prelude::*;
+
+use crate::error::RouterError;
+use crate::models::{HifzRange, HifzReview};
+use crate::DbPool;
+use actix_web::web;
+use uuid::Uuid;
+
+use super::{range_views, HifzRangeDetail, HifzWordMistakes};
+
+/// Returns a memorized range with its reviews and the mistaken words
+pub async fn hifz_view(
+    path: web::Path<Uuid>,
+    account: web::ReqData<u32>,
+    pool: web::Data<DbPool>,
+) -> Result<web::Json<HifzRangeDetail>, RouterError> {
+    use crate::schema::app_hifz_mistakes::dsl::{app_hifz_mistakes, mistakes};
+    use crate::schema::app_hifz_ranges::dsl::{account_id, app_hifz_ranges, uuid as range_uuid};
+    use crate::schema::app_hifz_reviews::dsl::{app_hifz_reviews, created_at, range_id};
+    use crate::schema::quran_words::dsl::{id as word_id, quran_words, uuid as word_uuid, word};
+
+    let target_range_uuid = path.into_inner();
+    let account = account.into_inner() as i32;
+
+    web::block(move || {
+        let mut conn = pool.get().unwrap();
+
+        let range: HifzRange = app_hifz_ranges
+            .filter(range_uuid.eq(target_range_uuid))
+            .filter(account_id.eq(account))
+            .get_result(&mut conn)?;
+
+        let reviews: Vec<HifzReview> = HifzReview::belonging_to(&range)
+            .order(created_at.desc())
+            .get_results(&mut conn)?;
+
+        let mut word_mistakes: Vec<HifzWordMistakes> = app_hifz_mistakes
+            .inner_join(app_hifz_reviews)
+            .inner_join(quran_words)
+            .filter(range_id.eq(range.id))
+            .group_by((word_id, word_uuid, word))
+            .select((word_uuid, word, diesel::dsl::sum(mistakes)))
+            .get_results::<(Uuid, String, Option<i64>)>(&mut conn)?
+            .into_iter()
+            .map(|(uuid, text, count)| HifzWordMistakes {
+                word_uuid: uuid,
+                word: text,
+                mistakes: count.unwrap_or(0),
+            })
+            .collect();
+
+        word_mistakes.sort_by_key(|w| std::cmp::Reverse(w.mistakes));
+
+        let Some(range) = range_views(&mut conn, vec![range])?.pop() else {
+            return Err(RouterError::from_predefined("NOT_FOUND"));
+        };
+
+        Ok(web::Json(HifzRangeDetail {
+            range,
+            reviews,
+            mistakes: word_mistakes,
+        }))
+    })
+    .await
+    .unwrap()
+}
@@ -0,0 +1,222 @@
+pub mod hifz_add;
+pub mod hifz_delete;
+pub mod hifz_due;
+pub mod hifz_list;
+pub mod hifz_review;
+pub mod hifz_view;
+
+use std::collections::HashMap;
+
+use chrono::{NaiveDate, NaiveDateTime};
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::models::{HifzRange, HifzReview};
+use crate::verse_key::{ayah_positions, VerseKey};
+
+/// Lowest ease factor of SM-2, reviews of the hard ranges don't get too frequent
+const MIN_EASE_FACTOR: f64 = 1.3;
+
+/// Grades lower than this are failed reviews, the range must be memorized again
+const PASSING_GRADE: i32 = 3;
+
+/// The request body for POST /hifz
+#[derive(Deserialize)]
+pub struct SimpleHifzRange {
+    pub ayah_uuid: Uuid,
+
+    /// Last ayah of the range, the range is a single ayah if not set
+    pub last_ayah_uuid: Option<Uuid>,
+}
+
+#[derive(Deserialize, Validate)]
+pub struct SimpleHifzMistake {
+    pub word_uuid: Uuid,
+
+    /// Number of the mistakes on the word
+    #[validate(range(min = 1))]
+    pub count: i32,
+}
+
+/// The request body for POST /hifz/{uuid}/review
+#[derive(Deserialize, Validate)]
+pub struct SimpleHifzReview {
+    /// 0 (forgotten) to 5 (perfect)
+    #[validate(range(min = 0, max = 5))]
+    pub grade: i32,
+
+    /// Words of the range that were recited wrong
+    #[serde(default)]
+    #[validate]
+    pub mistakes: Vec<SimpleHifzMistake>,
+}
+
+#[derive(Deserialize)]
+pub struct HifzDueQuery {
+    /// Today of the client, server date by default
+    pub date: Option<NaiveDate>,
+}
+
+#[derive(Serialize)]
+pub struct HifzRangeView {
+    pub uuid: Uuid,
+    pub mushaf_uuid: Uuid,
+    pub first_ayah: VerseKey,
+    pub last_ayah: VerseKey,
+    pub repetitions: i32,
+    pub interval_days: i32,
+    pub ease_factor: f64,
+    pub due_on: NaiveDate,
+    pub last_reviewed_at: Option<NaiveDateTime>,
+}
+
+/// Total mistakes on a word in all the reviews of the range
+#[derive(Serialize)]
+pub struct HifzWordMistakes {
+    pub word_uuid: Uuid,
+    pub word: String,
+    pub mistakes: i64,
+}
+
+/// The response type for GET /hifz/{uuid}
+#[derive(Serialize)]
+pub struct HifzRangeDetail {
+    #[serde(flatten)]
+    pub range: HifzRangeView,
+
+    /// Latest first
+    pub reviews: Vec<HifzReview>,
+
+    /// Most mistaken first
+    pub mistakes: Vec<HifzWordMistakes>,
+}
+
+/// Review schedule of a range, SM-2 algorithm
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Schedule {
+    pub repetitions: i32,
+    pub interval_days: i32,
+    pub ease_factor: f64,
+}
+
+impl Schedule {
+    pub fn from_range(range: &HifzRange) -> Self {
+        Self {
+            repetitions: range.repetitions,
+            interval_days: range.interval_days,
+            ease_factor: range.ease_factor,
+        }
+    }
+
+    /// Returns the next schedule after a review with this grade
+    pub fn review(self, grade: i32) -> Self {
+        let grade = grade.clamp(0, 5);
+        let wrong = (5 - grade) as f64;
+        let ease_factor =
+            (self.ease_factor + 0.1 - wrong * (0.08 + wrong * 0.02)).max(MIN_EASE_FACTOR);
+
+        if grade < PASSING_GRADE {
+            return Self {
+                repetitions: 0,
+                interval_days: 1,
+                ease_factor,
+            };
+        }
+
+        let interval_days = match self.repetitions {
+            0 => 1,
+            1 => 6,
+            _ => (self.interval_days as f64 * self.ease_factor).round() as i32,
+        };
+
+        Self {
+            repetitions: self.repetitions + 1,
+            interval_days,
+            ease_factor,
+        }
+    }
+}
+
+/// Returns the views of the ranges in the same order
+pub fn range_views(
+    conn: &mut PgConnection,
+    ranges: Vec<HifzRange>,
+) -> QueryResult<Vec<HifzRangeView>> {
+    use crate::schema::quran_mushafs::dsl::{id as mushaf_id, quran_mushafs, uuid as mushaf_uuid};
+
+    let positions = ayah_positions(
+        conn,
+        ranges
+            .iter()
+            .flat_map(|range| [range.first_ayah_id, range.last_ayah_id]),
+    )?;
+
+    let mushafs: HashMap<i32, Uuid> = quran_mushafs
+        .filter(mushaf_id.eq_any(positions.values().map(|position| position.mushaf_id)))
+        .select((mushaf_id, mushaf_uuid))
+        .get_results::<(i32, Uuid)>(conn)?
+        .into_iter()
+        .collect();
+
+    Ok(ranges
+        .into_iter()
+        .filter_map(|range| {
+            let first_ayah = positions.get(&range.first_ayah_id)?;
+            let last_ayah = positions.get(&range.last_ayah_id)?;
+
+            Some(HifzRangeView {
+                uuid: range.uuid,
+                mushaf_uuid: *mushafs.get(&first_ayah.mushaf_id)?,
+                first_ayah: first_ayah.key,
+                last_ayah: last_ayah.key,
+                repetitions: range.repetitions,
+                interval_days: range.interval_days,
+                ease_factor: range.ease_factor,
+                due_on: range.due_on,
+                last_reviewed_at: range.last_reviewed_at,
+            })
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NEW: Schedule = Schedule {
+        repetitions: 0,
+        interval_days: 0,
+        ease_factor: 2.5,
+    };
+
+    #[test]
+    fn test_schedule_intervals() {
+        let first = NEW.review(5);
+        assert_eq!(first.interval_days, 1);
+        assert!((first.ease_factor - 2.6).abs() < 1e-9);
+
+        let second = first.review(4);
+        assert_eq!(second.interval_days, 6);
+        assert_eq!(second.repetitions, 2);
+
+        let third = second.review(4);
+        assert_eq!(third.interval_days, 16);
+    }
+
+    #[test]
+    fn test_schedule_failed_review() {
+        let failed = NEW.review(5).review(5).review(1);
+
+        assert_eq!(failed.repetitions, 0);
+        assert_eq!(failed.interval_days, 1);
+
+        let mut hard = NEW;
+        for _ in 0..10 {
+            hard = hard.review(0);
+        }
+
+        assert_eq!(hard.ease_factor, MIN_EASE_FACTOR);
+    }
+}
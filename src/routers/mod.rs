@@ -1,10 +1,16 @@
 pub mod account;
+pub mod cache;
+pub mod collection;
 pub mod error;
+pub mod hifz;
 pub mod organization;
 pub mod permission;
 pub mod phrase;
+pub mod plan;
 pub mod profile;
 pub mod quran;
+pub mod reciter;
+pub mod sync;
 pub mod translation;
 pub mod user;
 
@@ -0,0 +1,191 @@
+pub mod plan_add;
+pub mod plan_day_edit;
+pub mod plan_delete;
+pub mod plan_list;
+pub mod plan_view;
+
+use std::fmt::Display;
+use std::ops::Range;
+use std::str::FromStr;
+
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::verse_key::VerseKey;
+
+/// Where the daily portions of a plan can start
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum PlanSnap {
+    /// Any ayah
+    #[default]
+    Ayah,
+
+    /// First ayah of a surah
+    Surah,
+
+    /// Ayah of a breaker, e.g. juz or hizb
+    Breaker,
+}
+
+impl Display for PlanSnap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Ayah => write!(f, "ayah"),
+            Self::Surah => write!(f, "surah"),
+            Self::Breaker => write!(f, "breaker"),
+        }
+    }
+}
+
+impl FromStr for PlanSnap {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ayah" => Ok(Self::Ayah),
+            "surah" => Ok(Self::Surah),
+            "breaker" => Ok(Self::Breaker),
+
+            _ => Err(()),
+        }
+    }
+}
+
+/// The request body for POST /plan
+#[derive(Deserialize, Validate)]
+pub struct SimplePlan {
+    pub mushaf_uuid: Uuid,
+
+    /// Finish the mushaf in this number of days
+    #[validate(range(min = 1))]
+    pub days: i32,
+
+    #[serde(default)]
+    pub snap: PlanSnap,
+
+    /// Name of the breaker, required if snap is breaker
+    #[validate(length(min = 1, max = 256))]
+    pub breaker: Option<String>,
+}
+
+/// The request body for POST /plan/{uuid}/{day}
+#[derive(Deserialize)]
+pub struct PlanDayEdit {
+    pub completed: bool,
+}
+
+#[derive(Serialize)]
+pub struct PlanListItem {
+    pub uuid: Uuid,
+    pub mushaf_uuid: Uuid,
+    pub days: i32,
+    pub snap: PlanSnap,
+    pub breaker: Option<String>,
+    pub completed_days: i64,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Serialize)]
+pub struct PlanDayView {
+    pub day: i32,
+    pub first_ayah: VerseKey,
+    pub last_ayah: VerseKey,
+    pub words: i32,
+    pub completed_at: Option<NaiveDateTime>,
+}
+
+/// The response type for GET /plan/{uuid}
+#[derive(Serialize)]
+pub struct PlanView {
+    pub uuid: Uuid,
+    pub mushaf_uuid: Uuid,
+    pub days: i32,
+    pub snap: PlanSnap,
+    pub breaker: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub portions: Vec<PlanDayView>,
+}
+
+/// Splits the ayahs into `days` portions, with the nearest sum of weights to each other
+///
+/// `starts` are the ayahs that a portion can start from, the first ayah always starts one.
+/// Returns None if there is not enough starts for the days
+pub fn split_portions(weights: &[u64], starts: &[bool], days: usize) -> Option<Vec<Range<usize>>> {
+    if days == 0 || weights.is_empty() {
+        return None;
+    }
+
+    // The ayahs that the second and later portions can start from
+    let cuts: Vec<usize> = (1..weights.len()).filter(|i| starts[*i]).collect();
+
+    if cuts.len() < days - 1 {
+        return None;
+    }
+
+    let mut prefix = vec![0; weights.len() + 1];
+    for (i, weight) in weights.iter().enumerate() {
+        prefix[i + 1] = prefix[i] + weight;
+    }
+
+    let total = prefix[weights.len()];
+    let mut bounds = vec![0];
+    let mut lo = 0;
+
+    for day in 1..days {
+        // Leave enough cuts for the remaining days
+        let hi = cuts.len() - (days - day);
+        let target = total * day as u64 / days as u64;
+
+        // First cut that reaches the target, or the one before it if that is nearer
+        let mut cut = lo + cuts[lo..=hi].partition_point(|c| prefix[*c] < target);
+
+        if cut > hi || (cut > lo && target - prefix[cuts[cut - 1]] <= prefix[cuts[cut]] - target) {
+            cut -= 1;
+        }
+
+        bounds.push(cuts[cut]);
+        lo = cut + 1;
+    }
+
+    bounds.push(weights.len());
+
+    Some(bounds.windows(2).map(|w| w[0]..w[1]).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_portions_balanced() {
+        let weights = [3, 3, 3, 3, 3, 3];
+
+        assert_eq!(
+            split_portions(&weights, &[true; 6], 3),
+            Some(vec![0..2, 2..4, 4..6])
+        );
+        assert_eq!(split_portions(&weights, &[true; 6], 1).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_split_portions_snapped() {
+        let weights = [5, 1, 1, 5, 1, 1];
+        let starts = [true, false, false, true, false, false];
+
+        assert_eq!(split_portions(&weights, &starts, 2), Some(vec![0..3, 3..6]));
+        assert_eq!(split_portions(&weights, &starts, 3), None);
+    }
+
+    #[test]
+    fn test_split_portions_every_day_has_an_ayah() {
+        let weights = [100, 1, 1];
+
+        assert_eq!(
+            split_portions(&weights, &[true; 3], 3),
+            Some(vec![0..1, 1..2, 2..3])
+        );
+    }
+}
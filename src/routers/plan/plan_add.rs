@@ -0,0 +1,131 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::error::RouterError;
+use crate::models::{NewQuranPlan, NewQuranPlanDay, QuranPlan};
+use crate::validate::validate;
+use crate::DbPool;
+use actix_web::web;
+use diesel::prelude::*;
+
+use super::{split_portions, PlanSnap, SimplePlan};
+
+/// Add's a new reading plan for the user
+///
+/// The mushaf is split into daily portions by the number of words
+pub async fn plan_add(
+    new_plan: web::Json<SimplePlan>,
+    pool: web::Data<DbPool>,
+    data: web::ReqData<u32>,
+) -> Result<&'static str, RouterError> {
+    use crate::schema::app_users::dsl::{account_id as user_acc_id, app_users, id as user_id};
+    use crate::schema::quran_ayahs::dsl::{ayah_number, id as ayah_id, quran_ayahs};
+    use crate::schema::quran_ayahs_breakers::dsl::{
+        ayah_id as breaker_ayah_id, name as breaker_name, quran_ayahs_breakers,
+    };
+    use crate::schema::quran_mushafs::dsl::{id as mushaf_id, quran_mushafs, uuid as mushaf_uuid};
+    use crate::schema::quran_plans::dsl::quran_plans;
+    use crate::schema::quran_plans_days::dsl::quran_plans_days;
+    use crate::schema::quran_surahs::dsl::{
+        mushaf_id as surah_mushaf_id, number as surah_number, quran_surahs,
+    };
+    use crate::schema::quran_words::dsl::{ayah_id as word_ayah_id, id as word_id, quran_words};
+
+    let new_plan = new_plan.into_inner();
+    let data = data.into_inner();
+
+    validate(&new_plan)?;
+
+    if new_plan.snap == PlanSnap::Breaker && new_plan.breaker.is_none() {
+        return Err(RouterError::from_predefined("PLAN_BREAKER_NOT_DEFINED"));
+    }
+
+    web::block(move || {
+        let mut conn = pool.get().unwrap();
+
+        let user: i32 = app_users
+            .filter(user_acc_id.eq(data as i32))
+            .select(user_id)
+            .get_result(&mut conn)?;
+
+        let mushaf: i32 = quran_mushafs
+            .filter(mushaf_uuid.eq(new_plan.mushaf_uuid))
+            .select(mushaf_id)
+            .get_result(&mut conn)?;
+
+        let ayahs: Vec<(i32, i32)> = quran_ayahs
+            .inner_join(quran_surahs)
+            .filter(surah_mushaf_id.eq(mushaf))
+            .order((surah_number.asc(), ayah_number.asc()))
+            .select((ayah_id, ayah_number))
+            .get_results(&mut conn)?;
+
+        let words_count: HashMap<i32, i64> = quran_words
+            .inner_join(quran_ayahs.inner_join(quran_surahs))
+            .filter(surah_mushaf_id.eq(mushaf))
+            .group_by(word_ayah_id)
+            .select((word_ayah_id, diesel::dsl::count(word_id)))
+            .get_results::<(i32, i64)>(&mut conn)?
+            .into_iter()
+            .collect();
+
+        let starts: Vec<bool> = match (new_plan.snap, &new_plan.breaker) {
+            (PlanSnap::Breaker, Some(name)) => {
+                let breakers: HashSet<i32> = quran_ayahs_breakers
+                    .filter(breaker_name.eq(name))
+                    .select(breaker_ayah_id)
+                    .get_results::<i32>(&mut conn)?
+                    .into_iter()
+                    .collect();
+
+                ayahs.iter().map(|(id, _)| breakers.contains(id)).collect()
+            }
+
+            (PlanSnap::Surah, _) => ayahs.iter().map(|(_, number)| *number == 1).collect(),
+
+            _ => vec![true; ayahs.len()],
+        };
+
+        let weights: Vec<u64> = ayahs
+            .iter()
+            .map(|(id, _)| words_count.get(id).copied().unwrap_or(0) as u64)
+            .collect();
+
+        let Some(portions) = split_portions(&weights, &starts, new_plan.days as usize) else {
+            return Err(RouterError::from_predefined("PLAN_DAYS_NOT_VALID"));
+        };
+
+        conn.transaction(|conn| {
+            let plan: QuranPlan = NewQuranPlan {
+                creator_user_id: user,
+                mushaf_id: mushaf,
+                days: new_plan.days,
+                snap: new_plan.snap.to_string(),
+                breaker_name: match new_plan.snap {
+                    PlanSnap::Breaker => new_plan.breaker,
+                    _ => None,
+                },
+            }
+            .insert_into(quran_plans)
+            .get_result(conn)?;
+
+            let days: Vec<NewQuranPlanDay> = portions
+                .into_iter()
+                .enumerate()
+                .map(|(index, portion)| NewQuranPlanDay {
+                    creator_user_id: user,
+                    plan_id: plan.id,
+                    day: index as i32 + 1,
+                    first_ayah_id: ayahs[portion.start].0,
+                    last_ayah_id: ayahs[portion.end - 1].0,
+                    words: weights[portion].iter().sum::<u64>() as i32,
+                })
+                .collect();
+
+            days.insert_into(quran_plans_days).execute(conn)?;
+
+            Ok("Added")
+        })
+    })
+    .await
+    .unwrap()
+}
@@ -0,0 +1,59 @@
+use crate::error::RouterError;
+use crate::DbPool;
+use actix_web::web;
+use chrono::Utc;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use super::PlanDayEdit;
+
+/// Marks a day of the plan as completed or not completed
+pub async fn plan_day_edit(
+    path: web::Path<(Uuid, i32)>,
+    edit: web::Json<PlanDayEdit>,
+    pool: web::Data<DbPool>,
+    data: web::ReqData<u32>,
+) -> Result<&'static str, RouterError> {
+    use crate::schema::app_users::dsl::{account_id as user_acc_id, app_users, id as user_id};
+    use crate::schema::quran_plans::dsl::{
+        creator_user_id, id as plan_id, quran_plans, uuid as plan_uuid,
+    };
+    use crate::schema::quran_plans_days::dsl::{
+        completed_at, day, plan_id as day_plan_id, quran_plans_days,
+    };
+
+    let (target_plan_uuid, target_day) = path.into_inner();
+    let edit = edit.into_inner();
+    let data = data.into_inner();
+
+    web::block(move || {
+        let mut conn = pool.get().unwrap();
+
+        let user: i32 = app_users
+            .filter(user_acc_id.eq(data as i32))
+            .select(user_id)
+            .get_result(&mut conn)?;
+
+        let plan: i32 = quran_plans
+            .filter(plan_uuid.eq(target_plan_uuid))
+            .filter(creator_user_id.eq(user))
+            .select(plan_id)
+            .get_result(&mut conn)?;
+
+        let edited = diesel::update(
+            quran_plans_days
+                .filter(day_plan_id.eq(plan))
+                .filter(day.eq(target_day)),
+        )
+        .set(completed_at.eq(edit.completed.then(|| Utc::now().naive_utc())))
+        .execute(&mut conn)?;
+
+        if edited == 0 {
+            return Err(RouterError::from_predefined("NOT_FOUND"));
+        }
+
+        Ok("Edited")
+    })
+    .await
+    .unwrap()
+}
@@ -0,0 +1,42 @@
+use crate::error::RouterError;
+use crate::DbPool;
+use actix_web::web;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+/// Delete's a reading plan of the user
+pub async fn plan_delete(
+    path: web::Path<Uuid>,
+    pool: web::Data<DbPool>,
+    data: web::ReqData<u32>,
+) -> Result<&'static str, RouterError> {
+    use crate::schema::app_users::dsl::{account_id as user_acc_id, app_users, id as user_id};
+    use crate::schema::quran_plans::dsl::{creator_user_id, quran_plans, uuid as plan_uuid};
+
+    let target_plan_uuid = path.into_inner();
+    let data = data.into_inner();
+
+    web::block(move || {
+        let mut conn = pool.get().unwrap();
+
+        let user: i32 = app_users
+            .filter(user_acc_id.eq(data as i32))
+            .select(user_id)
+            .get_result(&mut conn)?;
+
+        let deleted = diesel::delete(
+            quran_plans
+                .filter(plan_uuid.eq(target_plan_uuid))
+                .filter(creator_user_id.eq(user)),
+        )
+        .execute(&mut conn)?;
+
+        if deleted == 0 {
+            return Err(RouterError::from_predefined("NOT_FOUND"));
+        }
+
+        Ok("Deleted")
+    })
+    .await
+    .unwrap()
+}
@@ -0,0 +1,68 @@
+use std::collections::HashMap;
+
+use crate::error::RouterError;
+use crate::models::QuranPlan;
+use crate::DbPool;
+use actix_web::web;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use super::PlanListItem;
+
+/// Returns the reading plans of the user
+pub async fn plan_list(
+    pool: web::Data<DbPool>,
+    data: web::ReqData<u32>,
+) -> Result<web::Json<Vec<PlanListItem>>, RouterError> {
+    use crate::schema::app_users::dsl::{account_id as user_acc_id, app_users, id as user_id};
+    use crate::schema::quran_mushafs::dsl::{quran_mushafs, uuid as mushaf_uuid};
+    use crate::schema::quran_plans::dsl::{created_at, creator_user_id, quran_plans};
+    use crate::schema::quran_plans_days::dsl::{
+        completed_at, id as day_id, plan_id as day_plan_id, quran_plans_days,
+    };
+
+    let data = data.into_inner();
+
+    web::block(move || {
+        let mut conn = pool.get().unwrap();
+
+        let user: i32 = app_users
+            .filter(user_acc_id.eq(data as i32))
+            .select(user_id)
+            .get_result(&mut conn)?;
+
+        let plans: Vec<(QuranPlan, Uuid)> = quran_plans
+            .inner_join(quran_mushafs)
+            .filter(creator_user_id.eq(user))
+            .order(created_at.desc())
+            .select((QuranPlan::as_select(), mushaf_uuid))
+            .get_results(&mut conn)?;
+
+        let completed: HashMap<i32, i64> = quran_plans_days
+            .filter(day_plan_id.eq_any(plans.iter().map(|(plan, _)| plan.id)))
+            .filter(completed_at.is_not_null())
+            .group_by(day_plan_id)
+            .select((day_plan_id, diesel::dsl::count(day_id)))
+            .get_results::<(i32, i64)>(&mut conn)?
+            .into_iter()
+            .collect();
+
+        Ok(web::Json(
+            plans
+                .into_iter()
+                .map(|(plan, m_uuid)| PlanListItem {
+                    uuid: plan.uuid,
+                    mushaf_uuid: m_uuid,
+                    days: plan.days,
+                    // Snap is checked by the database
+                    snap: plan.snap.parse().unwrap_or_default(),
+                    breaker: plan.breaker_name,
+                    completed_days: completed.get(&plan.id).copied().unwrap_or(0),
+                    created_at: plan.created_at,
+                })
+                .collect(),
+        ))
+    })
+    .await
+    .unwrap()
+}
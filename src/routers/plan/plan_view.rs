@@ -0,0 +1,81 @@
+use crate::error::RouterError;
+use crate::models::{QuranPlan, QuranPlanDay};
+use crate::verse_key::ayah_positions;
+use crate::DbPool;
+use actix_web::web;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use super::{PlanDayView, PlanView};
+
+/// Returns a reading plan of the user with its daily portions
+pub async fn plan_view(
+    path: web::Path<Uuid>,
+    pool: web::Data<DbPool>,
+    data: web::ReqData<u32>,
+) -> Result<web::Json<PlanView>, RouterError> {
+    use crate::schema::app_users::dsl::{account_id as user_acc_id, app_users, id as user_id};
+    use crate::schema::quran_mushafs::dsl::{quran_mushafs, uuid as mushaf_uuid};
+    use crate::schema::quran_plans::dsl::{creator_user_id, quran_plans, uuid as plan_uuid};
+    use crate::schema::quran_plans_days::dsl::day;
+
+    let target_plan_uuid = path.into_inner();
+    let data = data.into_inner();
+
+    web::block(move || {
+        let mut conn = pool.get().unwrap();
+
+        let user: i32 = app_users
+            .filter(user_acc_id.eq(data as i32))
+            .select(user_id)
+            .get_result(&mut conn)?;
+
+        let (plan, m_uuid): (QuranPlan, Uuid) = quran_plans
+            .inner_join(quran_mushafs)
+            .filter(plan_uuid.eq(target_plan_uuid))
+            .filter(creator_user_id.eq(user))
+            .select((QuranPlan::as_select(), mushaf_uuid))
+            .get_result(&mut conn)?;
+
+        let days: Vec<QuranPlanDay> = QuranPlanDay::belonging_to(&plan)
+            .order(day.asc())
+            .get_results(&mut conn)?;
+
+        let positions = ayah_positions(
+            &mut conn,
+            days.iter().flat_map(|d| [d.first_ayah_id, d.last_ayah_id]),
+        )?;
+
+        let mut portions = Vec::with_capacity(days.len());
+
+        for plan_day in days {
+            let (Some(first_ayah), Some(last_ayah)) = (
+                positions.get(&plan_day.first_ayah_id),
+                positions.get(&plan_day.last_ayah_id),
+            ) else {
+                return Err(RouterError::from_predefined("NOT_FOUND"));
+            };
+
+            portions.push(PlanDayView {
+                day: plan_day.day,
+                first_ayah: first_ayah.key,
+                last_ayah: last_ayah.key,
+                words: plan_day.words,
+                completed_at: plan_day.completed_at,
+            });
+        }
+
+        Ok(web::Json(PlanView {
+            uuid: plan.uuid,
+            mushaf_uuid: m_uuid,
+            days: plan.days,
+            // Snap is checked by the database
+            snap: plan.snap.parse().unwrap_or_default(),
+            breaker: plan.breaker_name,
+            created_at: plan.created_at,
+            portions,
+        }))
+    })
+    .await
+    .unwrap()
+}
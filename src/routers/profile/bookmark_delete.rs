@@ -0,0 +1,52 @@
+use chrono::Utc;
+use diesel::dsl::sql;
+use diesel::prelude::*;
+use diesel::sql_types::BigInt;
+
+use crate::error::RouterError;
+use crate::DbPool;
+use actix_web::web;
+use uuid::Uuid;
+
+/// Delete's a bookmark of the account
+///
+/// The bookmark is kept as deleted, so the other devices can sync it
+pub async fn bookmark_delete(
+    path: web::Path<Uuid>,
+    account: web::ReqData<u32>,
+    pool: web::Data<DbPool>,
+) -> Result<&'static str, RouterError> {
+    use crate::schema::app_bookmarks::dsl::{
+        account_id, app_bookmarks, deleted_at, synced_transaction, updated_at,
+        uuid as bookmark_uuid,
+    };
+
+    let target_uuid = path.into_inner();
+    let account = account.into_inner() as i32;
+
+    web::block(move || {
+        let mut conn = pool.get().unwrap();
+
+        let now = Utc::now().naive_utc();
+
+        let deleted = diesel::update(
+            app_bookmarks
+                .filter(bookmark_uuid.eq(target_uuid))
+                .filter(account_id.eq(account)),
+        )
+        .set((
+            deleted_at.eq(now),
+            synced_transaction.eq(sql::<BigInt>("txid_current()")),
+            updated_at.eq(now),
+        ))
+        .execute(&mut conn)?;
+
+        if deleted == 0 {
+            return Err(RouterError::from_predefined("NOT_FOUND"));
+        }
+
+        Ok("Deleted")
+    })
+    .await
+    .unwrap()
+}
@@ -0,0 +1,84 @@
+use diesel::dsl::sql;
+use diesel::prelude::*;
+use diesel::sql_types::BigInt;
+
+use crate::error::RouterError;
+use crate::models::Bookmark;
+use crate::verse_key::VerseKey;
+use crate::DbPool;
+use actix_web::web;
+use uuid::Uuid;
+
+use super::{BookmarkList, BookmarkSyncQuery, BookmarkView};
+
+/// Returns the bookmarks of the account,
+/// or the changed bookmarks since the last sync
+///
+/// Changes are synced by their transaction, only the transactions older than every
+/// running transaction are returned, so a change committed later is never skipped
+pub async fn bookmark_list(
+    account: web::ReqData<u32>,
+    pool: web::Data<DbPool>,
+    web::Query(query): web::Query<BookmarkSyncQuery>,
+) -> Result<web::Json<BookmarkList>, RouterError> {
+    use crate::schema::app_bookmarks::dsl::{
+        account_id, app_bookmarks, deleted_at, synced_transaction, updated_at,
+    };
+    use crate::schema::quran_ayahs::dsl::{ayah_number, quran_ayahs, uuid as ayah_uuid};
+    use crate::schema::quran_mushafs::dsl::{quran_mushafs, uuid as mushaf_uuid};
+    use crate::schema::quran_surahs::dsl::{number as surah_number, quran_surahs};
+
+    let account = account.into_inner() as i32;
+
+    web::block(move || {
+        let mut conn = pool.get().unwrap();
+
+        // Oldest running transaction, changes before it are all committed
+        let visible_before: i64 =
+            diesel::select(sql::<BigInt>("txid_snapshot_xmin(txid_current_snapshot())"))
+                .get_result(&mut conn)?;
+
+        let mut bookmarks = app_bookmarks
+            .inner_join(quran_ayahs.inner_join(quran_surahs.inner_join(quran_mushafs)))
+            .filter(account_id.eq(account))
+            .into_boxed();
+
+        bookmarks = match query.since {
+            Some(since) => bookmarks
+                .filter(synced_transaction.ge(since))
+                .filter(synced_transaction.lt(visible_before)),
+            None => bookmarks.filter(deleted_at.is_null()),
+        };
+
+        let bookmarks: Vec<(Bookmark, Uuid, Uuid, i32, i32)> = bookmarks
+            .order(updated_at.desc())
+            .select((
+                Bookmark::as_select(),
+                mushaf_uuid,
+                ayah_uuid,
+                surah_number,
+                ayah_number,
+            ))
+            .get_results(&mut conn)?;
+
+        Ok(web::Json(BookmarkList {
+            cursor: visible_before.max(query.since.unwrap_or_default()),
+            bookmarks: bookmarks
+                .into_iter()
+                .map(|(bookmark, m_uuid, a_uuid, surah, ayah)| BookmarkView {
+                    uuid: bookmark.uuid,
+                    mushaf_uuid: m_uuid,
+                    ayah_uuid: a_uuid,
+                    key: VerseKey { surah, ayah },
+                    note: bookmark.note,
+                    tags: bookmark.tags.into_iter().flatten().collect(),
+                    colour: bookmark.colour,
+                    deleted: bookmark.deleted_at.is_some(),
+                    updated_at: bookmark.updated_at,
+                })
+                .collect(),
+        }))
+    })
+    .await
+    .unwrap()
+}
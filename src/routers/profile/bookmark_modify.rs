@@ -0,0 +1,101 @@
+use chrono::Utc;
+use diesel::dsl::sql;
+use diesel::prelude::*;
+use diesel::sql_types::BigInt;
+
+use crate::error::RouterError;
+use crate::models::{Bookmark, NewBookmark};
+use crate::validate::validate;
+use crate::DbPool;
+use actix_web::web;
+
+use super::SimpleBookmark;
+
+/// Add's or updates a bookmark of the account
+///
+/// Changes older than the saved bookmark are ignored
+pub async fn bookmark_modify(
+    account: web::ReqData<u32>,
+    pool: web::Data<DbPool>,
+    new_bookmark: web::Json<SimpleBookmark>,
+) -> Result<&'static str, RouterError> {
+    use crate::schema::app_bookmarks::dsl::{
+        app_bookmarks, ayah_id as bookmark_ayah_id, colour, deleted_at, note, synced_transaction,
+        tags, updated_at, uuid as bookmark_uuid,
+    };
+    use crate::schema::quran_ayahs::dsl::{id as ayah_id, quran_ayahs, uuid as ayah_uuid};
+
+    let account = account.into_inner() as i32;
+    let new_bookmark = new_bookmark.into_inner();
+
+    validate(&new_bookmark)?;
+
+    web::block(move || {
+        let mut conn = pool.get().unwrap();
+
+        let now = Utc::now().naive_utc();
+        let changed_at = new_bookmark.updated_at.unwrap_or(now);
+        let new_tags: Vec<Option<String>> = new_bookmark.tags.into_iter().map(Some).collect();
+
+        let ayah: i32 = quran_ayahs
+            .filter(ayah_uuid.eq(new_bookmark.ayah_uuid))
+            .select(ayah_id)
+            .get_result(&mut conn)?;
+
+        conn.transaction(|conn| {
+            let saved: Option<Bookmark> = match new_bookmark.uuid {
+                Some(target_uuid) => app_bookmarks
+                    .filter(bookmark_uuid.eq(target_uuid))
+                    .for_update()
+                    .get_result(conn)
+                    .optional()?,
+
+                None => None,
+            };
+
+            match saved {
+                // Bookmark uuid of another account
+                Some(saved) if saved.account_id != account => {
+                    Err(RouterError::from_predefined("NOT_FOUND"))
+                }
+
+                // The saved bookmark is newer
+                Some(saved) if saved.updated_at >= changed_at => Ok("Modified"),
+
+                Some(saved) => {
+                    diesel::update(&saved)
+                        .set((
+                            bookmark_ayah_id.eq(ayah),
+                            note.eq(new_bookmark.note),
+                            tags.eq(new_tags),
+                            colour.eq(new_bookmark.colour),
+                            deleted_at.eq(None::<chrono::NaiveDateTime>),
+                            synced_transaction.eq(sql::<BigInt>("txid_current()")),
+                            updated_at.eq(changed_at),
+                        ))
+                        .execute(conn)?;
+
+                    Ok("Modified")
+                }
+
+                None => {
+                    NewBookmark {
+                        uuid: new_bookmark.uuid,
+                        account_id: account,
+                        ayah_id: ayah,
+                        note: new_bookmark.note,
+                        tags: new_tags,
+                        colour: new_bookmark.colour,
+                        updated_at: changed_at,
+                    }
+                    .insert_into(app_bookmarks)
+                    .execute(conn)?;
+
+                    Ok("Modified")
+                }
+            }
+        })
+    })
+    .await
+    .unwrap()
+}
@@ -1,2 +1,84 @@
+pub mod bookmark_delete;
+pub mod bookmark_list;
+pub mod bookmark_modify;
+pub mod position_list;
+pub mod position_modify;
 pub mod profile_edit;
 pub mod profile_view;
+
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::verse_key::VerseKey;
+
+/// The request body for POST /profile/bookmark
+///
+/// Bookmarks are merged by their uuid, the latest `updated_at` wins
+#[derive(Deserialize, Validate)]
+pub struct SimpleBookmark {
+    /// Can be generated by the device, so a bookmark made offline
+    /// keeps its uuid on every device
+    pub uuid: Option<Uuid>,
+
+    pub ayah_uuid: Uuid,
+
+    #[validate(length(max = 5000))]
+    pub note: Option<String>,
+
+    #[serde(default)]
+    pub tags: Vec<String>,
+
+    #[validate(length(min = 1, max = 20))]
+    pub colour: Option<String>,
+
+    /// When the bookmark was changed on the device, now by default
+    pub updated_at: Option<NaiveDateTime>,
+}
+
+/// The query for GET /profile/bookmark
+#[derive(Deserialize)]
+pub struct BookmarkSyncQuery {
+    /// The `cursor` of the last sync, returns the deleted bookmarks too
+    pub since: Option<i64>,
+}
+
+#[derive(Serialize)]
+pub struct BookmarkView {
+    pub uuid: Uuid,
+    pub mushaf_uuid: Uuid,
+    pub ayah_uuid: Uuid,
+    pub key: VerseKey,
+    pub note: Option<String>,
+    pub tags: Vec<String>,
+    pub colour: Option<String>,
+    pub deleted: bool,
+    pub updated_at: NaiveDateTime,
+}
+
+/// The response type for GET /profile/bookmark
+#[derive(Serialize)]
+pub struct BookmarkList {
+    /// Use as the `since` of the next sync
+    pub cursor: i64,
+    pub bookmarks: Vec<BookmarkView>,
+}
+
+/// The request body for POST /profile/position
+#[derive(Deserialize)]
+pub struct SimpleReadingPosition {
+    pub ayah_uuid: Uuid,
+
+    /// When the ayah was read on the device, now by default
+    pub updated_at: Option<NaiveDateTime>,
+}
+
+/// The last read ayah of a mushaf
+#[derive(Serialize)]
+pub struct ReadingPositionView {
+    pub mushaf_uuid: Uuid,
+    pub ayah_uuid: Uuid,
+    pub key: VerseKey,
+    pub updated_at: NaiveDateTime,
+}
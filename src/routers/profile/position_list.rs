@@ -0,0 +1,59 @@
+use diesel::prelude::*;
+
+use crate::error::RouterError;
+use crate::verse_key::VerseKey;
+use crate::DbPool;
+use actix_web::web;
+use chrono::NaiveDateTime;
+use uuid::Uuid;
+
+use super::ReadingPositionView;
+
+/// Returns the last read ayah of every mushaf
+pub async fn position_list(
+    account: web::ReqData<u32>,
+    pool: web::Data<DbPool>,
+) -> Result<web::Json<Vec<ReadingPositionView>>, RouterError> {
+    use crate::schema::app_reading_positions::dsl::{
+        account_id, app_reading_positions, updated_at,
+    };
+    use crate::schema::quran_ayahs::dsl::{ayah_number, quran_ayahs, uuid as ayah_uuid};
+    use crate::schema::quran_mushafs::dsl::{quran_mushafs, uuid as mushaf_uuid};
+    use crate::schema::quran_surahs::dsl::{number as surah_number, quran_surahs};
+
+    let account = account.into_inner() as i32;
+
+    web::block(move || {
+        let mut conn = pool.get().unwrap();
+
+        let positions: Vec<(Uuid, Uuid, i32, i32, NaiveDateTime)> = app_reading_positions
+            .inner_join(quran_mushafs)
+            .inner_join(quran_ayahs.inner_join(quran_surahs))
+            .filter(account_id.eq(account))
+            .order(updated_at.desc())
+            .select((
+                mushaf_uuid,
+                ayah_uuid,
+                surah_number,
+                ayah_number,
+                updated_at,
+            ))
+            .get_results(&mut conn)?;
+
+        Ok(web::Json(
+            positions
+                .into_iter()
+                .map(
+                    |(m_uuid, a_uuid, surah, ayah, read_at)| ReadingPositionView {
+                        mushaf_uuid: m_uuid,
+                        ayah_uuid: a_uuid,
+                        key: VerseKey { surah, ayah },
+                        updated_at: read_at,
+                    },
+                )
+                .collect(),
+        ))
+    })
+    .await
+    .unwrap()
+}
@@ -0,0 +1,76 @@
+use chrono::Utc;
+use diesel::prelude::*;
+use diesel::upsert::excluded;
+
+use crate::error::RouterError;
+use crate::models::NewReadingPosition;
+use crate::DbPool;
+use actix_web::web;
+
+use super::SimpleReadingPosition;
+
+/// Saves the last read ayah of the mushaf
+///
+/// Positions older than the saved one are ignored
+pub async fn position_modify(
+    account: web::ReqData<u32>,
+    pool: web::Data<DbPool>,
+    new_position: web::Json<SimpleReadingPosition>,
+) -> Result<&'static str, RouterError> {
+    use crate::schema::quran_ayahs::dsl::{id as ayah_id, quran_ayahs, uuid as ayah_uuid};
+    use crate::schema::quran_surahs::dsl::{mushaf_id, quran_surahs};
+
+    let account = account.into_inner() as i32;
+    let new_position = new_position.into_inner();
+
+    web::block(move || {
+        let mut conn = pool.get().unwrap();
+
+        let (ayah, mushaf): (i32, i32) = quran_ayahs
+            .inner_join(quran_surahs)
+            .filter(ayah_uuid.eq(new_position.ayah_uuid))
+            .select((ayah_id, mushaf_id))
+            .get_result(&mut conn)?;
+
+        let changed_at = new_position
+            .updated_at
+            .unwrap_or_else(|| Utc::now().naive_utc());
+
+        save_position(
+            &mut conn,
+            NewReadingPosition {
+                account_id: account,
+                mushaf_id: mushaf,
+                ayah_id: ayah,
+                updated_at: changed_at,
+            },
+        )?;
+
+        Ok("Modified")
+    })
+    .await
+    .unwrap()
+}
+
+/// Inserts or updates the position of the account, the saved position is kept if it is newer
+fn save_position(conn: &mut PgConnection, position: NewReadingPosition) -> QueryResult<usize> {
+    // The upsert is filtered with FilterDsl, QueryDsl doesn't cover it
+    use diesel::query_dsl::methods::FilterDsl;
+
+    use crate::schema::app_reading_positions::dsl::{
+        account_id, app_reading_positions, ayah_id, mushaf_id, updated_at,
+    };
+
+    let upsert = position
+        .insert_into(app_reading_positions)
+        .on_conflict((account_id, mushaf_id))
+        .do_update()
+        .set((
+            ayah_id.eq(excluded(ayah_id)),
+            updated_at.eq(excluded(updated_at)),
+        ));
+
+    upsert
+        .filter(updated_at.lt(excluded(updated_at)))
+        .execute(conn)
+}
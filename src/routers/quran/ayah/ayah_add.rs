@@ -1,19 +1,30 @@
 use std::str::FromStr;
 
+use crate::checksum::MushafReference;
 use crate::error::RouterError;
 use crate::models::{NewQuranAyah, NewQuranWord, QuranAyah};
+use crate::validate::validate;
+use crate::verse_key::VerseKey;
 use crate::DbPool;
 use actix_web::web;
 use diesel::prelude::*;
 use serde::Deserialize;
 use uuid::Uuid;
+use validator::Validate;
 
-use super::Sajdah;
+use super::{shift_ayah_numbers, validate_sajdah, Sajdah};
 
-#[derive(Deserialize)]
+#[derive(Deserialize, Validate)]
 pub struct AyahWithText {
     pub surah_uuid: String,
-    pub sajdah: Option<Sajdah>,
+
+    /// Position of the new ayah in the surah,
+    /// the ayah will be appended to the surah if not provided
+    pub ayah_number: Option<i32>,
+
+    #[validate(custom = "validate_sajdah")]
+    pub sajdah: Option<String>,
+
     pub text: String,
     pub is_bismillah: bool,
     pub bismillah_text: Option<String>,
@@ -27,60 +38,98 @@ pub async fn ayah_add(
 ) -> Result<&'static str, RouterError> {
     use crate::schema::app_users::dsl::{account_id as user_acc_id, app_users, id as user_id};
     use crate::schema::quran_ayahs::dsl::quran_ayahs;
-    use crate::schema::quran_surahs::dsl::{id as surah_id, quran_surahs, uuid as surah_uuid};
+    use crate::schema::quran_surahs::dsl::{
+        id as surah_id, mushaf_id, number as surah_number, quran_surahs, uuid as surah_uuid,
+    };
     use crate::schema::quran_words::dsl::quran_words;
 
     let new_ayah = new_ayah.into_inner();
     let user_account_id = data.into_inner();
 
+    validate(&new_ayah)?;
+
     web::block(move || {
         let mut conn = pool.get().unwrap();
 
-        // Creator user_id
-        let user: i32 = app_users
-            .filter(user_acc_id.eq(user_account_id as i32))
-            .select(user_id)
-            .get_result(&mut conn)?;
-
-        // Get the target surah by surah-uuid
-        let target_surah: i32 = quran_surahs
-            .filter(surah_uuid.eq(Uuid::from_str(&new_ayah.surah_uuid)?))
-            .select(surah_id)
-            .get_result(&mut conn)?;
-
-        // Calculate amount of ayahs in surah
-        let latest_ayah_number: i64 = quran_ayahs
-            .inner_join(quran_surahs)
-            .filter(surah_id.eq(target_surah))
-            .count()
-            .get_result(&mut conn)?;
-
-        // Insert new ayah
-        let ayah: QuranAyah = NewQuranAyah {
-            surah_id: target_surah,
-            sajdah: new_ayah.sajdah.map(|sajdah| sajdah.to_string()),
-            ayah_number: (latest_ayah_number + 1) as i32,
-            creator_user_id: user,
-            is_bismillah: new_ayah.is_bismillah,
-            bismillah_text: new_ayah.bismillah_text,
-        }
-        .insert_into(quran_ayahs)
-        .get_result(&mut conn)?;
-
-        // Split the ayah text by space and insert them as quran_word
-        let words: Vec<NewQuranWord> = new_ayah
-            .text
-            .split(' ')
-            .map(|w| NewQuranWord {
+        conn.transaction(|conn| {
+            // Creator user_id
+            let user: i32 = app_users
+                .filter(user_acc_id.eq(user_account_id as i32))
+                .select(user_id)
+                .get_result(conn)?;
+
+            // Get the target surah by surah-uuid
+            let (target_surah, target_surah_number, target_mushaf): (i32, i32, i32) = quran_surahs
+                .filter(surah_uuid.eq(Uuid::from_str(&new_ayah.surah_uuid)?))
+                .select((surah_id, surah_number, mushaf_id))
+                .get_result(conn)?;
+
+            // Calculate amount of ayahs in surah
+            let ayahs_count: i64 = quran_ayahs
+                .inner_join(quran_surahs)
+                .filter(surah_id.eq(target_surah))
+                .count()
+                .get_result(conn)?;
+
+            let last_position = (ayahs_count + 1) as i32;
+
+            let position = match new_ayah.ayah_number {
+                Some(number) if number < 1 || number > last_position => {
+                    return Err(RouterError::from_predefined("AYAH_NUMBER_NOT_VALID"));
+                }
+
+                // Make room for the new ayah
+                Some(number) => {
+                    shift_ayah_numbers(conn, target_surah, number..=last_position, 1)?;
+
+                    number
+                }
+
+                None => last_position,
+            };
+
+            // Insert new ayah
+            let ayah: QuranAyah = NewQuranAyah {
+                surah_id: target_surah,
+                sajdah: Sajdah::from_option_string(new_ayah.sajdah)
+                    .map(|sajdah| sajdah.to_string()),
+                ayah_number: position,
                 creator_user_id: user,
-                word: w,
-                ayah_id: ayah.id,
-            })
-            .collect();
+                is_bismillah: new_ayah.is_bismillah,
+                bismillah_text: new_ayah.bismillah_text,
+            }
+            .insert_into(quran_ayahs)
+            .get_result(conn)?;
+
+            // Split the ayah text by space and insert them as quran_word
+            let words: Vec<NewQuranWord> = new_ayah
+                .text
+                .split(' ')
+                .map(|w| NewQuranWord {
+                    creator_user_id: user,
+                    word: w,
+                    ayah_id: ayah.id,
+                })
+                .collect();
+
+            words.insert_into(quran_words).execute(conn)?;
 
-        words.insert_into(quran_words).execute(&mut conn)?;
+            // The added text is the reference of the ayah
+            MushafReference::new(target_mushaf, user).insert_ayah(
+                conn,
+                VerseKey {
+                    surah: target_surah_number,
+                    ayah: position,
+                },
+                &new_ayah
+                    .text
+                    .split(' ')
+                    .map(str::to_string)
+                    .collect::<Vec<_>>(),
+            )?;
 
-        Ok("Added")
+            Ok("Added")
+        })
     })
     .await
     .unwrap()
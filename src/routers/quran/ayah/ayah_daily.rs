@@ -0,0 +1,38 @@
+use crate::error::RouterError;
+use crate::DbPool;
+use actix_web::{http::header, web, Responder};
+
+use super::{choose_ayah, ChosenAyah, DailyAyahQuery};
+
+/// Returns the ayah of the day
+///
+/// Every client gets the same ayah for the same date
+pub async fn ayah_daily(
+    web::Query(query): web::Query<DailyAyahQuery>,
+    pool: web::Data<DbPool>,
+) -> Result<impl Responder, RouterError> {
+    let date = query
+        .date
+        .unwrap_or_else(|| chrono::Utc::now().date_naive());
+
+    let ayah = web::block(move || {
+        let mut conn = pool.get().unwrap();
+
+        let ayah = choose_ayah(
+            &mut conn,
+            query.mushaf.as_deref(),
+            query.translation,
+            &format!("daily:{date}"),
+            query.weighted,
+        )?;
+
+        Ok::<ChosenAyah, RouterError>(ayah)
+    })
+    .await
+    .unwrap()?;
+
+    // Not made only of the content, so it can't be tagged with the content version
+    Ok(web::Json(ayah)
+        .customize()
+        .insert_header((header::CACHE_CONTROL, "no-store")))
+}
@@ -1,24 +1,65 @@
+use crate::checksum::MushafReference;
 use crate::error::RouterError;
+use crate::models::QuranAyah;
+use crate::verse_key::VerseKey;
 use crate::DbPool;
 use ::uuid::Uuid;
 use actix_web::web;
 use diesel::prelude::*;
 
+use super::shift_ayah_numbers;
+
 /// Delete's a single ayah
+///
+/// The ayahs after the deleted one will be renumbered
 pub async fn ayah_delete(
     path: web::Path<Uuid>,
     pool: web::Data<DbPool>,
+    data: web::ReqData<u32>,
 ) -> Result<&'static str, RouterError> {
+    use crate::schema::app_users::dsl::{account_id as user_acc_id, app_users, id as user_id};
     use crate::schema::quran_ayahs::dsl::{quran_ayahs, uuid as ayah_uuid};
+    use crate::schema::quran_surahs::dsl::{
+        id as surah_id, mushaf_id, number as surah_number, quran_surahs,
+    };
 
     let target_ayah_uuid = path.into_inner();
+    let user_account_id = data.into_inner();
 
     web::block(move || {
         let mut conn = pool.get().unwrap();
 
-        diesel::delete(quran_ayahs.filter(ayah_uuid.eq(target_ayah_uuid))).execute(&mut conn)?;
+        conn.transaction(|conn| {
+            let user: i32 = app_users
+                .filter(user_acc_id.eq(user_account_id as i32))
+                .select(user_id)
+                .get_result(conn)?;
+
+            let ayah: QuranAyah = quran_ayahs
+                .filter(ayah_uuid.eq(target_ayah_uuid))
+                .get_result(conn)?;
+
+            diesel::delete(&ayah).execute(conn)?;
+
+            // Close the gap
+            shift_ayah_numbers(conn, ayah.surah_id, ayah.ayah_number + 1..=i32::MAX, -1)?;
+
+            let (surah, mushaf): (i32, i32) = quran_surahs
+                .filter(surah_id.eq(ayah.surah_id))
+                .select((surah_number, mushaf_id))
+                .get_result(conn)?;
+
+            // Only the deleted ayah leaves the reference
+            MushafReference::new(mushaf, user).delete_ayah(
+                conn,
+                VerseKey {
+                    surah,
+                    ayah: ayah.ayah_number,
+                },
+            )?;
 
-        Ok("Deleted")
+            Ok("Deleted")
+        })
     })
     .await
     .unwrap()
@@ -1,53 +1,75 @@
 use crate::error::RouterError;
+use crate::models::QuranAyah;
+use crate::validate::validate;
 use crate::{AyahBismillah, DbPool};
 use actix_web::web;
 use diesel::prelude::*;
 use uuid::Uuid;
 
-use super::SimpleAyah;
+use super::{move_ayah, Sajdah, SimpleAyah};
 
 /// Update's single ayah
+///
+/// Changing the ayah_number will move the ayah
+/// and renumber the other ayahs of the surah
 pub async fn ayah_edit(
     path: web::Path<Uuid>,
     new_ayah: web::Json<SimpleAyah>,
     pool: web::Data<DbPool>,
+    data: web::ReqData<u32>,
 ) -> Result<&'static str, RouterError> {
+    use crate::schema::app_users::dsl::{account_id as user_acc_id, app_users, id as user_id};
     use crate::schema::quran_ayahs::dsl::{
-        ayah_number, bismillah_text, is_bismillah, quran_ayahs, sajdah as ayah_sajdah,
-        uuid as ayah_uuid,
+        bismillah_text, is_bismillah, quran_ayahs, sajdah as ayah_sajdah, uuid as ayah_uuid,
     };
 
     let new_ayah = new_ayah.into_inner();
     let target_ayah_uuid = path.into_inner();
+    let user_account_id = data.into_inner();
+
+    validate(&new_ayah)?;
 
     web::block(move || {
         let mut conn = pool.get().unwrap();
 
-        let new_sajdah = new_ayah.sajdah.map(|sajdah| sajdah.to_string());
-
-        diesel::update(quran_ayahs.filter(ayah_uuid.eq(target_ayah_uuid)))
-            .set((
-                ayah_number.eq(new_ayah.ayah_number),
-                ayah_sajdah.eq(new_sajdah),
-                is_bismillah.eq(new_ayah
-                    .bismillah
-                    .clone()
-                    .unwrap_or(AyahBismillah {
-                        is_ayah: false,
-                        text: None,
-                    })
-                    .is_ayah),
-                bismillah_text.eq(new_ayah
-                    .bismillah
-                    .unwrap_or(AyahBismillah {
-                        is_ayah: false,
-                        text: None,
-                    })
-                    .text),
-            ))
-            .execute(&mut conn)?;
-
-        Ok("Edited")
+        let new_sajdah =
+            Sajdah::from_option_string(new_ayah.sajdah).map(|sajdah| sajdah.to_string());
+
+        conn.transaction(|conn| {
+            let user: i32 = app_users
+                .filter(user_acc_id.eq(user_account_id as i32))
+                .select(user_id)
+                .get_result(conn)?;
+
+            let ayah: QuranAyah = quran_ayahs
+                .filter(ayah_uuid.eq(target_ayah_uuid))
+                .get_result(conn)?;
+
+            move_ayah(conn, &ayah, new_ayah.ayah_number, user)?;
+
+            diesel::update(&ayah)
+                .set((
+                    ayah_sajdah.eq(new_sajdah),
+                    is_bismillah.eq(new_ayah
+                        .bismillah
+                        .clone()
+                        .unwrap_or(AyahBismillah {
+                            is_ayah: false,
+                            text: None,
+                        })
+                        .is_ayah),
+                    bismillah_text.eq(new_ayah
+                        .bismillah
+                        .unwrap_or(AyahBismillah {
+                            is_ayah: false,
+                            text: None,
+                        })
+                        .text),
+                ))
+                .execute(conn)?;
+
+            Ok("Edited")
+        })
     })
     .await
     .unwrap()
@@ -2,27 +2,41 @@ use std::collections::HashMap;
 
 use crate::error::{RouterError, RouterErrorDetailBuilder};
 use crate::filter::Filter;
+use crate::http_cache::ContentVersion;
 use crate::models::{QuranAyah, QuranAyahBreaker, QuranWord, QuranWordBreaker};
+use crate::ndjson::{accepts_ndjson, stream_ndjson, StreamLimit};
 use crate::routers::multip;
-use crate::routers::quran::word::WordBreaker;
+use crate::routers::quran::word::{apply_script, WordBreaker};
+use crate::view_cache::ViewCache;
 use crate::{
     routers::quran::surah::{AyahTy, AyahWord, Format, SimpleAyah},
     DbPool,
 };
 use crate::{AyahBismillah, Breaker};
-use actix_web::{web, HttpRequest};
+use actix_web::{web, HttpRequest, HttpResponse};
+use diesel::pg::PgRowByRowLoadingMode;
 use diesel::prelude::*;
 
 use super::AyahListQuery;
 
+/// Breakers of the ayahs, and of the words if the format is word
+struct ListBreakers {
+    ayahs: HashMap<i32, Vec<Breaker>>,
+    words: Option<HashMap<i32, Vec<WordBreaker>>>,
+}
+
 /// Returns the list of ayahs
+///
+/// Ayahs are streamed one per line if the client accepts `application/x-ndjson`
 pub async fn ayah_list(
     pool: web::Data<DbPool>,
     web::Query(query): web::Query<AyahListQuery>,
+    view_cache: web::Data<ViewCache>,
+    stream_limit: web::Data<StreamLimit>,
+    version: Option<web::ReqData<ContentVersion>>,
     req: HttpRequest,
-) -> Result<web::Json<Vec<AyahTy>>, RouterError> {
+) -> Result<HttpResponse, RouterError> {
     use crate::schema::quran_ayahs::dsl::ayah_number;
-    use crate::schema::quran_ayahs_breakers::dsl::quran_ayahs_breakers;
     use crate::schema::quran_mushafs::dsl::{quran_mushafs, short_name as mushaf_short_name};
     use crate::schema::quran_surahs::dsl::{number as quran_surah_number, quran_surahs};
     use crate::schema::quran_words::dsl::quran_words;
@@ -32,61 +46,35 @@ pub async fn ayah_list(
 
     let error_detail = RouterErrorDetailBuilder::from_http_request(&req).build();
 
-    web::block(move || {
-        let mut conn = pool.get().unwrap();
-
-        // [{ayah_id, name}...]
-        // Also need to count name
-        let breakers: Vec<QuranAyahBreaker> = quran_ayahs_breakers.get_results(&mut conn)?;
-        let mut breakers = breakers.into_iter();
-
-        let mut breakers_count: HashMap<String, u32> = HashMap::new();
-        let mut map = HashMap::<i32, Vec<Breaker>>::new();
+    if accepts_ndjson(&req) {
+        // Filters are checked before the response is started
+        if let Err(err) = QuranAyah::filter(Box::from(query.clone())) {
+            return Err(err.log_to_db(pool, error_detail));
+        }
 
-        while let Some(breaker) = breakers.next() {
-            breakers_count
-                .entry(breaker.name)
-                .and_modify(|v| *v += 1)
-                .or_insert(1);
+        return stream_ndjson(&stream_limit, move |writer| {
+            stream_ayahs(pool, query, writer)
+        });
+    }
 
-            let val = breakers_count
-                .clone()
-                .into_iter()
-                .map(|(k, v)| Breaker { name: k, number: v })
-                .collect::<Vec<Breaker>>();
+    let version = version.map(|version| version.into_inner());
 
-            map.entry(breaker.ayah_id).insert_entry(val);
-        }
+    let view_key = query.view_key();
+    if let Some(cached) = view_cache.cached(version.as_ref(), &view_key) {
+        return Ok(cached);
+    }
 
-        // TODO: this gets every word's breaker. (not efficient)
-        let words_breakers = if matches!(query.format, Some(Format::Word)) {
-            let breakers: Vec<QuranWordBreaker> = quran_words_breakers.get_results(&mut conn)?;
-            let mut breakers = breakers.into_iter();
-            // (i32)
-            let mut collected_breakers: HashMap<i32, Vec<WordBreaker>> = HashMap::new();
-
-            while let Some(breaker) = breakers.next() {
-                collected_breakers
-                    .entry(breaker.word_id)
-                    .and_modify(|v| {
-                        v.push(WordBreaker {
-                            name: breaker.name.clone(),
-                        })
-                    })
-                    .or_insert(vec![WordBreaker { name: breaker.name }]);
-            }
+    let final_ayahs = web::block(move || {
+        let mut conn = pool.get().unwrap();
 
-            Some(collected_breakers)
-        } else {
-            None
-        };
+        let breakers = list_breakers(&mut conn, &query.format)?;
 
         let filtered_ayahs = match QuranAyah::filter(Box::from(query.clone())) {
             Ok(filtered) => filtered,
             Err(err) => return Err(err.log_to_db(pool, error_detail)),
         };
 
-        let ayahs_words = filtered_ayahs
+        let mut ayahs_words = filtered_ayahs
             .left_outer_join(quran_surahs.left_outer_join(quran_mushafs))
             // TODO: currently we dont use quran_words_breakers join
             .inner_join(quran_words.left_join(quran_words_breakers))
@@ -95,54 +83,224 @@ pub async fn ayah_list(
             .select((QuranAyah::as_select(), QuranWord::as_select()))
             .get_results::<(QuranAyah, QuranWord)>(&mut conn)?;
 
+        if let Some(ref target_script) = query.script {
+            apply_script(
+                &mut conn,
+                target_script,
+                ayahs_words.iter_mut().map(|(_, w)| w),
+            )?;
+        }
+
         let ayahs_words = ayahs_words
             .into_iter()
-            .map(|(ayah, word)| {
-                (
-                    SimpleAyah {
-                        id: ayah.id as u32,
-                        uuid: ayah.uuid,
-                        bismillah: AyahBismillah::from_ayah_fields(
-                            ayah.is_bismillah,
-                            ayah.bismillah_text,
-                        ),
-                        breakers: map.get(&ayah.id).clone().cloned(),
-                        number: ayah.ayah_number as u32,
-                        sajdah: ayah.sajdah,
-                    },
-                    word,
-                )
-            })
+            .map(|(ayah, word)| (simple_ayah(ayah, &breakers.ayahs), word))
             .collect::<Vec<(SimpleAyah, QuranWord)>>();
 
         let ayahs_as_map = multip(ayahs_words, |a| a);
         let final_ayahs = ayahs_as_map
             .into_iter()
-            .map(|(ayah, words)| match query.format {
-                Some(Format::Text) | None => AyahTy::Text(crate::AyahWithText {
-                    ayah,
-                    text: words
-                        .into_iter()
-                        .map(|w| w.word)
-                        .collect::<Vec<String>>()
-                        .join(" "),
-                }),
-                Some(Format::Word) => AyahTy::Words(crate::AyahWithWords {
-                    ayah: ayah.clone(),
-                    words: words
-                        .into_iter()
-                        .map(|w| AyahWord {
-                            // TODO: Very expensive operation. remove .clone()
-                            breakers: words_breakers.clone().unwrap().get(&w.id).clone().cloned(),
-                            word: w.word,
-                        })
-                        .collect(),
-                }),
-            })
+            .map(|(ayah, words)| ayah_with_words(ayah, words, &query.format, &breakers))
             .collect::<Vec<AyahTy>>();
 
-        Ok(web::Json(final_ayahs))
+        Ok(final_ayahs)
     })
     .await
-    .unwrap()
+    .unwrap()?;
+
+    Ok(view_cache.store(version.as_ref(), view_key, &final_ayahs))
+}
+
+/// Streams the ayahs as they are read, the words of an ayah are in consecutive rows
+fn stream_ayahs(
+    pool: std::sync::Arc<DbPool>,
+    query: AyahListQuery,
+    writer: &crate::ndjson::NdjsonWriter,
+) -> Result<(), RouterError> {
+    use crate::schema::quran_ayahs::dsl::ayah_number;
+    use crate::schema::quran_mushafs::dsl::{quran_mushafs, short_name as mushaf_short_name};
+    use crate::schema::quran_surahs::dsl::{number as quran_surah_number, quran_surahs};
+    use crate::schema::quran_words::dsl::{id as word_id, quran_words};
+    use crate::schema::quran_words_breakers::dsl::quran_words_breakers;
+
+    let mut conn = pool.get()?;
+
+    let breakers = list_breakers(&mut conn, &query.format)?;
+
+    // The connection is busy while the rows are read, so the scripts are selected before
+    let mut renditions = match query.script {
+        Some(ref target_script) => mushaf_renditions(&mut conn, &query.mushaf, target_script)?,
+        None => HashMap::new(),
+    };
+
+    let rows = QuranAyah::filter(Box::from(query.clone()))?
+        .left_outer_join(quran_surahs.left_outer_join(quran_mushafs))
+        .inner_join(quran_words.left_join(quran_words_breakers))
+        .filter(mushaf_short_name.eq(query.mushaf.clone()))
+        .order((quran_surah_number.asc(), ayah_number.asc(), word_id.asc()))
+        .select((QuranAyah::as_select(), QuranWord::as_select()))
+        .load_iter::<(QuranAyah, QuranWord), PgRowByRowLoadingMode>(&mut conn)?;
+
+    let mut current: Option<(QuranAyah, Vec<QuranWord>)> = None;
+
+    // Sends the ayah, returns false if the client is disconnected
+    let send = |(ayah, words): (QuranAyah, Vec<QuranWord>)| -> bool {
+        let ayah = simple_ayah(ayah, &breakers.ayahs);
+
+        writer.write(&ayah_with_words(ayah, words, &query.format, &breakers))
+    };
+
+    for row in rows {
+        let (ayah, mut word) = row?;
+
+        if let Some(rendition) = renditions.remove(&word.id) {
+            word.word = rendition;
+        }
+
+        match current {
+            Some((ref last, ref mut words)) if last.id == ayah.id => words.push(word),
+
+            _ => {
+                if let Some(finished) = current.replace((ayah, vec![word])) {
+                    if !send(finished) {
+                        return Ok(());
+                    }
+                }
+            }
+        }
+    }
+
+    if let Some(last) = current {
+        send(last);
+    }
+
+    Ok(())
+}
+
+/// Returns the text of the mushaf words in the target script, by the word id
+fn mushaf_renditions(
+    conn: &mut PgConnection,
+    mushaf: &str,
+    target_script: &str,
+) -> QueryResult<HashMap<i32, String>> {
+    use crate::schema::quran_ayahs::dsl::quran_ayahs;
+    use crate::schema::quran_mushafs::dsl::{quran_mushafs, short_name as mushaf_short_name};
+    use crate::schema::quran_surahs::dsl::quran_surahs;
+    use crate::schema::quran_words::dsl::quran_words;
+    use crate::schema::quran_words_renditions::dsl::{
+        quran_words_renditions, script, text, word_id,
+    };
+
+    Ok(quran_words_renditions
+        .inner_join(
+            quran_words.inner_join(quran_ayahs.inner_join(quran_surahs.inner_join(quran_mushafs))),
+        )
+        .filter(mushaf_short_name.eq(mushaf))
+        .filter(script.eq(target_script))
+        .select((word_id, text))
+        .get_results::<(i32, String)>(conn)?
+        .into_iter()
+        .collect())
+}
+
+/// Returns the breakers of the list, numbered by their order
+fn list_breakers(conn: &mut PgConnection, format: &Option<Format>) -> QueryResult<ListBreakers> {
+    use crate::schema::quran_ayahs_breakers::dsl::quran_ayahs_breakers;
+    use crate::schema::quran_words_breakers::dsl::quran_words_breakers;
+
+    // [{ayah_id, name}...]
+    // Also need to count name
+    let breakers: Vec<QuranAyahBreaker> = quran_ayahs_breakers.get_results(conn)?;
+
+    let mut breakers_count: HashMap<String, u32> = HashMap::new();
+    let mut map = HashMap::<i32, Vec<Breaker>>::new();
+
+    for breaker in breakers {
+        breakers_count
+            .entry(breaker.name)
+            .and_modify(|v| *v += 1)
+            .or_insert(1);
+
+        let val = breakers_count
+            .clone()
+            .into_iter()
+            .map(|(k, v)| Breaker { name: k, number: v })
+            .collect::<Vec<Breaker>>();
+
+        map.entry(breaker.ayah_id).insert_entry(val);
+    }
+
+    // TODO: this gets every word's breaker. (not efficient)
+    let words_breakers = if matches!(format, Some(Format::Word)) {
+        let breakers: Vec<QuranWordBreaker> = quran_words_breakers.get_results(conn)?;
+        // (i32)
+        let mut collected_breakers: HashMap<i32, Vec<WordBreaker>> = HashMap::new();
+
+        for breaker in breakers {
+            collected_breakers
+                .entry(breaker.word_id)
+                .and_modify(|v| {
+                    v.push(WordBreaker {
+                        name: breaker.name.clone(),
+                    })
+                })
+                .or_insert(vec![WordBreaker { name: breaker.name }]);
+        }
+
+        Some(collected_breakers)
+    } else {
+        None
+    };
+
+    Ok(ListBreakers {
+        ayahs: map,
+        words: words_breakers,
+    })
+}
+
+/// Converts the ayah model to the response ayah, with its breakers
+fn simple_ayah(ayah: QuranAyah, breakers: &HashMap<i32, Vec<Breaker>>) -> SimpleAyah {
+    SimpleAyah {
+        id: ayah.id as u32,
+        uuid: ayah.uuid,
+        bismillah: AyahBismillah::from_ayah_fields(ayah.is_bismillah, ayah.bismillah_text),
+        breakers: breakers.get(&ayah.id).cloned(),
+        number: ayah.ayah_number as u32,
+        sajdah: ayah.sajdah,
+    }
+}
+
+/// Builds the ayah of the list with its words in the requested format
+fn ayah_with_words(
+    ayah: SimpleAyah,
+    words: Vec<QuranWord>,
+    format: &Option<Format>,
+    breakers: &ListBreakers,
+) -> AyahTy {
+    match format {
+        Some(Format::Text) | None => AyahTy::Text(crate::AyahWithText {
+            ayah,
+            text: words
+                .into_iter()
+                .map(|w| w.word)
+                .collect::<Vec<String>>()
+                .join(" "),
+            transliteration: None,
+        }),
+        Some(Format::Word) => AyahTy::Words(crate::AyahWithWords {
+            ayah,
+            words: words
+                .into_iter()
+                .map(|w| AyahWord {
+                    breakers: breakers
+                        .words
+                        .as_ref()
+                        .and_then(|words| words.get(&w.id))
+                        .cloned(),
+                    tajweed: None,
+                    transliteration: None,
+                    word: w.word,
+                })
+                .collect(),
+        }),
+    }
 }
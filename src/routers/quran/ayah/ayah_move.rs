@@ -0,0 +1,44 @@
+use crate::error::RouterError;
+use crate::models::QuranAyah;
+use crate::DbPool;
+use ::uuid::Uuid;
+use actix_web::web;
+use diesel::prelude::*;
+
+use super::{move_ayah, MoveAyah};
+
+/// Moves the ayah to another position in its surah
+pub async fn ayah_move(
+    path: web::Path<Uuid>,
+    target: web::Json<MoveAyah>,
+    pool: web::Data<DbPool>,
+    data: web::ReqData<u32>,
+) -> Result<&'static str, RouterError> {
+    use crate::schema::app_users::dsl::{account_id as user_acc_id, app_users, id as user_id};
+    use crate::schema::quran_ayahs::dsl::{quran_ayahs, uuid as ayah_uuid};
+
+    let target_ayah_uuid = path.into_inner();
+    let target = target.into_inner();
+    let user_account_id = data.into_inner();
+
+    web::block(move || {
+        let mut conn = pool.get().unwrap();
+
+        conn.transaction(|conn| {
+            let user: i32 = app_users
+                .filter(user_acc_id.eq(user_account_id as i32))
+                .select(user_id)
+                .get_result(conn)?;
+
+            let ayah: QuranAyah = quran_ayahs
+                .filter(ayah_uuid.eq(target_ayah_uuid))
+                .get_result(conn)?;
+
+            move_ayah(conn, &ayah, target.ayah_number, user)?;
+
+            Ok("Moved")
+        })
+    })
+    .await
+    .unwrap()
+}
@@ -0,0 +1,37 @@
+use crate::error::RouterError;
+use crate::DbPool;
+use actix_web::{http::header, web, Responder};
+use rand::distributions::{Alphanumeric, DistString};
+
+use super::{choose_ayah, ChosenAyah, RandomAyahQuery};
+
+/// Returns a random ayah, the same ayah for the same seed
+pub async fn ayah_random(
+    web::Query(query): web::Query<RandomAyahQuery>,
+    pool: web::Data<DbPool>,
+) -> Result<impl Responder, RouterError> {
+    let seed = query
+        .seed
+        .unwrap_or_else(|| Alphanumeric.sample_string(&mut rand::thread_rng(), 16));
+
+    let ayah = web::block(move || {
+        let mut conn = pool.get().unwrap();
+
+        let ayah = choose_ayah(
+            &mut conn,
+            query.mushaf.as_deref(),
+            query.translation,
+            &seed,
+            query.weighted,
+        )?;
+
+        Ok::<ChosenAyah, RouterError>(ayah)
+    })
+    .await
+    .unwrap()?;
+
+    // Not made only of the content, so it can't be tagged with the content version
+    Ok(web::Json(ayah)
+        .customize()
+        .insert_header((header::CACHE_CONTROL, "no-store")))
+}
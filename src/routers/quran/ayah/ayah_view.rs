@@ -1,7 +1,9 @@
 use super::{AyahWithContentSurah, SimpleWord};
 use crate::error::RouterError;
 use crate::models::{QuranAyah, QuranMushaf, QuranSurah, QuranWord};
-use crate::{routers::quran::surah::SurahName, AyahWithContent, DbPool, Sajdah, SingleSurahMushaf};
+use crate::routers::quran::word::{apply_script, words_transliteration};
+use crate::routers::quran::surah::{fallback_languages, surah_names, NameLanguages};
+use crate::{AyahWithContent, DbPool, Sajdah, SingleSurahMushaf};
 use ::uuid::Uuid;
 use actix_web::web;
 use diesel::prelude::*;
@@ -9,7 +11,14 @@ use serde::Deserialize;
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct GetAyahQuery {
+    #[serde(alias = "lang")]
     lang_code: Option<String>,
+    fallback: Option<String>,
+    script: Option<String>,
+
+    /// Return the latin transliteration of the ayah
+    #[serde(default)]
+    transliteration: bool,
 }
 
 /// Return's a single ayah
@@ -18,10 +27,6 @@ pub async fn ayah_view(
     web::Query(query): web::Query<GetAyahQuery>,
     pool: web::Data<DbPool>,
 ) -> Result<web::Json<AyahWithContent>, RouterError> {
-    use crate::schema::app_phrase_translations::dsl::{
-        app_phrase_translations, language as p_t_lang, text as p_t_text,
-    };
-    use crate::schema::app_phrases::dsl::{app_phrases, phrase as p_phrase};
     use crate::schema::quran_ayahs::dsl::{quran_ayahs, uuid as ayah_uuid};
     use crate::schema::quran_mushafs::dsl::{id as mushaf_id, quran_mushafs};
     use crate::schema::quran_surahs::dsl::{id as surah_id, quran_surahs};
@@ -47,29 +52,35 @@ pub async fn ayah_view(
             .filter(mushaf_id.eq(surah.id))
             .get_result::<QuranMushaf>(&mut conn)?;
 
-        let translation = if let Some(ref phrase) = surah.name_translation_phrase {
-            let mut p = app_phrases.left_join(app_phrase_translations).into_boxed();
+        let names = surah_names(
+            &mut conn,
+            std::slice::from_ref(&surah),
+            &NameLanguages::from_query(query.lang_code.as_deref()),
+            &fallback_languages(query.fallback.as_deref()),
+        )?
+        .remove(&surah.id)
+        .unwrap_or_default();
 
-            if let Some(ref l) = query.lang_code {
-                p = p.filter(p_t_lang.eq(l));
-            } else {
-                p = p.filter(p_t_lang.eq("en"));
-            }
+        let mut words: Vec<QuranWord> = quran_words
+            .filter(ayah_id.eq(quran_ayah.id))
+            .order(word_id.asc())
+            .get_results(&mut conn)?;
 
-            p.filter(p_phrase.eq(phrase))
-                .select(p_t_text.nullable())
-                .get_result(&mut conn)?
+        // Transliterated from the mushaf text, before applying the script
+        let mut transliterations = if query.transliteration {
+            Some(words_transliteration(&mut conn, [&words])?)
         } else {
             None
         };
-        let words: Vec<QuranWord> = quran_words
-            .filter(ayah_id.eq(quran_ayah.id))
-            .order(word_id.asc())
-            .get_results(&mut conn)?;
+
+        if let Some(ref target_script) = query.script {
+            apply_script(&mut conn, target_script, words.iter_mut())?;
+        }
 
         let words_simple: Vec<SimpleWord> = words
             .into_iter()
             .map(|word| SimpleWord {
+                transliteration: transliterations.as_mut().and_then(|t| t.remove(&word.id)),
                 word: word.word,
                 uuid: word.uuid,
             })
@@ -82,22 +93,25 @@ pub async fn ayah_view(
             .collect::<Vec<String>>()
             .join(" ");
 
+        let transliteration = query.transliteration.then(|| {
+            words_simple
+                .iter()
+                .filter_map(|word| word.transliteration.clone())
+                .collect::<Vec<String>>()
+                .join(" ")
+        });
+
         Ok(web::Json(AyahWithContent {
             surah: AyahWithContentSurah {
                 uuid: surah.uuid,
-                names: vec![SurahName {
-                    arabic: surah.name,
-                    translation,
-                    translation_phrase: surah.name_translation_phrase,
-                    pronunciation: surah.name_pronunciation,
-                    transliteration: surah.name_transliteration,
-                }],
+                names,
             },
             mushaf: SingleSurahMushaf::from(mushaf),
             sajdah: Sajdah::from_option_string(quran_ayah.sajdah),
             ayah_number: quran_ayah.ayah_number,
             words: words_simple,
             text,
+            transliteration,
         }))
     })
     .await
@@ -1,20 +1,35 @@
 pub mod ayah_add;
+pub mod ayah_daily;
 pub mod ayah_delete;
 pub mod ayah_edit;
 pub mod ayah_list;
+pub mod ayah_move;
+pub mod ayah_random;
 pub mod ayah_view;
 
+use std::cmp::Ordering;
+use std::collections::HashMap;
 use std::fmt::Display;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
 
+use diesel::prelude::*;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use uuid::Uuid;
+use validator::{Validate, ValidationError};
 
 use crate::{
+    checksum::MushafReference,
+    error::RouterError,
     filter::{Filters, Order},
+    models::QuranAyah,
+    verse_key::VerseKey,
+    view_cache::ViewKey,
     AyahBismillah, Format, SingleSurahMushaf, SurahName,
 };
 
-#[derive(Deserialize, Serialize)]
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum Sajdah {
     Mostahab,
@@ -30,23 +45,39 @@ impl Display for Sajdah {
     }
 }
 
-impl Sajdah {
-    pub fn from_option_string(value: Option<String>) -> Option<Self> {
-        let value = value?;
+impl FromStr for Sajdah {
+    type Err = ();
 
-        match value.as_str() {
-            "vajib" => Some(Self::Vajib),
-            "mostahab" => Some(Self::Mostahab),
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "vajib" => Ok(Self::Vajib),
+            "mostahab" => Ok(Self::Mostahab),
 
-            _ => None,
+            _ => Err(()),
         }
     }
 }
 
+impl Sajdah {
+    pub fn from_option_string(value: Option<String>) -> Option<Self> {
+        value?.parse().ok()
+    }
+}
+
+pub fn validate_sajdah(sajdah: &str) -> Result<(), ValidationError> {
+    match Sajdah::from_str(sajdah) {
+        Ok(_) => Ok(()),
+        Err(()) => Err(ValidationError::new("Sajdah must be vajib or mostahab.")),
+    }
+}
+
 #[derive(Clone, Serialize, Deserialize)]
 pub struct SimpleWord {
     uuid: Uuid,
     word: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    transliteration: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -62,20 +93,35 @@ pub struct AyahWithContent {
     ayah_number: i32,
     sajdah: Option<Sajdah>,
     text: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    transliteration: Option<String>,
+
     words: Vec<SimpleWord>,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Validate)]
 pub struct SimpleAyah {
     pub ayah_number: i32,
-    pub sajdah: Option<Sajdah>,
+
+    #[validate(custom = "validate_sajdah")]
+    pub sajdah: Option<String>,
+
     pub bismillah: Option<AyahBismillah>,
 }
 
+/// The request body for /ayah/move/{uuid}
+#[derive(Deserialize)]
+pub struct MoveAyah {
+    /// The new position of the ayah in its surah
+    pub ayah_number: i32,
+}
+
 #[derive(Deserialize, Clone)]
 pub struct AyahListQuery {
     mushaf: String,
     format: Option<Format>,
+    script: Option<String>,
 
     sort: Option<String>,
     order: Option<Order>,
@@ -84,6 +130,20 @@ pub struct AyahListQuery {
     to: Option<u64>,
 }
 
+impl AyahListQuery {
+    /// Key of the cached list of the ayahs
+    pub fn view_key(&self) -> ViewKey {
+        ViewKey::new("ayah_list")
+            .mushaf(&self.mushaf)
+            .format(&self.format)
+            .option("script", &self.script)
+            .option("sort", &self.sort)
+            .option("order", &self.order)
+            .option("from", &self.from)
+            .option("to", &self.to)
+    }
+}
+
 impl Filters for AyahListQuery {
     fn sort(&self) -> Option<String> {
         self.sort.clone()
@@ -101,3 +161,334 @@ impl Filters for AyahListQuery {
         self.to
     }
 }
+
+/// The query for /ayah/random
+#[derive(Deserialize)]
+pub struct RandomAyahQuery {
+    /// Mushaf short name, not required if the translation is set
+    pub mushaf: Option<String>,
+
+    /// The same seed always returns the same ayah,
+    /// a random ayah is returned if not set
+    pub seed: Option<String>,
+
+    pub translation: Option<Uuid>,
+
+    /// Choose the longer ayahs more often
+    #[serde(default)]
+    pub weighted: bool,
+}
+
+/// The query for /ayah/daily
+#[derive(Deserialize)]
+pub struct DailyAyahQuery {
+    /// Mushaf short name, not required if the translation is set
+    pub mushaf: Option<String>,
+
+    /// Today (UTC) by default
+    pub date: Option<chrono::NaiveDate>,
+
+    pub translation: Option<Uuid>,
+
+    /// Choose the longer ayahs more often
+    #[serde(default)]
+    pub weighted: bool,
+}
+
+/// The response type for /ayah/random and /ayah/daily
+#[derive(Serialize)]
+pub struct ChosenAyah {
+    pub uuid: Uuid,
+    pub key: VerseKey,
+    pub surah_uuid: Uuid,
+    pub sajdah: Option<Sajdah>,
+    pub text: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub translation: Option<String>,
+}
+
+/// Maps the seed to a number, the same on every platform and release
+fn seed_number(seed: &str) -> u64 {
+    let hash = Sha256::digest(seed.as_bytes());
+
+    u64::from_be_bytes(hash[..8].try_into().unwrap())
+}
+
+/// Picks an index by the weights, indexes with the zero weight are never chosen
+fn pick_weighted(number: u64, weights: &[u64]) -> Option<usize> {
+    let total: u64 = weights.iter().sum();
+
+    if total == 0 {
+        return None;
+    }
+
+    let mut target = number % total;
+
+    for (index, weight) in weights.iter().enumerate() {
+        if target < *weight {
+            return Some(index);
+        }
+
+        target -= weight;
+    }
+
+    None
+}
+
+/// Chooses an ayah of the mushaf (or the mushaf of the translation) by the seed
+///
+/// Ayahs are chosen uniformly, or by their number of words if `weighted`
+pub fn choose_ayah(
+    conn: &mut PgConnection,
+    mushaf: Option<&str>,
+    translation: Option<Uuid>,
+    seed: &str,
+    weighted: bool,
+) -> Result<ChosenAyah, RouterError> {
+    use crate::schema::quran_ayahs::dsl::{ayah_number, id as ayah_id, quran_ayahs};
+    use crate::schema::quran_mushafs::dsl::{id as mushaf_id, quran_mushafs, short_name};
+    use crate::schema::quran_surahs::dsl::{
+        id as surah_id, mushaf_id as surah_mushaf_id, number as surah_number, quran_surahs,
+        uuid as surah_uuid,
+    };
+    use crate::schema::quran_translations::dsl::{
+        id as translation_id, mushaf_id as translation_mushaf_id, quran_translations,
+        uuid as translation_uuid,
+    };
+    use crate::schema::quran_translations_ayahs::dsl::{
+        ayah_id as text_ayah_id, quran_translations_ayahs, text as translation_text,
+        translation_id as text_translation_id,
+    };
+    use crate::schema::quran_words::dsl::{
+        ayah_id as word_ayah_id, id as word_id, quran_words, word,
+    };
+
+    let (target_mushaf, target_translation): (i32, Option<i32>) = match (translation, mushaf) {
+        (Some(uuid), _) => {
+            let (t_id, m_id): (i32, i32) = quran_translations
+                .filter(translation_uuid.eq(uuid))
+                .select((translation_id, translation_mushaf_id))
+                .get_result(conn)?;
+
+            (m_id, Some(t_id))
+        }
+
+        (None, Some(name)) => (
+            quran_mushafs
+                .filter(short_name.eq(name))
+                .select(mushaf_id)
+                .get_result(conn)?,
+            None,
+        ),
+
+        (None, None) => return Err(RouterError::from_predefined("AYAH_MUSHAF_NOT_DEFINED")),
+    };
+
+    // Ordered by the verse key so the choice doesn't depend on the ids
+    let ayahs: Vec<i32> = quran_ayahs
+        .inner_join(quran_surahs)
+        .filter(surah_mushaf_id.eq(target_mushaf))
+        .order((surah_number.asc(), ayah_number.asc()))
+        .select(ayah_id)
+        .get_results(conn)?;
+
+    let weights: Vec<u64> = if weighted {
+        let counts: HashMap<i32, i64> = quran_words
+            .inner_join(quran_ayahs.inner_join(quran_surahs))
+            .filter(surah_mushaf_id.eq(target_mushaf))
+            .group_by(word_ayah_id)
+            .select((word_ayah_id, diesel::dsl::count(word_id)))
+            .get_results::<(i32, i64)>(conn)?
+            .into_iter()
+            .collect();
+
+        ayahs
+            .iter()
+            .map(|id| counts.get(id).copied().unwrap_or(0) as u64)
+            .collect()
+    } else {
+        vec![1; ayahs.len()]
+    };
+
+    let Some(index) = pick_weighted(seed_number(seed), &weights) else {
+        return Err(RouterError::from_predefined("NOT_FOUND"));
+    };
+
+    let ayah: QuranAyah = quran_ayahs
+        .filter(ayah_id.eq(ayahs[index]))
+        .get_result(conn)?;
+
+    let (surah, s_uuid): (i32, Uuid) = quran_surahs
+        .filter(surah_id.eq(ayah.surah_id))
+        .select((surah_number, surah_uuid))
+        .get_result(conn)?;
+
+    let words: Vec<String> = quran_words
+        .filter(word_ayah_id.eq(ayah.id))
+        .order(word_id.asc())
+        .select(word)
+        .get_results(conn)?;
+
+    let translation = match target_translation {
+        Some(t_id) => quran_translations_ayahs
+            .filter(text_translation_id.eq(t_id))
+            .filter(text_ayah_id.eq(ayah.id))
+            .select(translation_text)
+            .get_result(conn)
+            .optional()?,
+
+        None => None,
+    };
+
+    Ok(ChosenAyah {
+        uuid: ayah.uuid,
+        key: VerseKey {
+            surah,
+            ayah: ayah.ayah_number,
+        },
+        surah_uuid: s_uuid,
+        sajdah: Sajdah::from_option_string(ayah.sajdah),
+        text: words.join(" "),
+        translation,
+    })
+}
+
+/// Adds `by` to the number of every ayah of the surah
+/// that its number is in the `range`
+///
+/// This must run in a transaction, the (surah_id, ayah_number)
+/// unique constraint is deferred until the commit.
+pub fn shift_ayah_numbers(
+    conn: &mut PgConnection,
+    target_surah_id: i32,
+    range: RangeInclusive<i32>,
+    by: i32,
+) -> QueryResult<usize> {
+    use crate::schema::quran_ayahs::dsl::{ayah_number, quran_ayahs, surah_id};
+
+    diesel::update(
+        quran_ayahs
+            .filter(surah_id.eq(target_surah_id))
+            .filter(ayah_number.between(*range.start(), *range.end())),
+    )
+    .set(ayah_number.eq(ayah_number + by))
+    .execute(conn)
+}
+
+/// Moves the ayah to the `new_number` position of its surah
+/// and renumbers the ayahs between the old and new position
+pub fn move_ayah(
+    conn: &mut PgConnection,
+    ayah: &QuranAyah,
+    new_number: i32,
+    creator_user_id: i32,
+) -> Result<(), RouterError> {
+    use crate::schema::quran_ayahs::dsl::{ayah_number, quran_ayahs, surah_id};
+    use crate::schema::quran_surahs::dsl::{
+        id as s_id, mushaf_id, number as surah_number, quran_surahs,
+    };
+
+    let ayahs_count: i64 = quran_ayahs
+        .filter(surah_id.eq(ayah.surah_id))
+        .count()
+        .get_result(conn)?;
+
+    if new_number < 1 || new_number as i64 > ayahs_count {
+        return Err(RouterError::from_predefined("AYAH_NUMBER_NOT_VALID"));
+    }
+
+    let Some((range, by)) = move_shift(ayah.ayah_number, new_number) else {
+        return Ok(());
+    };
+
+    shift_ayah_numbers(conn, ayah.surah_id, range, by)?;
+
+    diesel::update(ayah)
+        .set(ayah_number.eq(new_number))
+        .execute(conn)?;
+
+    let (surah, mushaf): (i32, i32) = quran_surahs
+        .filter(s_id.eq(ayah.surah_id))
+        .select((surah_number, mushaf_id))
+        .get_result(conn)?;
+
+    // The ayah keeps its reference checksum in the new position
+    MushafReference::new(mushaf, creator_user_id).move_ayah(
+        conn,
+        VerseKey {
+            surah,
+            ayah: ayah.ayah_number,
+        },
+        new_number,
+    )?;
+
+    Ok(())
+}
+
+/// Returns the range of ayah numbers that must be shifted
+/// and the shift direction, when an ayah moves from `from` to `to`
+fn move_shift(from: i32, to: i32) -> Option<(RangeInclusive<i32>, i32)> {
+    match from.cmp(&to) {
+        // Moving down, ayahs after the old position come up
+        Ordering::Less => Some((from + 1..=to, -1)),
+
+        // Moving up, ayahs from the new position go down
+        Ordering::Greater => Some((to..=from - 1, 1)),
+
+        Ordering::Equal => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{move_shift, pick_weighted, seed_number, Sajdah, SimpleAyah};
+    use validator::Validate;
+
+    #[test]
+    fn test_move_shift_down() {
+        assert_eq!(move_shift(2, 5), Some((3..=5, -1)));
+    }
+
+    #[test]
+    fn test_move_shift_up() {
+        assert_eq!(move_shift(5, 2), Some((2..=4, 1)));
+    }
+
+    #[test]
+    fn test_move_shift_same_position() {
+        assert_eq!(move_shift(3, 3), None);
+    }
+
+    #[test]
+    fn test_sajdah_validation() {
+        let ayah = |sajdah: Option<&str>| SimpleAyah {
+            ayah_number: 1,
+            sajdah: sajdah.map(str::to_string),
+            bismillah: None,
+        };
+
+        assert!(ayah(None).validate().is_ok());
+        assert!(ayah(Some("vajib")).validate().is_ok());
+        assert!(ayah(Some("wajib")).validate().is_err());
+        assert_eq!(
+            Sajdah::from_option_string(Some("mostahab".to_string())),
+            Some(Sajdah::Mostahab)
+        );
+    }
+
+    #[test]
+    fn test_pick_weighted() {
+        assert_eq!(pick_weighted(0, &[1, 1, 1]), Some(0));
+        assert_eq!(pick_weighted(4, &[1, 1, 1]), Some(1));
+        assert_eq!(pick_weighted(3, &[2, 0, 2]), Some(2));
+        assert_eq!(pick_weighted(7, &[0, 0]), None);
+        assert_eq!(pick_weighted(7, &[]), None);
+    }
+
+    #[test]
+    fn test_seed_number_is_stable() {
+        assert_eq!(seed_number("2026-10-17"), seed_number("2026-10-17"));
+        assert_ne!(seed_number("2026-10-17"), seed_number("2026-10-18"));
+    }
+}
@@ -2,4 +2,5 @@ pub mod mushaf;
 pub mod surah;
 pub mod ayah;
 pub mod word;
+pub mod relation;
 mod test;
@@ -1,13 +1,35 @@
 pub mod mushaf_add;
+pub mod mushaf_alignment_import;
+pub mod mushaf_alignment_view;
+pub mod mushaf_bundle_export;
+pub mod mushaf_checksum_add;
+pub mod mushaf_checksum_verify;
+pub mod mushaf_compare;
 pub mod mushaf_delete;
 pub mod mushaf_edit;
+pub mod mushaf_index_view;
+pub mod mushaf_layout_import;
 pub mod mushaf_list;
+pub mod mushaf_page_view;
+pub mod mushaf_script_import;
+pub mod mushaf_statistics_view;
+pub mod mushaf_tajweed_import;
 pub mod mushaf_view;
 
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::str::FromStr;
+
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::alignment::{AlignedVerse, WordRange};
+use crate::difference::DifferenceResult;
 use crate::filter::{Filters, Order};
+use crate::routers::quran::ayah::Sajdah;
+use crate::routers::quran::word::TajweedAnnotation;
+use crate::statistics::{AyahStatistics, SurahStatistics, WordFrequency};
+use crate::verse_key::{VerseKey, WordKey};
 
 #[derive(Serialize)]
 pub struct MushafListItem {
@@ -24,6 +46,275 @@ pub struct SimpleMushaf {
     source: String,
 }
 
+/// The request body for /mushaf/script/{uuid}
+#[derive(Deserialize)]
+pub struct MushafScriptImport {
+    pub script: String,
+
+    /// surah:ayah:word -> text of the word in the script
+    pub words: BTreeMap<WordKey, String>,
+}
+
+/// A row of the mushaf alignment table
+#[derive(Deserialize)]
+pub struct AlignedAyahs {
+    pub source: VerseKey,
+    pub source_words: Option<WordRange>,
+
+    pub target: VerseKey,
+    pub target_words: Option<WordRange>,
+}
+
+/// The request body for /mushaf/alignment/{uuid}
+#[derive(Deserialize)]
+pub struct MushafAlignmentImport {
+    pub target_mushaf_uuid: Uuid,
+
+    /// Only the ayahs that are counted differently
+    pub ayahs: Vec<AlignedAyahs>,
+}
+
+/// for example /mushaf/alignment/{uuid}?target_mushaf_uuid={uuid}&key=1:7
+#[derive(Deserialize)]
+pub struct MushafAlignmentQuery {
+    pub target_mushaf_uuid: Uuid,
+    pub key: VerseKey,
+}
+
+#[derive(Serialize)]
+pub struct AlignedAyah {
+    pub uuid: Uuid,
+
+    #[serde(flatten)]
+    pub verse: AlignedVerse,
+}
+
+/// for example /mushaf/compare?a={uuid}&b={uuid}
+#[derive(Deserialize)]
+pub struct MushafCompareQuery {
+    pub a: Uuid,
+    pub b: Uuid,
+}
+
+/// A word level change of an ayah
+///
+/// positions start from 0, position of the Delete is in
+/// the mushaf a, others in the mushaf b
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum WordDifference {
+    Insert {
+        position: usize,
+        word: String,
+    },
+
+    Delete {
+        position: usize,
+        word: String,
+    },
+
+    Substitute {
+        position: usize,
+        old: String,
+        new: String,
+    },
+}
+
+impl From<DifferenceResult<(usize, String)>> for WordDifference {
+    fn from(value: DifferenceResult<(usize, String)>) -> Self {
+        match value {
+            DifferenceResult::Insert((position, word)) => Self::Insert { position, word },
+            DifferenceResult::Remove((position, word)) => Self::Delete { position, word },
+            DifferenceResult::Update((_, old), (position, new)) => {
+                Self::Substitute { position, old, new }
+            }
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct AyahComparison {
+    pub key: VerseKey,
+    pub words: Vec<WordDifference>,
+}
+
+#[derive(Serialize)]
+pub struct MushafComparison {
+    /// Ayahs of a that b doesn't have
+    pub missing_ayahs: Vec<VerseKey>,
+
+    /// Ayahs of b that a doesn't have
+    pub extra_ayahs: Vec<VerseKey>,
+
+    /// Ayahs with different words
+    pub ayahs: Vec<AyahComparison>,
+}
+
+/// An ayah of the tajweed annotation file
+///
+/// example: `{"surah": 1, "ayah": 1, "annotations": [{"rule": "ghunnah", "start": 3, "end": 5}]}`
+#[derive(Deserialize)]
+pub struct AyahTajweed {
+    pub surah: i32,
+    pub ayah: i32,
+    pub annotations: Vec<TajweedAnnotation>,
+}
+
+/// Type of a line in the mushaf page
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LineType {
+    SurahHeader,
+    Bismillah,
+    Text,
+}
+
+impl Display for LineType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::SurahHeader => write!(f, "surah_header"),
+            Self::Bismillah => write!(f, "bismillah"),
+            Self::Text => write!(f, "text"),
+        }
+    }
+}
+
+impl FromStr for LineType {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "surah_header" => Ok(Self::SurahHeader),
+            "bismillah" => Ok(Self::Bismillah),
+            "text" => Ok(Self::Text),
+
+            _ => Err(()),
+        }
+    }
+}
+
+/// A line of the mushaf layout import
+///
+/// surah is required for the surah header and bismillah lines,
+/// first_word and last_word for the text lines
+#[derive(Deserialize)]
+pub struct LayoutLine {
+    pub page: i32,
+    pub line: i32,
+
+    #[serde(rename = "type")]
+    pub line_type: LineType,
+
+    pub surah: Option<i32>,
+    pub first_word: Option<WordKey>,
+    pub last_word: Option<WordKey>,
+}
+
+#[derive(Serialize, Clone)]
+pub struct PageWord {
+    pub uuid: Uuid,
+    pub key: WordKey,
+    pub word: String,
+}
+
+#[derive(Serialize)]
+pub struct PageLine {
+    pub line: i32,
+
+    #[serde(rename = "type")]
+    pub line_type: LineType,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub surah: Option<i32>,
+
+    pub words: Vec<PageWord>,
+}
+
+/// The response type for /mushaf/{uuid}/page/{page}
+#[derive(Serialize)]
+pub struct MushafPage {
+    pub page: i32,
+    pub lines: Vec<PageLine>,
+}
+
+/// The special ayah indexes of a mushaf
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MushafIndex {
+    /// Ayahs with a sajdah
+    Sajdah,
+
+    /// First ayahs of the surahs that start with a bismillah
+    Bismillah,
+
+    /// Ayahs that are the bismillah itself
+    BismillahAyah,
+}
+
+/// An ayah of the mushaf index
+#[derive(Serialize)]
+pub struct IndexAyah {
+    pub uuid: Uuid,
+    pub key: VerseKey,
+    pub surah_uuid: Uuid,
+    pub text: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sajdah: Option<Sajdah>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bismillah: Option<String>,
+}
+
+/// The response type for /mushaf/{uuid}/index/{index}
+#[derive(Serialize)]
+pub struct MushafIndexView {
+    pub index: MushafIndex,
+    pub ayahs: Vec<IndexAyah>,
+}
+
+/// The query for /mushaf/bundle/{uuid}
+///
+/// for example ?translations={uuid},{uuid}&breakers=true
+#[derive(Deserialize)]
+pub struct MushafBundleQuery {
+    /// Comma separated uuids of the translations
+    pub translations: Option<String>,
+
+    #[serde(default)]
+    pub breakers: bool,
+
+    #[serde(default)]
+    pub phrases: bool,
+
+    /// Names of the surahs in the other languages
+    #[serde(default)]
+    pub names: bool,
+}
+
+/// The query for /mushaf/{uuid}/statistics
+#[derive(Deserialize)]
+pub struct MushafStatisticsQuery {
+    /// Length of the lists, 10 by default and 100 at most
+    pub limit: Option<usize>,
+}
+
+/// The response type for /mushaf/{uuid}/statistics
+#[derive(Serialize)]
+pub struct MushafStatisticsView {
+    pub surahs: u32,
+    pub ayahs: u32,
+    pub words: u32,
+    pub letters: u32,
+    pub unique_words: u32,
+
+    pub frequent_words: Vec<WordFrequency>,
+    pub longest_ayahs: Vec<AyahStatistics>,
+    pub shortest_ayahs: Vec<AyahStatistics>,
+    pub longest_surahs: Vec<SurahStatistics>,
+    pub shortest_surahs: Vec<SurahStatistics>,
+}
+
 #[derive(Deserialize)]
 pub struct MushafListQuery {
     sort: Option<String>,
@@ -0,0 +1,123 @@
+use crate::error::RouterError;
+use crate::models::NewQuranMushafAlignment;
+use crate::verse_key::mushaf_ayah_ids;
+use crate::DbPool;
+use actix_web::web;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use super::MushafAlignmentImport;
+
+/// Rows per insert query, postgres can't bind more than 65535 params
+const IMPORT_CHUNK_SIZE: usize = 5_000;
+
+/// Imports the alignment table of two mushafs
+///
+/// The existing alignment of these mushafs will be replaced
+pub async fn mushaf_alignment_import(
+    path: web::Path<Uuid>,
+    import: web::Json<MushafAlignmentImport>,
+    pool: web::Data<DbPool>,
+    data: web::ReqData<u32>,
+) -> Result<&'static str, RouterError> {
+    use crate::schema::app_users::dsl::{account_id as user_acc_id, app_users, id as user_id};
+    use crate::schema::quran_mushafs::dsl::{id as mushaf_id, quran_mushafs, uuid as mushaf_uuid};
+    use crate::schema::quran_mushafs_alignments::dsl::{
+        quran_mushafs_alignments, source_mushaf_id, target_mushaf_id,
+    };
+
+    let source_mushaf_uuid = path.into_inner();
+    let import = import.into_inner();
+    let data = data.into_inner();
+
+    for aligned in &import.ayahs {
+        let ranges = [aligned.source_words, aligned.target_words];
+
+        if let Some(range) = ranges.into_iter().flatten().find(|r| !r.is_valid()) {
+            return Err(RouterError::from_predefined_with_detail(
+                "WORD_RANGE_NOT_VALID",
+                &format!("{}-{}", range.from, range.to),
+            ));
+        }
+    }
+
+    web::block(move || {
+        let mut conn = pool.get().unwrap();
+
+        conn.transaction(|conn| {
+            let user: i32 = app_users
+                .filter(user_acc_id.eq(data as i32))
+                .select(user_id)
+                .get_result(conn)?;
+
+            let source_mushaf: i32 = quran_mushafs
+                .filter(mushaf_uuid.eq(source_mushaf_uuid))
+                .select(mushaf_id)
+                .get_result(conn)?;
+
+            let target_mushaf: i32 = quran_mushafs
+                .filter(mushaf_uuid.eq(import.target_mushaf_uuid))
+                .select(mushaf_id)
+                .get_result(conn)?;
+
+            let source_ayahs = mushaf_ayah_ids(conn, source_mushaf)?;
+            let target_ayahs = mushaf_ayah_ids(conn, target_mushaf)?;
+
+            let mut alignments = vec![];
+
+            for aligned in import.ayahs.iter() {
+                let Some(source_ayah) = source_ayahs.get(&aligned.source) else {
+                    return Err(RouterError::from_predefined_with_detail(
+                        "AYAH_NOT_FOUND",
+                        &aligned.source.to_string(),
+                    ));
+                };
+
+                let Some(target_ayah) = target_ayahs.get(&aligned.target) else {
+                    return Err(RouterError::from_predefined_with_detail(
+                        "AYAH_NOT_FOUND",
+                        &aligned.target.to_string(),
+                    ));
+                };
+
+                alignments.push(NewQuranMushafAlignment {
+                    creator_user_id: user,
+                    source_mushaf_id: source_mushaf,
+                    source_ayah_id: *source_ayah,
+                    source_word_from: aligned.source_words.map(|w| w.from),
+                    source_word_to: aligned.source_words.map(|w| w.to),
+                    target_mushaf_id: target_mushaf,
+                    target_ayah_id: *target_ayah,
+                    target_word_from: aligned.target_words.map(|w| w.from),
+                    target_word_to: aligned.target_words.map(|w| w.to),
+                });
+            }
+
+            // Remove the old alignment in both directions
+            diesel::delete(
+                quran_mushafs_alignments
+                    .filter(
+                        source_mushaf_id
+                            .eq(source_mushaf)
+                            .and(target_mushaf_id.eq(target_mushaf)),
+                    )
+                    .or_filter(
+                        source_mushaf_id
+                            .eq(target_mushaf)
+                            .and(target_mushaf_id.eq(source_mushaf)),
+                    ),
+            )
+            .execute(conn)?;
+
+            for chunk in alignments.chunks(IMPORT_CHUNK_SIZE) {
+                diesel::insert_into(quran_mushafs_alignments)
+                    .values(chunk)
+                    .execute(conn)?;
+            }
+
+            Ok("Imported")
+        })
+    })
+    .await
+    .unwrap()
+}
@@ -0,0 +1,88 @@
+use std::collections::HashMap;
+
+use crate::alignment::Alignment;
+use crate::error::RouterError;
+use crate::verse_key::VerseKey;
+use crate::DbPool;
+use actix_web::web;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use super::{AlignedAyah, MushafAlignmentQuery};
+
+/// Translates a verse key of the mushaf to the verse keys
+/// of the target mushaf
+pub async fn mushaf_alignment_view(
+    path: web::Path<Uuid>,
+    web::Query(query): web::Query<MushafAlignmentQuery>,
+    pool: web::Data<DbPool>,
+) -> Result<web::Json<Vec<AlignedAyah>>, RouterError> {
+    use crate::schema::quran_ayahs::dsl::{
+        ayah_number, id as ayah_id, quran_ayahs, uuid as ayah_uuid,
+    };
+    use crate::schema::quran_mushafs::dsl::{id as mushaf_id, quran_mushafs, uuid as mushaf_uuid};
+    use crate::schema::quran_surahs::dsl::{
+        mushaf_id as surah_mushaf_id, number as surah_number, quran_surahs,
+    };
+
+    let source_mushaf_uuid = path.into_inner();
+
+    web::block(move || {
+        let mut conn = pool.get().unwrap();
+
+        let source_mushaf: i32 = quran_mushafs
+            .filter(mushaf_uuid.eq(source_mushaf_uuid))
+            .select(mushaf_id)
+            .get_result(&mut conn)?;
+
+        let target_mushaf: i32 = quran_mushafs
+            .filter(mushaf_uuid.eq(query.target_mushaf_uuid))
+            .select(mushaf_id)
+            .get_result(&mut conn)?;
+
+        // The ayah must exist in the source mushaf
+        quran_ayahs
+            .inner_join(quran_surahs)
+            .filter(surah_mushaf_id.eq(source_mushaf))
+            .filter(surah_number.eq(query.key.surah))
+            .filter(ayah_number.eq(query.key.ayah))
+            .select(ayah_id)
+            .get_result::<i32>(&mut conn)?;
+
+        let alignment = Alignment::load(&mut conn, source_mushaf, target_mushaf)?;
+
+        let verses = alignment.translate(query.key);
+
+        // Ayahs of the surahs of the verses, in a single query
+        let target_ayahs: HashMap<VerseKey, Uuid> = quran_ayahs
+            .inner_join(quran_surahs)
+            .filter(surah_mushaf_id.eq(target_mushaf))
+            .filter(surah_number.eq_any(verses.iter().map(|verse| verse.key.surah)))
+            .select((surah_number, ayah_number, ayah_uuid))
+            .get_results::<(i32, i32, Uuid)>(&mut conn)?
+            .into_iter()
+            .map(|(surah, ayah, uuid)| (VerseKey { surah, ayah }, uuid))
+            .collect();
+
+        // Not aligned ayahs may not exist in the target mushaf
+        let result: Vec<AlignedAyah> = verses
+            .into_iter()
+            .filter_map(|verse| {
+                let uuid = *target_ayahs.get(&verse.key)?;
+
+                Some(AlignedAyah { uuid, verse })
+            })
+            .collect();
+
+        if result.is_empty() {
+            return Err(RouterError::from_predefined_with_detail(
+                "AYAH_NOT_FOUND",
+                &query.key.to_string(),
+            ));
+        }
+
+        Ok(web::Json(result))
+    })
+    .await
+    .unwrap()
+}
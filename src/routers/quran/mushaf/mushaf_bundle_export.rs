@@ -0,0 +1,77 @@
+use std::{env, fs};
+
+use crate::bundle::{build_bundle, bundle_translation_ids, BundleOptions};
+use crate::error::RouterError;
+use crate::DbPool;
+use actix_web::http::header::{ContentDisposition, DispositionParam, DispositionType};
+use actix_web::{web, HttpResponse};
+use diesel::prelude::*;
+use rand::distributions::{Alphanumeric, DistString};
+use uuid::Uuid;
+
+use super::MushafBundleQuery;
+
+/// Returns the offline SQLite bundle of the mushaf
+///
+/// The bundle is built on every request, so this is only for the admins
+pub async fn mushaf_bundle_export(
+    path: web::Path<Uuid>,
+    web::Query(query): web::Query<MushafBundleQuery>,
+    pool: web::Data<DbPool>,
+) -> Result<HttpResponse, RouterError> {
+    use crate::schema::quran_mushafs::dsl::{id as mushaf_id, quran_mushafs, uuid as mushaf_uuid};
+
+    let target_mushaf_uuid = path.into_inner();
+
+    let translations = query
+        .translations
+        .as_deref()
+        .unwrap_or_default()
+        .split(',')
+        .filter(|uuid| !uuid.is_empty())
+        .map(Uuid::parse_str)
+        .collect::<Result<Vec<Uuid>, _>>()?;
+
+    let bundle = web::block(move || {
+        let mut conn = pool.get().unwrap();
+
+        let mushaf: i32 = quran_mushafs
+            .filter(mushaf_uuid.eq(target_mushaf_uuid))
+            .select(mushaf_id)
+            .get_result(&mut conn)?;
+
+        let options = BundleOptions {
+            mushaf_id: mushaf,
+            translation_ids: bundle_translation_ids(&mut conn, mushaf, &translations)?,
+            breakers: query.breakers,
+            phrases: query.phrases,
+            surah_names: query.names,
+        };
+
+        let file = env::temp_dir().join(format!(
+            "nq-bundle-{}.sqlite",
+            Alphanumeric.sample_string(&mut rand::thread_rng(), 16)
+        ));
+
+        let result = build_bundle(&mut conn, &options, &file)
+            .and_then(|()| Ok(fs::read(&file)?))
+            .map_err(RouterError::from);
+
+        let _ = fs::remove_file(&file);
+
+        result
+    })
+    .await
+    .unwrap()?;
+
+    Ok(HttpResponse::Ok()
+        .content_type("application/vnd.sqlite3")
+        .insert_header(ContentDisposition {
+            disposition: DispositionType::Attachment,
+            parameters: vec![DispositionParam::Filename(format!(
+                "{}.sqlite",
+                target_mushaf_uuid
+            ))],
+        })
+        .body(bundle))
+}
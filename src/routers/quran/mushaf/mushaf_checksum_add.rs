@@ -0,0 +1,46 @@
+use crate::checksum::MushafChecksums;
+use crate::error::RouterError;
+use crate::DbPool;
+use actix_web::web;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+/// Stores the checksums of the current mushaf content as the reference
+///
+/// Adding, moving and deleting the ayahs and adding the words keep the reference,
+/// must be called after intentionally editing the words
+pub async fn mushaf_checksum_add(
+    path: web::Path<Uuid>,
+    pool: web::Data<DbPool>,
+    data: web::ReqData<u32>,
+) -> Result<web::Json<String>, RouterError> {
+    use crate::schema::app_users::dsl::{account_id as user_acc_id, app_users, id as user_id};
+    use crate::schema::quran_mushafs::dsl::{id as mushaf_id, quran_mushafs, uuid as mushaf_uuid};
+
+    let target_mushaf_uuid = path.into_inner();
+    let data = data.into_inner();
+
+    web::block(move || {
+        let mut conn = pool.get().unwrap();
+
+        conn.transaction(|conn| {
+            let user: i32 = app_users
+                .filter(user_acc_id.eq(data as i32))
+                .select(user_id)
+                .get_result(conn)?;
+
+            let mushaf: i32 = quran_mushafs
+                .filter(mushaf_uuid.eq(target_mushaf_uuid))
+                .select(mushaf_id)
+                .get_result(conn)?;
+
+            let checksums = MushafChecksums::current(conn, mushaf)?;
+
+            checksums.store(conn, mushaf, user)?;
+
+            Ok(web::Json(checksums.mushaf))
+        })
+    })
+    .await
+    .unwrap()
+}
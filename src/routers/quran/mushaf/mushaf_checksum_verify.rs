@@ -0,0 +1,36 @@
+use crate::checksum::{MushafChecksums, MushafVerification};
+use crate::error::RouterError;
+use crate::DbPool;
+use actix_web::web;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+/// Verifies the mushaf content against the reference checksums
+/// and lists the drifted ayahs
+pub async fn mushaf_checksum_verify(
+    path: web::Path<Uuid>,
+    pool: web::Data<DbPool>,
+) -> Result<web::Json<MushafVerification>, RouterError> {
+    use crate::schema::quran_mushafs::dsl::{id as mushaf_id, quran_mushafs, uuid as mushaf_uuid};
+
+    let target_mushaf_uuid = path.into_inner();
+
+    web::block(move || {
+        let mut conn = pool.get().unwrap();
+
+        let mushaf: i32 = quran_mushafs
+            .filter(mushaf_uuid.eq(target_mushaf_uuid))
+            .select(mushaf_id)
+            .get_result(&mut conn)?;
+
+        let Some(reference) = MushafChecksums::reference(&mut conn, mushaf)? else {
+            return Err(RouterError::from_predefined("CHECKSUM_NOT_FOUND"));
+        };
+
+        let current = MushafChecksums::current(&mut conn, mushaf)?;
+
+        Ok(web::Json(reference.verify(&current)))
+    })
+    .await
+    .unwrap()
+}
@@ -0,0 +1,81 @@
+use crate::difference::{sequence_diff, Difference, DifferenceContext, DifferenceResult, GetKey};
+use crate::error::RouterError;
+use crate::verse_key::{mushaf_ayah_words, VerseKey};
+use crate::DbPool;
+use actix_web::web;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use super::{AyahComparison, MushafCompareQuery, MushafComparison, WordDifference};
+
+#[derive(Hash, PartialEq, Eq, Clone)]
+struct ComparableAyah {
+    key: VerseKey,
+    words: Vec<String>,
+}
+
+impl GetKey for ComparableAyah {
+    fn get_key(&self) -> String {
+        self.key.to_string()
+    }
+}
+
+/// Loads the words of the mushaf ayahs in order
+fn mushaf_ayahs(conn: &mut PgConnection, target_mushaf: Uuid) -> QueryResult<Vec<ComparableAyah>> {
+    use crate::schema::quran_mushafs::dsl::{id as mushaf_id, quran_mushafs, uuid as mushaf_uuid};
+
+    let target_mushaf_id: i32 = quran_mushafs
+        .filter(mushaf_uuid.eq(target_mushaf))
+        .select(mushaf_id)
+        .get_result(conn)?;
+
+    Ok(mushaf_ayah_words(conn, target_mushaf_id)?
+        .into_iter()
+        .map(|(key, words)| ComparableAyah { key, words })
+        .collect())
+}
+
+/// Compares the mushaf b to the mushaf a, ayah by ayah and word by word
+pub async fn mushaf_compare(
+    web::Query(query): web::Query<MushafCompareQuery>,
+    pool: web::Data<DbPool>,
+) -> Result<web::Json<MushafComparison>, RouterError> {
+    web::block(move || {
+        let mut conn = pool.get().unwrap();
+
+        let a = mushaf_ayahs(&mut conn, query.a)?;
+        let b = mushaf_ayahs(&mut conn, query.b)?;
+
+        let mut diff = Difference::from(DifferenceContext::new(a, b));
+
+        let mut result = MushafComparison {
+            missing_ayahs: vec![],
+            extra_ayahs: vec![],
+            ayahs: vec![],
+        };
+
+        for difference in diff.diff() {
+            match difference {
+                DifferenceResult::Remove(ayah) => result.missing_ayahs.push(ayah.key),
+
+                DifferenceResult::Insert(ayah) => result.extra_ayahs.push(ayah.key),
+
+                DifferenceResult::Update(old, new) => result.ayahs.push(AyahComparison {
+                    key: old.key,
+                    words: sequence_diff(&old.words, &new.words)
+                        .into_iter()
+                        .map(WordDifference::from)
+                        .collect(),
+                }),
+            }
+        }
+
+        result.missing_ayahs.sort();
+        result.extra_ayahs.sort();
+        result.ayahs.sort_by_key(|ayah| ayah.key);
+
+        Ok(web::Json(result))
+    })
+    .await
+    .unwrap()
+}
@@ -0,0 +1,100 @@
+use std::collections::HashMap;
+
+use crate::error::RouterError;
+use crate::routers::quran::ayah::Sajdah;
+use crate::verse_key::VerseKey;
+use crate::DbPool;
+use actix_web::web;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use super::{IndexAyah, MushafIndex, MushafIndexView};
+
+/// (ayah id, ayah uuid, surah number, ayah number, surah uuid, sajdah, bismillah text)
+type IndexRow = (i32, Uuid, i32, i32, Uuid, Option<String>, Option<String>);
+
+/// Returns the sajdah, bismillah or bismillah-ayah index of a mushaf
+pub async fn mushaf_index_view(
+    path: web::Path<(Uuid, MushafIndex)>,
+    pool: web::Data<DbPool>,
+) -> Result<web::Json<MushafIndexView>, RouterError> {
+    use crate::schema::quran_ayahs::dsl::{
+        ayah_number, bismillah_text, id as ayah_id, is_bismillah, quran_ayahs, sajdah,
+        uuid as ayah_uuid,
+    };
+    use crate::schema::quran_mushafs::dsl::{id as mushaf_id, quran_mushafs, uuid as mushaf_uuid};
+    use crate::schema::quran_surahs::dsl::{
+        mushaf_id as surah_mushaf_id, number as surah_number, quran_surahs, uuid as surah_uuid,
+    };
+    use crate::schema::quran_words::dsl::{
+        ayah_id as word_ayah_id, id as word_id, quran_words, word,
+    };
+
+    let (target_mushaf_uuid, index) = path.into_inner();
+
+    web::block(move || {
+        let mut conn = pool.get().unwrap();
+
+        let mushaf: i32 = quran_mushafs
+            .filter(mushaf_uuid.eq(target_mushaf_uuid))
+            .select(mushaf_id)
+            .get_result(&mut conn)?;
+
+        let query = quran_ayahs
+            .inner_join(quran_surahs)
+            .filter(surah_mushaf_id.eq(mushaf))
+            .order((surah_number.asc(), ayah_number.asc()))
+            .select((
+                ayah_id,
+                ayah_uuid,
+                surah_number,
+                ayah_number,
+                surah_uuid,
+                sajdah,
+                bismillah_text,
+            ))
+            .into_boxed();
+
+        let rows: Vec<IndexRow> = match index {
+            MushafIndex::Sajdah => query.filter(sajdah.is_not_null()),
+            MushafIndex::Bismillah => query
+                .filter(ayah_number.eq(1))
+                .filter(bismillah_text.is_not_null()),
+            MushafIndex::BismillahAyah => query.filter(is_bismillah.eq(true)),
+        }
+        .get_results(&mut conn)?;
+
+        let words: Vec<(i32, String)> = quran_words
+            .filter(word_ayah_id.eq_any(rows.iter().map(|row| row.0)))
+            .order(word_id.asc())
+            .select((word_ayah_id, word))
+            .get_results(&mut conn)?;
+
+        let mut texts: HashMap<i32, Vec<String>> = HashMap::new();
+
+        for (id, text) in words {
+            texts.entry(id).or_default().push(text);
+        }
+
+        let ayahs = rows
+            .into_iter()
+            .map(
+                |(id, uuid, surah, ayah, s_uuid, ayah_sajdah, bismillah)| IndexAyah {
+                    uuid,
+                    key: VerseKey { surah, ayah },
+                    surah_uuid: s_uuid,
+                    text: texts.remove(&id).unwrap_or_default().join(" "),
+                    sajdah: Sajdah::from_option_string(ayah_sajdah),
+                    bismillah: match index {
+                        MushafIndex::Sajdah => None,
+                        _ => bismillah,
+                    },
+                },
+            )
+            .collect();
+
+        Ok(web::Json(MushafIndexView { index, ayahs }))
+    })
+    .await
+    .unwrap()
+}
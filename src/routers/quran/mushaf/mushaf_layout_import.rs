@@ -0,0 +1,112 @@
+use crate::error::RouterError;
+use crate::models::NewQuranMushafLine;
+use crate::verse_key::mushaf_word_ids;
+use crate::DbPool;
+use actix_web::web;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use super::{LayoutLine, LineType};
+
+/// Rows per insert query, postgres can't bind more than 65535 params
+const IMPORT_CHUNK_SIZE: usize = 5_000;
+
+/// Imports the page and line layout of the mushaf
+///
+/// The existing layout of the mushaf will be replaced
+pub async fn mushaf_layout_import(
+    path: web::Path<Uuid>,
+    import: web::Json<Vec<LayoutLine>>,
+    pool: web::Data<DbPool>,
+    data: web::ReqData<u32>,
+) -> Result<&'static str, RouterError> {
+    use crate::schema::app_users::dsl::{account_id as user_acc_id, app_users, id as user_id};
+    use crate::schema::quran_mushafs::dsl::{id as mushaf_id, quran_mushafs, uuid as mushaf_uuid};
+    use crate::schema::quran_mushafs_lines::dsl::{
+        mushaf_id as line_mushaf_id, quran_mushafs_lines,
+    };
+
+    let target_mushaf_uuid = path.into_inner();
+    let import = import.into_inner();
+    let data = data.into_inner();
+
+    web::block(move || {
+        let mut conn = pool.get().unwrap();
+
+        conn.transaction(|conn| {
+            let user: i32 = app_users
+                .filter(user_acc_id.eq(data as i32))
+                .select(user_id)
+                .get_result(conn)?;
+
+            let mushaf: i32 = quran_mushafs
+                .filter(mushaf_uuid.eq(target_mushaf_uuid))
+                .select(mushaf_id)
+                .get_result(conn)?;
+
+            let word_ids = mushaf_word_ids(conn, mushaf)?;
+
+            let mut lines = vec![];
+
+            for line in import {
+                let line_not_valid = || {
+                    RouterError::from_predefined_with_detail(
+                        "LAYOUT_LINE_NOT_VALID",
+                        &format!("{}:{}", line.page, line.line),
+                    )
+                };
+
+                let (surah_number, words) = match (line.line_type, line.first_word, line.last_word)
+                {
+                    (LineType::Text, Some(first), Some(last)) if first <= last => {
+                        let word_id = |key| {
+                            word_ids.get(&key).copied().ok_or_else(|| {
+                                RouterError::from_predefined_with_detail(
+                                    "WORD_NOT_FOUND",
+                                    &key.to_string(),
+                                )
+                            })
+                        };
+
+                        (None, Some((word_id(first)?, word_id(last)?)))
+                    }
+
+                    (LineType::SurahHeader | LineType::Bismillah, None, None)
+                        if line.surah.is_some() =>
+                    {
+                        (line.surah, None)
+                    }
+
+                    _ => return Err(line_not_valid()),
+                };
+
+                if line.page < 1 || line.line < 1 {
+                    return Err(line_not_valid());
+                }
+
+                lines.push(NewQuranMushafLine {
+                    creator_user_id: user,
+                    mushaf_id: mushaf,
+                    page: line.page,
+                    line_number: line.line,
+                    line_type: line.line_type.to_string(),
+                    surah_number,
+                    first_word_id: words.map(|(first, _)| first),
+                    last_word_id: words.map(|(_, last)| last),
+                });
+            }
+
+            diesel::delete(quran_mushafs_lines.filter(line_mushaf_id.eq(mushaf))).execute(conn)?;
+
+            for chunk in lines.chunks(IMPORT_CHUNK_SIZE) {
+                diesel::insert_into(quran_mushafs_lines)
+                    .values(chunk)
+                    .execute(conn)?;
+            }
+
+            Ok("Imported")
+        })
+    })
+    .await
+    .unwrap()
+}
@@ -0,0 +1,241 @@
+use crate::error::RouterError;
+use crate::models::QuranMushafLine;
+use crate::verse_key::{VerseKey, WordKey};
+use crate::DbPool;
+use actix_web::web;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use super::{LineType, MushafPage, PageLine, PageWord};
+
+/// Returns the lines of a mushaf page with their words
+pub async fn mushaf_page_view(
+    path: web::Path<(Uuid, i32)>,
+    pool: web::Data<DbPool>,
+) -> Result<web::Json<MushafPage>, RouterError> {
+    use crate::schema::quran_ayahs::dsl::{ayah_number, quran_ayahs};
+    use crate::schema::quran_mushafs::dsl::{id as mushaf_id, quran_mushafs, uuid as mushaf_uuid};
+    use crate::schema::quran_mushafs_lines::dsl::{
+        line_number, mushaf_id as line_mushaf_id, page, quran_mushafs_lines,
+    };
+    use crate::schema::quran_surahs::dsl::{
+        mushaf_id as surah_mushaf_id, number as surah_number, quran_surahs,
+    };
+    use crate::schema::quran_words::dsl::{id as word_id, quran_words, uuid as word_uuid, word};
+
+    let (target_mushaf_uuid, target_page) = path.into_inner();
+
+    web::block(move || {
+        let mut conn = pool.get().unwrap();
+
+        let mushaf: i32 = quran_mushafs
+            .filter(mushaf_uuid.eq(target_mushaf_uuid))
+            .select(mushaf_id)
+            .get_result(&mut conn)?;
+
+        let lines: Vec<QuranMushafLine> = quran_mushafs_lines
+            .filter(line_mushaf_id.eq(mushaf))
+            .filter(page.eq(target_page))
+            .order(line_number.asc())
+            .get_results(&mut conn)?;
+
+        if lines.is_empty() {
+            return Err(RouterError::from_predefined("NOT_FOUND"));
+        }
+
+        let range_ids: Vec<i32> = lines
+            .iter()
+            .flat_map(|line| [line.first_word_id, line.last_word_id])
+            .flatten()
+            .collect();
+
+        // The verses that the page starts and ends with
+        let range_verses: Vec<(i32, i32)> = quran_surahs
+            .inner_join(quran_ayahs.inner_join(quran_words))
+            .filter(word_id.eq_any(range_ids))
+            .select((surah_number, ayah_number))
+            .get_results(&mut conn)?;
+
+        let range_verses: Vec<VerseKey> = range_verses
+            .into_iter()
+            .map(|(surah, ayah)| VerseKey { surah, ayah })
+            .collect();
+
+        let (Some(first_verse), Some(last_verse)) =
+            (range_verses.iter().min(), range_verses.iter().max())
+        else {
+            // Page has no text lines
+            return Ok(web::Json(MushafPage {
+                page: target_page,
+                lines: page_lines(lines, vec![]),
+            }));
+        };
+
+        let words: Vec<(i32, Uuid, String, i32, i32)> = quran_surahs
+            .inner_join(quran_ayahs.inner_join(quran_words))
+            .filter(surah_mushaf_id.eq(mushaf))
+            .filter(surah_number.between(first_verse.surah, last_verse.surah))
+            .order((surah_number.asc(), ayah_number.asc(), word_id.asc()))
+            .select((word_id, word_uuid, word, surah_number, ayah_number))
+            .get_results(&mut conn)?;
+
+        // Only the ayahs of the page, every ayah has all of its words
+        let words = words
+            .into_iter()
+            .filter(|(_, _, _, surah, ayah)| {
+                let verse = VerseKey {
+                    surah: *surah,
+                    ayah: *ayah,
+                };
+
+                verse >= *first_verse && verse <= *last_verse
+            })
+            .collect();
+
+        Ok(web::Json(MushafPage {
+            page: target_page,
+            lines: page_lines(lines, words),
+        }))
+    })
+    .await
+    .unwrap()
+}
+
+/// Returns the lines with their words
+///
+/// `words` are (id, uuid, word, surah, ayah) of the page ayahs in order, with every word of
+/// the ayahs, so the positions are right when the page starts in the middle of an ayah
+fn page_lines(
+    lines: Vec<QuranMushafLine>,
+    words: Vec<(i32, Uuid, String, i32, i32)>,
+) -> Vec<PageLine> {
+    // Positions of the words in their ayahs
+    let mut page_words = vec![];
+    let mut last_verse_key = None;
+    let mut position = 0;
+
+    for (id, uuid, text, surah, ayah) in words {
+        let verse = VerseKey { surah, ayah };
+
+        if last_verse_key != Some(verse) {
+            last_verse_key = Some(verse);
+            position = 0;
+        }
+
+        position += 1;
+
+        page_words.push((
+            id,
+            PageWord {
+                uuid,
+                key: WordKey {
+                    surah,
+                    ayah,
+                    word: position,
+                },
+                word: text,
+            },
+        ));
+    }
+
+    lines
+        .into_iter()
+        .map(|line| {
+            let line_words = match (line.first_word_id, line.last_word_id) {
+                (Some(first), Some(last)) => {
+                    let start = page_words.iter().position(|(id, _)| *id == first);
+                    let end = page_words.iter().position(|(id, _)| *id == last);
+
+                    match (start, end) {
+                        (Some(start), Some(end)) if start <= end => page_words[start..=end]
+                            .iter()
+                            .map(|(_, w)| w.clone())
+                            .collect(),
+
+                        _ => vec![],
+                    }
+                }
+
+                _ => vec![],
+            };
+
+            page_line(line, line_words)
+        })
+        .collect()
+}
+
+fn page_line(line: QuranMushafLine, words: Vec<PageWord>) -> PageLine {
+    PageLine {
+        line: line.line_number,
+        // Line types are validated on import
+        line_type: line.line_type.parse().unwrap_or(LineType::Text),
+        surah: line.surah_number,
+        words,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDateTime;
+    use uuid::Uuid;
+
+    use super::page_lines;
+    use crate::models::QuranMushafLine;
+    use crate::routers::quran::mushaf::LineType;
+
+    fn line(
+        line_number: i32,
+        line_type: &str,
+        first_word_id: Option<i32>,
+        last_word_id: Option<i32>,
+    ) -> QuranMushafLine {
+        QuranMushafLine {
+            id: line_number,
+            uuid: Uuid::nil(),
+            creator_user_id: 1,
+            mushaf_id: 1,
+            page: 2,
+            line_number,
+            line_type: line_type.to_string(),
+            surah_number: None,
+            first_word_id,
+            last_word_id,
+            created_at: NaiveDateTime::default(),
+            updated_at: NaiveDateTime::default(),
+        }
+    }
+
+    #[test]
+    fn test_page_starts_mid_ayah() {
+        // 2:5 has three words, the page starts at its second word
+        let words = [(10, 2, 5), (11, 2, 5), (12, 2, 5), (13, 2, 6), (14, 2, 6)]
+            .into_iter()
+            .map(|(id, surah, ayah)| (id, Uuid::nil(), format!("w{}", id), surah, ayah))
+            .collect();
+
+        let lines = page_lines(
+            vec![
+                line(1, "text", Some(11), Some(13)),
+                line(2, "text", Some(14), Some(14)),
+                line(3, "bismillah", None, None),
+            ],
+            words,
+        );
+
+        let keys: Vec<Vec<String>> = lines
+            .iter()
+            .map(|l| l.words.iter().map(|w| w.key.to_string()).collect())
+            .collect();
+
+        assert_eq!(
+            keys,
+            vec![
+                vec!["2:5:2", "2:5:3", "2:6:1"],
+                vec!["2:6:2"],
+                Vec::<&str>::new(),
+            ]
+        );
+        assert_eq!(lines[0].words[0].word, "w11");
+        assert_eq!(lines[2].line_type, LineType::Bismillah);
+    }
+}
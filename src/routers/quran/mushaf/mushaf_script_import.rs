@@ -0,0 +1,82 @@
+use crate::error::RouterError;
+use crate::models::NewQuranWordRendition;
+use crate::verse_key::mushaf_word_ids;
+use crate::DbPool;
+use actix_web::web;
+use diesel::prelude::*;
+use diesel::upsert::excluded;
+use uuid::Uuid;
+
+use super::MushafScriptImport;
+
+/// Rows per insert query, postgres can't bind more than 65535 params
+const IMPORT_CHUNK_SIZE: usize = 10_000;
+
+/// Imports the text of the mushaf words in another script
+///
+/// Existing renditions of the words in that script will be replaced
+pub async fn mushaf_script_import(
+    path: web::Path<Uuid>,
+    import: web::Json<MushafScriptImport>,
+    pool: web::Data<DbPool>,
+    data: web::ReqData<u32>,
+) -> Result<&'static str, RouterError> {
+    use crate::schema::app_users::dsl::{account_id as user_acc_id, app_users, id as user_id};
+    use crate::schema::quran_mushafs::dsl::{id as mushaf_id, quran_mushafs, uuid as mushaf_uuid};
+    use crate::schema::quran_words_renditions::dsl::{
+        quran_words_renditions, script, text, word_id,
+    };
+
+    let target_mushaf_uuid = path.into_inner();
+    let import = import.into_inner();
+    let data = data.into_inner();
+
+    web::block(move || {
+        let mut conn = pool.get().unwrap();
+
+        conn.transaction(|conn| {
+            let user: i32 = app_users
+                .filter(user_acc_id.eq(data as i32))
+                .select(user_id)
+                .get_result(conn)?;
+
+            let mushaf: i32 = quran_mushafs
+                .filter(mushaf_uuid.eq(target_mushaf_uuid))
+                .select(mushaf_id)
+                .get_result(conn)?;
+
+            let word_ids = mushaf_word_ids(conn, mushaf)?;
+
+            let mut renditions = vec![];
+
+            for (key, rendition) in import.words.iter() {
+                let Some(id) = word_ids.get(key) else {
+                    return Err(RouterError::from_predefined_with_detail(
+                        "WORD_NOT_FOUND",
+                        &key.to_string(),
+                    ));
+                };
+
+                renditions.push(NewQuranWordRendition {
+                    creator_user_id: user,
+                    word_id: *id,
+                    script: &import.script,
+                    text: rendition,
+                });
+            }
+
+            for chunk in renditions.chunks(IMPORT_CHUNK_SIZE) {
+                diesel::insert_into(quran_words_renditions)
+                    .values(chunk)
+                    .on_conflict((word_id, script))
+                    .do_update()
+                    .set(text.eq(excluded(text)))
+                    .execute(conn)?;
+            }
+
+            Ok("Imported")
+        })
+    })
+    .await
+    .unwrap()
+}
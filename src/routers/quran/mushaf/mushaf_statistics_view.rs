@@ -0,0 +1,58 @@
+use crate::error::RouterError;
+use crate::http_cache::ContentVersion;
+use crate::statistics::{MushafStatistics, StatisticsCache};
+use crate::verse_key::mushaf_ayah_words;
+use crate::DbPool;
+use actix_web::web;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use super::{MushafStatisticsQuery, MushafStatisticsView};
+
+const DEFAULT_LIMIT: usize = 10;
+const MAX_LIMIT: usize = 100;
+
+/// Returns the word and letter statistics of a mushaf
+pub async fn mushaf_statistics_view(
+    path: web::Path<Uuid>,
+    web::Query(query): web::Query<MushafStatisticsQuery>,
+    pool: web::Data<DbPool>,
+    cache: web::Data<StatisticsCache>,
+    version: Option<web::ReqData<ContentVersion>>,
+) -> Result<web::Json<MushafStatisticsView>, RouterError> {
+    use crate::schema::quran_mushafs::dsl::{id as mushaf_id, quran_mushafs, uuid as mushaf_uuid};
+
+    let target_mushaf_uuid = path.into_inner();
+    let version = version.map(|version| version.into_inner().0);
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT);
+
+    web::block(move || {
+        let mut conn = pool.get().unwrap();
+
+        let mushaf: i32 = quran_mushafs
+            .filter(mushaf_uuid.eq(target_mushaf_uuid))
+            .select(mushaf_id)
+            .get_result(&mut conn)?;
+
+        let statistics = cache.get_or_compute(mushaf, version, || {
+            Ok::<_, RouterError>(MushafStatistics::compute(&mushaf_ayah_words(
+                &mut conn, mushaf,
+            )?))
+        })?;
+
+        Ok(web::Json(MushafStatisticsView {
+            surahs: statistics.surahs.len() as u32,
+            ayahs: statistics.ayahs.len() as u32,
+            words: statistics.words(),
+            letters: statistics.letters(),
+            unique_words: statistics.words.len() as u32,
+            frequent_words: statistics.words.iter().take(limit).cloned().collect(),
+            longest_ayahs: statistics.longest_ayahs(limit),
+            shortest_ayahs: statistics.shortest_ayahs(limit),
+            longest_surahs: statistics.longest_surahs(limit),
+            shortest_surahs: statistics.shortest_surahs(limit),
+        }))
+    })
+    .await
+    .unwrap()
+}
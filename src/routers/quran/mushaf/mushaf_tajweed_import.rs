@@ -0,0 +1,128 @@
+use std::collections::HashMap;
+
+use crate::error::RouterError;
+use crate::models::NewQuranWordTajweed;
+use crate::routers::quran::word::split_tajweed;
+use crate::verse_key::VerseKey;
+use crate::DbPool;
+use actix_web::web;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use super::AyahTajweed;
+
+/// Rows per insert query, postgres can't bind more than 65535 params
+const IMPORT_CHUNK_SIZE: usize = 10_000;
+
+/// Imports the tajweed annotations of the mushaf ayahs
+///
+/// Annotations are on the ayah text and will be splitted into the words,
+/// existing annotations of the imported ayahs will be replaced
+pub async fn mushaf_tajweed_import(
+    path: web::Path<Uuid>,
+    import: web::Json<Vec<AyahTajweed>>,
+    pool: web::Data<DbPool>,
+    data: web::ReqData<u32>,
+) -> Result<&'static str, RouterError> {
+    use crate::schema::app_users::dsl::{account_id as user_acc_id, app_users, id as user_id};
+    use crate::schema::quran_ayahs::dsl::{ayah_number, quran_ayahs};
+    use crate::schema::quran_mushafs::dsl::{id as mushaf_id, quran_mushafs, uuid as mushaf_uuid};
+    use crate::schema::quran_surahs::dsl::{
+        mushaf_id as surah_mushaf_id, number as surah_number, quran_surahs,
+    };
+    use crate::schema::quran_words::dsl::{id as word_id, quran_words, word};
+    use crate::schema::quran_words_tajweed::dsl::{
+        quran_words_tajweed, word_id as tajweed_word_id,
+    };
+
+    let target_mushaf_uuid = path.into_inner();
+    let import = import.into_inner();
+    let data = data.into_inner();
+
+    web::block(move || {
+        let mut conn = pool.get().unwrap();
+
+        conn.transaction(|conn| {
+            let user: i32 = app_users
+                .filter(user_acc_id.eq(data as i32))
+                .select(user_id)
+                .get_result(conn)?;
+
+            let mushaf: i32 = quran_mushafs
+                .filter(mushaf_uuid.eq(target_mushaf_uuid))
+                .select(mushaf_id)
+                .get_result(conn)?;
+
+            // Words of the imported surahs by their ayah, in a single query
+            let mut ayahs_words: HashMap<VerseKey, Vec<(i32, String)>> = HashMap::new();
+
+            let rows: Vec<(i32, i32, i32, String)> = quran_surahs
+                .inner_join(quran_ayahs.inner_join(quran_words))
+                .filter(surah_mushaf_id.eq(mushaf))
+                .filter(surah_number.eq_any(import.iter().map(|ayah| ayah.surah)))
+                .order(word_id.asc())
+                .select((surah_number, ayah_number, word_id, word))
+                .get_results(conn)?;
+
+            for (surah, ayah, id, text) in rows {
+                ayahs_words
+                    .entry(VerseKey { surah, ayah })
+                    .or_default()
+                    .push((id, text));
+            }
+
+            let mut annotations = vec![];
+            let mut imported_words = vec![];
+
+            for ayah in import {
+                let key = VerseKey {
+                    surah: ayah.surah,
+                    ayah: ayah.ayah,
+                };
+
+                let Some(words) = ayahs_words.get(&key) else {
+                    return Err(RouterError::from_predefined_with_detail(
+                        "AYAH_NOT_FOUND",
+                        &key.to_string(),
+                    ));
+                };
+
+                let texts: Vec<String> = words.iter().map(|(_, text)| text.clone()).collect();
+
+                let Some(splitted) = split_tajweed(&texts, &ayah.annotations) else {
+                    return Err(RouterError::from_predefined_with_detail(
+                        "TAJWEED_ANNOTATION_NOT_VALID",
+                        &key.to_string(),
+                    ));
+                };
+
+                imported_words.extend(words.iter().map(|(id, _)| *id));
+
+                annotations.extend(splitted.into_iter().map(|(index, annotation)| {
+                    NewQuranWordTajweed {
+                        creator_user_id: user,
+                        word_id: words[index].0,
+                        rule: annotation.rule.to_string(),
+                        start_index: annotation.start,
+                        end_index: annotation.end,
+                    }
+                }));
+            }
+
+            for chunk in imported_words.chunks(IMPORT_CHUNK_SIZE) {
+                diesel::delete(quran_words_tajweed.filter(tajweed_word_id.eq_any(chunk)))
+                    .execute(conn)?;
+            }
+
+            for chunk in annotations.chunks(IMPORT_CHUNK_SIZE) {
+                diesel::insert_into(quran_words_tajweed)
+                    .values(chunk)
+                    .execute(conn)?;
+            }
+
+            Ok("Imported")
+        })
+    })
+    .await
+    .unwrap()
+}
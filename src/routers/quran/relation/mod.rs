@@ -0,0 +1,151 @@
+pub mod relation_add;
+pub mod relation_delete;
+pub mod relation_edit;
+pub mod relation_import;
+pub mod relation_list;
+pub mod relation_similar;
+pub mod relation_view;
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::models::QuranAyahRelation;
+use crate::verse_key::VerseKey;
+
+/// Type of the link between two ayahs
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RelationType {
+    Thematic,
+    SameStory,
+
+    /// The ayah abrogates the related ayah
+    Abrogation,
+
+    /// The ayah explains the related ayah
+    Explanation,
+
+    /// Computed from the text similarity
+    Similar,
+}
+
+impl RelationType {
+    pub const ALL: [Self; 5] = [
+        Self::Thematic,
+        Self::SameStory,
+        Self::Abrogation,
+        Self::Explanation,
+        Self::Similar,
+    ];
+}
+
+impl Display for RelationType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Thematic => "thematic",
+            Self::SameStory => "same_story",
+            Self::Abrogation => "abrogation",
+            Self::Explanation => "explanation",
+            Self::Similar => "similar",
+        };
+
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for RelationType {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|relation_type| relation_type.to_string() == s)
+            .ok_or(())
+    }
+}
+
+#[derive(Deserialize, Validate)]
+pub struct SimpleRelation {
+    pub ayah_uuid: Uuid,
+    pub related_ayah_uuid: Uuid,
+    pub relation_type: RelationType,
+
+    #[validate(length(min = 1))]
+    pub source: Option<String>,
+}
+
+#[derive(Deserialize, Validate)]
+pub struct EditableSimpleRelation {
+    pub relation_type: RelationType,
+
+    #[validate(length(min = 1))]
+    pub source: Option<String>,
+}
+
+/// A relation of the import, ayahs are addressed by their `surah:ayah` key
+#[derive(Deserialize)]
+pub struct ImportedRelation {
+    pub ayah: VerseKey,
+    pub related_ayah: VerseKey,
+    pub relation_type: RelationType,
+    pub source: Option<String>,
+}
+
+/// The response type for /relation/{uuid}
+#[derive(Serialize)]
+pub struct RelationWithAyahs {
+    #[serde(flatten)]
+    pub relation: QuranAyahRelation,
+
+    pub ayah_uuid: Uuid,
+    pub related_ayah_uuid: Uuid,
+}
+
+/// The query for the /relation
+/// example: /relation?ayah_uuid={uuid}&relation_type=thematic
+#[derive(Deserialize)]
+pub struct RelationListQuery {
+    pub ayah_uuid: Uuid,
+    pub relation_type: Option<RelationType>,
+
+    /// Return the related ayahs text in this translation too
+    pub translation_uuid: Option<Uuid>,
+}
+
+/// The query for the /relation/similar/{translation_uuid}
+#[derive(Deserialize)]
+pub struct SimilarQuery {
+    /// Similar ayahs of every ayah
+    pub limit: Option<usize>,
+    pub min_score: Option<f64>,
+}
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RelationDirection {
+    /// The requested ayah is the ayah of the relation
+    Outgoing,
+
+    /// The requested ayah is the related ayah of the relation
+    Incoming,
+}
+
+#[derive(Serialize)]
+pub struct RelatedAyah {
+    pub relation_uuid: Uuid,
+    pub relation_type: String,
+    pub direction: RelationDirection,
+    pub source: Option<String>,
+
+    pub ayah_uuid: Uuid,
+    pub surah_number: i32,
+    pub ayah_number: i32,
+    pub text: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub translation: Option<String>,
+}
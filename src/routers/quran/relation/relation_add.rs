@@ -0,0 +1,62 @@
+use crate::error::RouterError;
+use crate::models::NewQuranAyahRelation;
+use crate::validate::validate;
+use crate::DbPool;
+use actix_web::web;
+use diesel::prelude::*;
+
+use super::SimpleRelation;
+
+/// Add's a new relation between two ayahs
+pub async fn relation_add(
+    new_relation: web::Json<SimpleRelation>,
+    pool: web::Data<DbPool>,
+    data: web::ReqData<u32>,
+) -> Result<&'static str, RouterError> {
+    use crate::schema::app_users::dsl::{account_id as user_acc_id, app_users, id as user_id};
+    use crate::schema::quran_ayahs::dsl::{id as ayah_id, quran_ayahs, uuid as ayah_uuid};
+    use crate::schema::quran_ayahs_relations::dsl::quran_ayahs_relations;
+
+    let new_relation = new_relation.into_inner();
+    let data = data.into_inner();
+
+    validate(&new_relation)?;
+
+    if new_relation.ayah_uuid == new_relation.related_ayah_uuid {
+        return Err(RouterError::from_predefined("RELATION_NOT_VALID"));
+    }
+
+    web::block(move || {
+        let mut conn = pool.get().unwrap();
+
+        // Get the creator user-id
+        let user: i32 = app_users
+            .filter(user_acc_id.eq(data as i32))
+            .select(user_id)
+            .get_result(&mut conn)?;
+
+        let ayah: i32 = quran_ayahs
+            .filter(ayah_uuid.eq(new_relation.ayah_uuid))
+            .select(ayah_id)
+            .get_result(&mut conn)?;
+
+        let related_ayah: i32 = quran_ayahs
+            .filter(ayah_uuid.eq(new_relation.related_ayah_uuid))
+            .select(ayah_id)
+            .get_result(&mut conn)?;
+
+        NewQuranAyahRelation {
+            creator_user_id: user,
+            ayah_id: ayah,
+            related_ayah_id: related_ayah,
+            relation_type: new_relation.relation_type.to_string(),
+            source: new_relation.source.as_deref(),
+        }
+        .insert_into(quran_ayahs_relations)
+        .execute(&mut conn)?;
+
+        Ok("Added")
+    })
+    .await
+    .unwrap()
+}
@@ -0,0 +1,39 @@
+use crate::error::RouterError;
+use crate::validate::validate;
+use crate::DbPool;
+use actix_web::web;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use super::EditableSimpleRelation;
+
+/// Update's single relation
+pub async fn relation_edit(
+    path: web::Path<Uuid>,
+    new_relation: web::Json<EditableSimpleRelation>,
+    pool: web::Data<DbPool>,
+) -> Result<&'static str, RouterError> {
+    use crate::schema::quran_ayahs_relations::dsl::{
+        quran_ayahs_relations, relation_type, source, uuid as relation_uuid,
+    };
+
+    let new_relation = new_relation.into_inner();
+    let path = path.into_inner();
+
+    validate(&new_relation)?;
+
+    web::block(move || {
+        let mut conn = pool.get().unwrap();
+
+        diesel::update(quran_ayahs_relations.filter(relation_uuid.eq(path)))
+            .set((
+                relation_type.eq(new_relation.relation_type.to_string()),
+                source.eq(new_relation.source),
+            ))
+            .execute(&mut conn)?;
+
+        Ok("Edited")
+    })
+    .await
+    .unwrap()
+}
@@ -0,0 +1,101 @@
+use crate::error::RouterError;
+use crate::models::NewQuranAyahRelation;
+use crate::verse_key::mushaf_ayah_ids;
+use crate::DbPool;
+use actix_web::web;
+use diesel::prelude::*;
+use diesel::upsert::excluded;
+use uuid::Uuid;
+
+use super::ImportedRelation;
+
+/// Rows per insert query, postgres can't bind more than 65535 params
+const IMPORT_CHUNK_SIZE: usize = 10_000;
+
+/// Imports the relations between the mushaf ayahs
+///
+/// Existing relations with the same ayahs and type get the new source
+pub async fn relation_import(
+    path: web::Path<Uuid>,
+    import: web::Json<Vec<ImportedRelation>>,
+    pool: web::Data<DbPool>,
+    data: web::ReqData<u32>,
+) -> Result<&'static str, RouterError> {
+    use crate::schema::app_users::dsl::{account_id as user_acc_id, app_users, id as user_id};
+    use crate::schema::quran_ayahs_relations::dsl::{
+        ayah_id, quran_ayahs_relations, related_ayah_id, relation_type, source,
+    };
+    use crate::schema::quran_mushafs::dsl::{id as mushaf_id, quran_mushafs, uuid as mushaf_uuid};
+
+    let target_mushaf_uuid = path.into_inner();
+    let import = import.into_inner();
+    let data = data.into_inner();
+
+    web::block(move || {
+        let mut conn = pool.get().unwrap();
+
+        let user: i32 = app_users
+            .filter(user_acc_id.eq(data as i32))
+            .select(user_id)
+            .get_result(&mut conn)?;
+
+        let mushaf: i32 = quran_mushafs
+            .filter(mushaf_uuid.eq(target_mushaf_uuid))
+            .select(mushaf_id)
+            .get_result(&mut conn)?;
+
+        let ayah_ids = mushaf_ayah_ids(&mut conn, mushaf)?;
+
+        let mut new_relations = Vec::with_capacity(import.len());
+
+        for relation in &import {
+            let (Some(ayah), Some(related_ayah)) = (
+                ayah_ids.get(&relation.ayah),
+                ayah_ids.get(&relation.related_ayah),
+            ) else {
+                let missing = if ayah_ids.contains_key(&relation.ayah) {
+                    relation.related_ayah
+                } else {
+                    relation.ayah
+                };
+
+                return Err(RouterError::from_predefined_with_detail(
+                    "AYAH_NOT_FOUND",
+                    &missing.to_string(),
+                ));
+            };
+
+            if ayah == related_ayah {
+                return Err(RouterError::from_predefined_with_detail(
+                    "RELATION_NOT_VALID",
+                    &relation.ayah.to_string(),
+                ));
+            }
+
+            new_relations.push(NewQuranAyahRelation {
+                creator_user_id: user,
+                ayah_id: *ayah,
+                related_ayah_id: *related_ayah,
+                relation_type: relation.relation_type.to_string(),
+                source: relation.source.as_deref(),
+            });
+        }
+
+        conn.transaction(|conn| {
+            for chunk in new_relations.chunks(IMPORT_CHUNK_SIZE) {
+                diesel::insert_into(quran_ayahs_relations)
+                    .values(chunk)
+                    .on_conflict((ayah_id, related_ayah_id, relation_type))
+                    .do_update()
+                    .set(source.eq(excluded(source)))
+                    .execute(conn)?;
+            }
+
+            Ok::<(), RouterError>(())
+        })?;
+
+        Ok("Imported")
+    })
+    .await
+    .unwrap()
+}
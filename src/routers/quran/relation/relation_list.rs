@@ -0,0 +1,140 @@
+use std::collections::HashMap;
+
+use crate::models::QuranAyahRelation;
+use crate::{error::RouterError, DbPool};
+use actix_web::web;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use super::{RelatedAyah, RelationDirection, RelationListQuery};
+
+/// Returns the related ayahs of an ayah with their text
+pub async fn relation_list(
+    query: web::Query<RelationListQuery>,
+    pool: web::Data<DbPool>,
+) -> Result<web::Json<Vec<RelatedAyah>>, RouterError> {
+    use crate::schema::quran_ayahs::dsl::{
+        ayah_number, id as ayah_id, quran_ayahs, uuid as ayah_uuid,
+    };
+    use crate::schema::quran_ayahs_relations::dsl::{
+        ayah_id as relation_ayah_id, quran_ayahs_relations, related_ayah_id, relation_type,
+    };
+    use crate::schema::quran_surahs::dsl::{number as surah_number, quran_surahs};
+    use crate::schema::quran_translations::dsl::{
+        id as translation_id, quran_translations, uuid as translation_uuid,
+    };
+    use crate::schema::quran_translations_ayahs::dsl::{
+        ayah_id as text_ayah_id, quran_translations_ayahs, text as translation_text,
+        translation_id as text_translation_id,
+    };
+    use crate::schema::quran_words::dsl::{
+        ayah_id as word_ayah_id, id as word_id, quran_words, word,
+    };
+
+    let query = query.into_inner();
+
+    web::block(move || {
+        let mut conn = pool.get().unwrap();
+
+        let ayah: i32 = quran_ayahs
+            .filter(ayah_uuid.eq(query.ayah_uuid))
+            .select(ayah_id)
+            .get_result(&mut conn)?;
+
+        let mut outgoing = quran_ayahs_relations
+            .filter(relation_ayah_id.eq(ayah))
+            .into_boxed();
+        let mut incoming = quran_ayahs_relations
+            .filter(related_ayah_id.eq(ayah))
+            .into_boxed();
+
+        if let Some(ref target_type) = query.relation_type {
+            outgoing = outgoing.filter(relation_type.eq(target_type.to_string()));
+            incoming = incoming.filter(relation_type.eq(target_type.to_string()));
+        }
+
+        // (relation, direction, the other ayah)
+        let relations: Vec<(QuranAyahRelation, RelationDirection, i32)> = outgoing
+            .get_results::<QuranAyahRelation>(&mut conn)?
+            .into_iter()
+            .map(|r| {
+                let other = r.related_ayah_id;
+                (r, RelationDirection::Outgoing, other)
+            })
+            .chain(
+                incoming
+                    .get_results::<QuranAyahRelation>(&mut conn)?
+                    .into_iter()
+                    .map(|r| {
+                        let other = r.ayah_id;
+                        (r, RelationDirection::Incoming, other)
+                    }),
+            )
+            .collect();
+
+        let ids: Vec<i32> = relations.iter().map(|(_, _, other)| *other).collect();
+
+        let ayahs: HashMap<i32, (Uuid, i32, i32)> = quran_ayahs
+            .inner_join(quran_surahs)
+            .filter(ayah_id.eq_any(&ids))
+            .select((ayah_id, ayah_uuid, surah_number, ayah_number))
+            .get_results::<(i32, Uuid, i32, i32)>(&mut conn)?
+            .into_iter()
+            .map(|(id, uuid, surah, number)| (id, (uuid, surah, number)))
+            .collect();
+
+        let mut texts: HashMap<i32, Vec<String>> = HashMap::new();
+        for (id, text) in quran_words
+            .filter(word_ayah_id.eq_any(&ids))
+            .order(word_id.asc())
+            .select((word_ayah_id, word))
+            .get_results::<(i32, String)>(&mut conn)?
+        {
+            texts.entry(id).or_default().push(text);
+        }
+
+        let translations: HashMap<i32, String> = match query.translation_uuid {
+            Some(uuid) => {
+                let translation: i32 = quran_translations
+                    .filter(translation_uuid.eq(uuid))
+                    .select(translation_id)
+                    .get_result(&mut conn)?;
+
+                quran_translations_ayahs
+                    .filter(text_translation_id.eq(translation))
+                    .filter(text_ayah_id.eq_any(&ids))
+                    .select((text_ayah_id, translation_text))
+                    .get_results::<(i32, String)>(&mut conn)?
+                    .into_iter()
+                    .collect()
+            }
+
+            None => HashMap::new(),
+        };
+
+        let mut result: Vec<RelatedAyah> = relations
+            .into_iter()
+            .filter_map(|(relation, direction, other)| {
+                let (uuid, surah, number) = *ayahs.get(&other)?;
+
+                Some(RelatedAyah {
+                    relation_uuid: relation.uuid,
+                    relation_type: relation.relation_type,
+                    direction,
+                    source: relation.source,
+                    ayah_uuid: uuid,
+                    surah_number: surah,
+                    ayah_number: number,
+                    text: texts.get(&other).map(|t| t.join(" ")).unwrap_or_default(),
+                    translation: translations.get(&other).cloned(),
+                })
+            })
+            .collect();
+
+        result.sort_by_key(|r| (r.surah_number, r.ayah_number));
+
+        Ok(web::Json(result))
+    })
+    .await
+    .unwrap()
+}
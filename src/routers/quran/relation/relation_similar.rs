@@ -0,0 +1,101 @@
+use crate::error::RouterError;
+use crate::models::NewQuranAyahRelation;
+use crate::similarity::similar_documents;
+use crate::DbPool;
+use actix_web::web;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use super::{RelationType, SimilarQuery};
+
+/// Rows per insert query, postgres can't bind more than 65535 params
+const IMPORT_CHUNK_SIZE: usize = 10_000;
+
+const DEFAULT_LIMIT: usize = 5;
+const DEFAULT_MIN_SCORE: f64 = 0.3;
+
+/// Computes the similar ayahs from the TF-IDF of a translation text
+///
+/// Similar relations computed from the same translation before will be replaced
+pub async fn relation_similar(
+    path: web::Path<Uuid>,
+    query: web::Query<SimilarQuery>,
+    pool: web::Data<DbPool>,
+    data: web::ReqData<u32>,
+) -> Result<&'static str, RouterError> {
+    use crate::schema::app_users::dsl::{account_id as user_acc_id, app_users, id as user_id};
+    use crate::schema::quran_ayahs_relations::dsl::{quran_ayahs_relations, relation_type, source};
+    use crate::schema::quran_translations::dsl::{
+        id as translation_id, quran_translations, uuid as translation_uuid,
+    };
+    use crate::schema::quran_translations_ayahs::dsl::{
+        ayah_id, quran_translations_ayahs, text, translation_id as text_translation_id,
+    };
+
+    let target_translation_uuid = path.into_inner();
+    let query = query.into_inner();
+    let data = data.into_inner();
+
+    web::block(move || {
+        let mut conn = pool.get().unwrap();
+
+        let user: i32 = app_users
+            .filter(user_acc_id.eq(data as i32))
+            .select(user_id)
+            .get_result(&mut conn)?;
+
+        let translation: i32 = quran_translations
+            .filter(translation_uuid.eq(target_translation_uuid))
+            .select(translation_id)
+            .get_result(&mut conn)?;
+
+        let ayahs: Vec<(i32, String)> = quran_translations_ayahs
+            .filter(text_translation_id.eq(translation))
+            .order(ayah_id.asc())
+            .select((ayah_id, text))
+            .get_results(&mut conn)?;
+
+        let documents: Vec<&str> = ayahs.iter().map(|(_, t)| t.as_str()).collect();
+
+        let similar = similar_documents(
+            &documents,
+            query.limit.unwrap_or(DEFAULT_LIMIT),
+            query.min_score.unwrap_or(DEFAULT_MIN_SCORE),
+        );
+
+        let relation_source = format!("tf-idf:{}", target_translation_uuid);
+
+        let new_relations: Vec<NewQuranAyahRelation> = similar
+            .into_iter()
+            .map(|(ayah, similar_ayah, _)| NewQuranAyahRelation {
+                creator_user_id: user,
+                ayah_id: ayahs[ayah].0,
+                related_ayah_id: ayahs[similar_ayah].0,
+                relation_type: RelationType::Similar.to_string(),
+                source: Some(&relation_source),
+            })
+            .collect();
+
+        conn.transaction(|conn| {
+            diesel::delete(
+                quran_ayahs_relations
+                    .filter(relation_type.eq(RelationType::Similar.to_string()))
+                    .filter(source.eq(&relation_source)),
+            )
+            .execute(conn)?;
+
+            for chunk in new_relations.chunks(IMPORT_CHUNK_SIZE) {
+                diesel::insert_into(quran_ayahs_relations)
+                    .values(chunk)
+                    .on_conflict_do_nothing()
+                    .execute(conn)?;
+            }
+
+            Ok::<(), RouterError>(())
+        })?;
+
+        Ok("Imported")
+    })
+    .await
+    .unwrap()
+}
@@ -0,0 +1,44 @@
+use crate::models::QuranAyahRelation;
+use crate::{error::RouterError, DbPool};
+use actix_web::web;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use super::RelationWithAyahs;
+
+/// Return's a single relation
+pub async fn relation_view(
+    path: web::Path<Uuid>,
+    pool: web::Data<DbPool>,
+) -> Result<web::Json<RelationWithAyahs>, RouterError> {
+    use crate::schema::quran_ayahs::dsl::{id as ayah_id, quran_ayahs, uuid as ayah_uuid};
+    use crate::schema::quran_ayahs_relations::dsl::{quran_ayahs_relations, uuid as relation_uuid};
+
+    let path = path.into_inner();
+
+    web::block(move || {
+        let mut conn = pool.get().unwrap();
+
+        let relation: QuranAyahRelation = quran_ayahs_relations
+            .filter(relation_uuid.eq(path))
+            .get_result(&mut conn)?;
+
+        let ayah: Uuid = quran_ayahs
+            .filter(ayah_id.eq(relation.ayah_id))
+            .select(ayah_uuid)
+            .get_result(&mut conn)?;
+
+        let related_ayah: Uuid = quran_ayahs
+            .filter(ayah_id.eq(relation.related_ayah_id))
+            .select(ayah_uuid)
+            .get_result(&mut conn)?;
+
+        Ok(web::Json(RelationWithAyahs {
+            relation,
+            ayah_uuid: ayah,
+            related_ayah_uuid: related_ayah,
+        }))
+    })
+    .await
+    .unwrap()
+}
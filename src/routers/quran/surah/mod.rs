@@ -2,30 +2,78 @@ pub mod surah_add;
 pub mod surah_delete;
 pub mod surah_edit;
 pub mod surah_list;
+pub mod surah_name_delete;
+pub mod surah_name_modify;
+pub mod surah_statistics_view;
 pub mod surah_view;
 
+use std::collections::{BTreeSet, HashMap};
+use std::fmt::Display;
 use std::hash::Hash;
+use std::str::FromStr;
 
 use crate::{
     filter::{Filters, Order},
-    models::QuranMushaf,
+    models::{QuranMushaf, QuranSurah, QuranSurahName},
 };
+use diesel::prelude::*;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
+use validator::{Validate, ValidationError};
 
-use super::word::WordBreaker;
+use super::word::{TajweedAnnotation, WordBreaker};
+use crate::statistics::AyahStatistics;
+use crate::view_cache::ViewKey;
 
 /// The quran text format Each word has its own uuid
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Default)]
 #[serde(rename_all = "lowercase")]
 pub enum Format {
+    #[default]
     Text,
     Word,
 }
 
-impl Default for Format {
-    fn default() -> Self {
-        Self::Text
+/// Where the surah is revealed
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Period {
+    Meccan,
+    Medinan,
+}
+
+impl Display for Period {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Meccan => write!(f, "meccan"),
+            Self::Medinan => write!(f, "medinan"),
+        }
+    }
+}
+
+impl FromStr for Period {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "meccan" => Ok(Self::Meccan),
+            "medinan" => Ok(Self::Medinan),
+
+            _ => Err(()),
+        }
+    }
+}
+
+impl Period {
+    pub fn from_option_string(value: Option<String>) -> Option<Self> {
+        value?.parse().ok()
+    }
+}
+
+fn validate_period(period: &str) -> Result<(), ValidationError> {
+    match Period::from_str(period) {
+        Ok(_) => Ok(()),
+        Err(()) => Err(ValidationError::new("Period must be meccan or medinan.")),
     }
 }
 
@@ -85,6 +133,9 @@ pub struct AyahWithText {
     #[serde(flatten)]
     pub ayah: SimpleAyah,
     pub text: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transliteration: Option<String>,
 }
 
 #[derive(Serialize, Clone, Debug)]
@@ -93,6 +144,12 @@ pub struct AyahWord {
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub breakers: Option<Vec<WordBreaker>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tajweed: Option<Vec<TajweedAnnotation>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transliteration: Option<String>,
 }
 
 #[derive(Serialize, Clone, Debug)]
@@ -163,15 +220,42 @@ pub struct GetSurahQuery {
     #[serde(default)]
     format: Format,
 
+    /// Comma separated languages of the names, or `all`
+    #[serde(alias = "lang")]
     lang_code: Option<String>,
+
+    /// Comma separated languages to use when a name is not in the requested language
+    fallback: Option<String>,
+
+    /// Return the words in this script rendition
+    script: Option<String>,
+
+    /// Return the tajweed annotations of the words (format=word),
+    /// can't be used with `script`
+    #[serde(default)]
+    tajweed: bool,
+
+    /// Return the latin transliteration of the ayahs
+    #[serde(default)]
+    transliteration: bool,
 }
 
 /// The query needs the mushaf
 /// for example /surah?mushaf=hafs
 #[derive(Clone, Deserialize)]
 pub struct SurahListQuery {
+    /// Comma separated languages of the names, or `all`
+    #[serde(alias = "lang")]
     lang_code: Option<String>,
+
+    /// Comma separated languages to use when a name is not in the requested language
+    fallback: Option<String>,
+
     mushaf: String,
+
+    /// Only the surahs of this period
+    period: Option<Period>,
+
     sort: Option<String>,
     order: Option<Order>,
 
@@ -179,6 +263,35 @@ pub struct SurahListQuery {
     to: Option<u64>,
 }
 
+impl GetSurahQuery {
+    /// Key of the cached view of the surah
+    pub fn view_key(&self, surah: Uuid) -> ViewKey {
+        ViewKey::new("surah_view")
+            .surah(surah)
+            .format(&self.format)
+            .lang(self.lang_code.as_deref())
+            .option("fallback", &self.fallback)
+            .option("script", &self.script)
+            .option("tajweed", &self.tajweed)
+            .option("transliteration", &self.transliteration)
+    }
+}
+
+impl SurahListQuery {
+    /// Key of the cached list of the surahs
+    pub fn view_key(&self) -> ViewKey {
+        ViewKey::new("surah_list")
+            .mushaf(&self.mushaf)
+            .lang(self.lang_code.as_deref())
+            .option("fallback", &self.fallback)
+            .option("period", &self.period)
+            .option("sort", &self.sort)
+            .option("order", &self.order)
+            .option("from", &self.from)
+            .option("to", &self.to)
+    }
+}
+
 impl Filters for SurahListQuery {
     fn sort(&self) -> Option<String> {
         self.sort.clone()
@@ -199,11 +312,219 @@ impl Filters for SurahListQuery {
 
 #[derive(Serialize, Clone, Debug)]
 pub struct SurahName {
+    /// Requested language
+    pub language: String,
     pub arabic: String,
     pub pronunciation: Option<String>,
     pub translation_phrase: Option<String>,
     pub translation: Option<String>,
     pub transliteration: Option<String>,
+
+    /// Languages the values are found in, a fallback language if the requested one is missing,
+    /// None if the value is not localized
+    pub pronunciation_language: Option<String>,
+    pub translation_language: Option<String>,
+    pub transliteration_language: Option<String>,
+}
+
+/// The response type for /surah/{uuid}/statistics
+#[derive(Serialize)]
+pub struct SurahStatisticsView {
+    pub uuid: Uuid,
+    pub number: i32,
+    pub ayahs: u32,
+    pub words: u32,
+    pub letters: u32,
+    pub ayahs_statistics: Vec<AyahStatistics>,
+}
+
+/// The request body for /surah/name/{uuid}
+#[derive(Deserialize, Validate)]
+pub struct SimpleSurahName {
+    #[validate(length(min = 1, max = 3))]
+    pub language: String,
+    pub pronunciation: Option<String>,
+    pub transliteration: Option<String>,
+}
+
+/// the query for the /surah/name/{uuid}
+#[derive(Deserialize)]
+pub struct SurahNameQuery {
+    pub language: String,
+}
+
+const DEFAULT_LANGUAGE: &str = "en";
+
+/// Requested languages of the surah names
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NameLanguages {
+    /// Every language the surah has a name in
+    All,
+    List(Vec<String>),
+}
+
+/// Splits the comma separated languages
+fn split_languages(languages: &str) -> Vec<String> {
+    let mut result: Vec<String> = vec![];
+
+    for language in languages.split(',').map(str::trim) {
+        if !language.is_empty() && !result.iter().any(|l| l == language) {
+            result.push(language.to_string());
+        }
+    }
+
+    result
+}
+
+impl NameLanguages {
+    pub fn from_query(lang_code: Option<&str>) -> Self {
+        match lang_code.map(str::trim) {
+            Some("all") => Self::All,
+            Some(languages) if !split_languages(languages).is_empty() => {
+                Self::List(split_languages(languages))
+            }
+            _ => Self::List(vec![DEFAULT_LANGUAGE.to_string()]),
+        }
+    }
+}
+
+/// Returns the fallback chain of the languages, english by default
+pub fn fallback_languages(fallback: Option<&str>) -> Vec<String> {
+    match fallback.map(split_languages) {
+        Some(languages) if !languages.is_empty() => languages,
+        _ => vec![DEFAULT_LANGUAGE.to_string()],
+    }
+}
+
+/// Returns the first value found for the language or its fallbacks, with its language
+fn resolve_language<'a, T, F>(
+    values: Option<&'a HashMap<String, T>>,
+    language: &str,
+    fallbacks: &[String],
+    field: F,
+) -> Option<(String, String)>
+where
+    F: Fn(&'a T) -> Option<&'a String>,
+{
+    let values = values?;
+
+    std::iter::once(language)
+        .chain(fallbacks.iter().map(String::as_str))
+        .find_map(|l| {
+            values
+                .get(l)
+                .and_then(&field)
+                .map(|value| (l.to_string(), value.clone()))
+        })
+}
+
+/// Returns the names of the surahs by the surah id, one name per language
+///
+/// Translations are from the name phrase of the surah, pronunciations and
+/// transliterations from the localized names with the surah fields as default
+pub fn surah_names(
+    conn: &mut PgConnection,
+    surahs: &[QuranSurah],
+    languages: &NameLanguages,
+    fallbacks: &[String],
+) -> QueryResult<HashMap<i32, Vec<SurahName>>> {
+    use crate::schema::app_phrase_translations::dsl::{
+        app_phrase_translations, language as p_t_lang, text as p_t_text,
+    };
+    use crate::schema::app_phrases::dsl::{app_phrases, phrase as p_phrase};
+    use crate::schema::quran_surahs_names::dsl::{quran_surahs_names, surah_id};
+
+    let phrases: Vec<&String> = surahs
+        .iter()
+        .filter_map(|s| s.name_translation_phrase.as_ref())
+        .collect();
+
+    // phrase -> language -> text
+    let mut translations: HashMap<String, HashMap<String, String>> = HashMap::new();
+    for (phrase, language, text) in app_phrases
+        .inner_join(app_phrase_translations)
+        .filter(p_phrase.eq_any(phrases))
+        .select((p_phrase, p_t_lang, p_t_text))
+        .get_results::<(String, String, String)>(conn)?
+    {
+        translations
+            .entry(phrase)
+            .or_default()
+            .insert(language, text);
+    }
+
+    // surah -> language -> localized name
+    let mut localized: HashMap<i32, HashMap<String, QuranSurahName>> = HashMap::new();
+    for name in quran_surahs_names
+        .filter(surah_id.eq_any(surahs.iter().map(|s| s.id).collect::<Vec<i32>>()))
+        .get_results::<QuranSurahName>(conn)?
+    {
+        localized
+            .entry(name.surah_id)
+            .or_default()
+            .insert(name.language.clone(), name);
+    }
+
+    let mut result = HashMap::with_capacity(surahs.len());
+
+    for surah in surahs {
+        let surah_translations = surah
+            .name_translation_phrase
+            .as_ref()
+            .and_then(|p| translations.get(p));
+        let surah_localized = localized.get(&surah.id);
+
+        let surah_languages: Vec<String> = match languages {
+            NameLanguages::List(list) => list.clone(),
+            NameLanguages::All => {
+                let all: BTreeSet<&String> = surah_translations
+                    .into_iter()
+                    .flat_map(|t| t.keys())
+                    .chain(surah_localized.into_iter().flat_map(|n| n.keys()))
+                    .collect();
+
+                if all.is_empty() {
+                    fallbacks.iter().take(1).cloned().collect()
+                } else {
+                    all.into_iter().cloned().collect()
+                }
+            }
+        };
+
+        let names = surah_languages
+            .into_iter()
+            .map(|language| {
+                let (translation_language, translation) =
+                    resolve_language(surah_translations, &language, fallbacks, Some).unzip();
+                let (pronunciation_language, pronunciation) =
+                    resolve_language(surah_localized, &language, fallbacks, |n| {
+                        n.pronunciation.as_ref()
+                    })
+                    .unzip();
+                let (transliteration_language, transliteration) =
+                    resolve_language(surah_localized, &language, fallbacks, |n| {
+                        n.transliteration.as_ref()
+                    })
+                    .unzip();
+
+                SurahName {
+                    arabic: surah.name.clone(),
+                    translation,
+                    translation_phrase: surah.name_translation_phrase.clone(),
+                    pronunciation: pronunciation.or_else(|| surah.name_pronunciation.clone()),
+                    transliteration: transliteration.or_else(|| surah.name_transliteration.clone()),
+                    pronunciation_language,
+                    translation_language,
+                    transliteration_language,
+                    language,
+                }
+            })
+            .collect();
+
+        result.insert(surah.id, names);
+    }
+
+    Ok(result)
 }
 
 #[derive(Serialize, Clone, Debug)]
@@ -232,7 +553,9 @@ pub struct SingleSurahResponse {
     pub number: u32,
     pub number_of_ayahs: u32,
     pub names: Vec<SurahName>,
-    pub period: Option<String>,
+    pub period: Option<Period>,
+    pub revelation_order: Option<i32>,
+    pub ruku_count: Option<i32>,
     pub bismillah: Option<AyahBismillahInSurah>,
     pub search_terms: Option<Vec<String>>,
 }
@@ -242,7 +565,9 @@ pub struct SingleSurahResponse {
 pub struct SurahListResponse {
     pub uuid: Uuid,
     pub number: i32,
-    pub period: Option<String>,
+    pub period: Option<Period>,
+    pub revelation_order: Option<i32>,
+    pub ruku_count: Option<i32>,
     pub number_of_ayahs: i64,
     pub names: Vec<SurahName>,
     pub search_terms: Option<Vec<String>>,
@@ -250,14 +575,89 @@ pub struct SurahListResponse {
 
 // TODO: Remove number. number must be generated at api runtime
 /// User request body type
-#[derive(Serialize, Clone, Debug, Deserialize)]
+#[derive(Serialize, Clone, Debug, Deserialize, Validate)]
 pub struct SimpleSurah {
     pub name: String,
     pub name_pronunciation: Option<String>,
     pub name_translation_phrase: Option<String>,
     pub name_transliteration: Option<String>,
+
+    #[validate(custom = "validate_period")]
     pub period: Option<String>,
+
+    #[validate(range(min = 1))]
+    pub revelation_order: Option<i32>,
+
+    #[validate(range(min = 0))]
+    pub ruku_count: Option<i32>,
+
     pub search_terms: Option<Vec<String>>,
     pub number: i32,
     pub mushaf_uuid: Uuid,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn surah_with_period(period: &str) -> SimpleSurah {
+        SimpleSurah {
+            name: "الفاتحة".to_string(),
+            name_pronunciation: None,
+            name_translation_phrase: None,
+            name_transliteration: None,
+            period: Some(period.to_string()),
+            revelation_order: Some(5),
+            ruku_count: Some(1),
+            search_terms: None,
+            number: 1,
+            mushaf_uuid: Uuid::nil(),
+        }
+    }
+
+    #[test]
+    fn test_name_languages() {
+        assert_eq!(NameLanguages::from_query(Some("all")), NameLanguages::All);
+        assert_eq!(
+            NameLanguages::from_query(Some("fa, en,fa")),
+            NameLanguages::List(vec!["fa".to_string(), "en".to_string()])
+        );
+        assert_eq!(
+            NameLanguages::from_query(None),
+            NameLanguages::List(vec!["en".to_string()])
+        );
+        assert_eq!(fallback_languages(Some(",")), vec!["en".to_string()]);
+    }
+
+    #[test]
+    fn test_resolve_language() {
+        let values: HashMap<String, String> = [
+            ("en".to_string(), "The Opening".to_string()),
+            ("fa".to_string(), "گشاینده".to_string()),
+        ]
+        .into();
+        let fallbacks = vec!["tr".to_string(), "en".to_string()];
+
+        assert_eq!(
+            resolve_language(Some(&values), "fa", &fallbacks, Some),
+            Some(("fa".to_string(), "گشاینده".to_string()))
+        );
+        // The value of the fallback is labeled with its own language
+        assert_eq!(
+            resolve_language(Some(&values), "ps", &fallbacks, Some),
+            Some(("en".to_string(), "The Opening".to_string()))
+        );
+        assert_eq!(resolve_language(Some(&values), "ps", &[], Some), None);
+    }
+
+    #[test]
+    fn test_surah_period_validation() {
+        assert!(surah_with_period("meccan").validate().is_ok());
+        assert!(surah_with_period("medinan").validate().is_ok());
+        assert!(surah_with_period("makki").validate().is_err());
+        assert_eq!(
+            Period::from_option_string(Some("medinan".to_string())),
+            Some(Period::Medinan)
+        );
+    }
+}
@@ -1,6 +1,7 @@
 use super::SimpleSurah;
 use crate::error::RouterErrorDetail;
 use crate::models::NewQuranSurah;
+use crate::validate::validate;
 use crate::{error::RouterError, DbPool};
 use actix_web::web;
 use diesel::dsl::exists;
@@ -20,6 +21,8 @@ pub async fn surah_add(
     let new_surah = new_surah.into_inner();
     let data = data.into_inner();
 
+    validate(&new_surah)?;
+
     web::block(move || {
         let mut conn = pool.get().unwrap();
 
@@ -56,7 +59,7 @@ pub async fn surah_add(
 
         let search_terms = new_surah.search_terms.map(|v| {
             v.into_iter()
-                .map(|s| Some(s))
+                .map(Some)
                 .collect::<Vec<Option<String>>>()
         });
 
@@ -71,6 +74,8 @@ pub async fn surah_add(
             name_translation_phrase: new_surah.name_translation_phrase,
             name_transliteration: new_surah.name_transliteration,
             search_terms,
+            revelation_order: new_surah.revelation_order,
+            ruku_count: new_surah.ruku_count,
         }
         .insert_into(quran_surahs)
         .execute(&mut conn)?;
@@ -1,4 +1,5 @@
 use crate::error::RouterError;
+use crate::validate::validate;
 use crate::DbPool;
 use actix_web::web;
 use diesel::prelude::*;
@@ -15,13 +16,15 @@ pub async fn surah_edit(
     use crate::schema::quran_mushafs::dsl::{id as mushaf_id, quran_mushafs, uuid as mushaf_uuid};
     use crate::schema::quran_surahs::dsl::{
         mushaf_id as surah_mushaf_id, name, name_pronunciation, name_translation_phrase,
-        name_transliteration, number, period, quran_surahs, search_terms as surah_search_terms,
-        uuid as surah_uuid,
+        name_transliteration, number, period, quran_surahs, revelation_order, ruku_count,
+        search_terms as surah_search_terms, uuid as surah_uuid,
     };
 
     let new_surah = new_surah.into_inner();
     let target_surah_uuid = path.into_inner();
 
+    validate(&new_surah)?;
+
     web::block(move || {
         let mut conn = pool.get().unwrap();
 
@@ -34,7 +37,7 @@ pub async fn surah_edit(
 
         let search_terms = new_surah.search_terms.map(|v| {
             v.into_iter()
-                .map(|s| Some(s))
+                .map(Some)
                 .collect::<Vec<Option<String>>>()
         });
 
@@ -48,6 +51,8 @@ pub async fn surah_edit(
                 name_translation_phrase.eq(new_surah.name_translation_phrase),
                 name_transliteration.eq(new_surah.name_transliteration),
                 surah_search_terms.eq(search_terms),
+                revelation_order.eq(new_surah.revelation_order),
+                ruku_count.eq(new_surah.ruku_count),
             ))
             .execute(&mut conn)?;
 
@@ -1,33 +1,38 @@
-use super::SurahName;
-use super::{SurahListQuery, SurahListResponse};
+use super::{fallback_languages, surah_names, NameLanguages};
+use super::{Period, SurahListQuery, SurahListResponse};
 use crate::error::RouterErrorDetailBuilder;
 use crate::filter::Filter;
-use crate::models::{QuranAyah, QuranMushaf, QuranSurah};
-use crate::schema::quran_ayahs::surah_id;
+use crate::http_cache::ContentVersion;
+use crate::models::{QuranMushaf, QuranSurah};
+use crate::statistics::surahs_ayah_count;
+use crate::view_cache::ViewCache;
 use crate::{error::RouterError, DbPool};
-use actix_web::{web, HttpRequest};
-use diesel::dsl::count;
+use actix_web::{web, HttpRequest, HttpResponse};
 use diesel::prelude::*;
 
 /// Get the lists of surah
 pub async fn surah_list(
     query: web::Query<SurahListQuery>,
     pool: web::Data<DbPool>,
+    view_cache: web::Data<ViewCache>,
+    version: Option<web::ReqData<ContentVersion>>,
     req: HttpRequest,
-) -> Result<web::Json<Vec<SurahListResponse>>, RouterError> {
-    use crate::schema::app_phrase_translations::dsl::{
-        app_phrase_translations, language as p_t_lang, text as p_t_text,
-    };
-    use crate::schema::app_phrases::dsl::{app_phrases, phrase as p_phrase};
+) -> Result<HttpResponse, RouterError> {
     use crate::schema::quran_mushafs::dsl::{quran_mushafs, short_name as mushaf_name};
     use crate::schema::quran_surahs::dsl::*;
 
     let query = query.into_inner();
     let pool = pool.into_inner();
+    let version = version.map(|version| version.into_inner());
+
+    let view_key = query.view_key();
+    if let Some(cached) = view_cache.cached(version.as_ref(), &view_key) {
+        return Ok(cached);
+    }
 
     let error_detail = RouterErrorDetailBuilder::from_http_request(&req).build();
 
-    web::block(move || {
+    let surahs = web::block(move || {
         let mut conn = pool.get().unwrap();
 
         // Select the specific mushaf
@@ -41,46 +46,32 @@ pub async fn surah_list(
             Err(err) => return Err(err.log_to_db(pool, error_detail)),
         };
 
+        let mut filtered_surahs = filtered_surahs.filter(mushaf_id.eq(mushaf.id));
+
+        if let Some(target_period) = query.period {
+            filtered_surahs = filtered_surahs.filter(period.eq(target_period.to_string()));
+        }
+
         // Get the list of surahs from the database
-        let surahs = filtered_surahs
-            .filter(mushaf_id.eq(mushaf.id))
-            .load::<QuranSurah>(&mut conn)?;
+        let surahs = filtered_surahs.load::<QuranSurah>(&mut conn)?;
 
-        let ayahs = surahs
-            .clone()
-            .into_iter()
-            .map(|s| {
-                QuranAyah::belonging_to(&s)
-                    .select(count(surah_id))
-                    .get_result(&mut conn)
-                    //TODO: remove unwrap
-                    .unwrap()
-            })
-            .collect::<Vec<i64>>();
+        let ayahs_count = surahs_ayah_count(
+            &mut conn,
+            &surahs.iter().map(|s| s.id).collect::<Vec<i32>>(),
+        )?;
+
+        let mut names = surah_names(
+            &mut conn,
+            &surahs,
+            &NameLanguages::from_query(query.lang_code.as_deref()),
+            &fallback_languages(query.fallback.as_deref()),
+        )?;
 
         // now iter over the surahs and bind it with
         // number_of_ayahs
         let surahs = surahs
             .into_iter()
-            .zip(ayahs)
-            .map(|(surah, number_of_ayahs)| {
-                let translation = if let Some(ref phrase) = surah.name_translation_phrase {
-                    let mut p = app_phrases.left_join(app_phrase_translations).into_boxed();
-
-                    if let Some(ref l) = query.lang_code {
-                        p = p.filter(p_t_lang.eq(l));
-                    } else {
-                        p = p.filter(p_t_lang.eq("en"));
-                    }
-
-                    p.filter(p_phrase.eq(phrase))
-                        .select(p_t_text.nullable())
-                        .get_result(&mut conn)
-                        .unwrap()
-                } else {
-                    None
-                };
-
+            .map(|surah| {
                 let surah_search_terms = surah.search_terms.map(|st| {
                     st.into_iter()
                         .map(|s| s.unwrap_or(String::new()))
@@ -89,23 +80,21 @@ pub async fn surah_list(
 
                 SurahListResponse {
                     uuid: surah.uuid,
-                    names: vec![SurahName {
-                        arabic: surah.name,
-                        translation,
-                        translation_phrase: surah.name_translation_phrase,
-                        pronunciation: surah.name_pronunciation,
-                        transliteration: surah.name_transliteration,
-                    }],
+                    names: names.remove(&surah.id).unwrap_or_default(),
                     number: surah.number,
-                    period: surah.period,
-                    number_of_ayahs,
+                    period: Period::from_option_string(surah.period),
+                    revelation_order: surah.revelation_order,
+                    ruku_count: surah.ruku_count,
+                    number_of_ayahs: ayahs_count.get(&surah.id).copied().unwrap_or(0),
                     search_terms: surah_search_terms,
                 }
             })
             .collect::<Vec<SurahListResponse>>();
 
-        Ok(web::Json(surahs))
+        Ok(surahs)
     })
     .await
-    .unwrap()
+    .unwrap()?;
+
+    Ok(view_cache.store(version.as_ref(), view_key, &surahs))
 }
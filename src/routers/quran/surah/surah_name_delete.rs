@@ -0,0 +1,42 @@
+use crate::error::RouterError;
+use crate::DbPool;
+use actix_web::web;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use super::SurahNameQuery;
+
+/// Delete's the localized name of a surah in a language
+pub async fn surah_name_delete(
+    path: web::Path<Uuid>,
+    pool: web::Data<DbPool>,
+    query: web::Query<SurahNameQuery>,
+) -> Result<&'static str, RouterError> {
+    use crate::schema::quran_surahs::dsl::{id as surah_id, quran_surahs, uuid as surah_uuid};
+    use crate::schema::quran_surahs_names::dsl::{
+        language, quran_surahs_names, surah_id as name_surah_id,
+    };
+
+    let target_surah_uuid = path.into_inner();
+    let query = query.into_inner();
+
+    web::block(move || {
+        let mut conn = pool.get().unwrap();
+
+        let target_surah: i32 = quran_surahs
+            .filter(surah_uuid.eq(target_surah_uuid))
+            .select(surah_id)
+            .get_result(&mut conn)?;
+
+        diesel::delete(
+            quran_surahs_names
+                .filter(name_surah_id.eq(target_surah))
+                .filter(language.eq(query.language)),
+        )
+        .execute(&mut conn)?;
+
+        Ok("Deleted")
+    })
+    .await
+    .unwrap()
+}
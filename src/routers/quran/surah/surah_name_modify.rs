@@ -0,0 +1,67 @@
+use crate::error::RouterError;
+use crate::models::NewQuranSurahName;
+use crate::validate::validate;
+use crate::DbPool;
+use actix_web::web;
+use diesel::prelude::*;
+use diesel::upsert::excluded;
+use uuid::Uuid;
+
+use super::SimpleSurahName;
+
+/// Modify the localized name of a surah,
+///
+/// If the surah has a name in the language update it,
+/// otherwise add.
+pub async fn surah_name_modify(
+    path: web::Path<Uuid>,
+    new_name: web::Json<SimpleSurahName>,
+    pool: web::Data<DbPool>,
+    data: web::ReqData<u32>,
+) -> Result<&'static str, RouterError> {
+    use crate::schema::app_users::dsl::{account_id as user_acc_id, app_users, id as user_id};
+    use crate::schema::quran_surahs::dsl::{id as surah_id, quran_surahs, uuid as surah_uuid};
+    use crate::schema::quran_surahs_names::dsl::{
+        language, pronunciation, quran_surahs_names, surah_id as name_surah_id, transliteration,
+    };
+
+    let target_surah_uuid = path.into_inner();
+    let new_name = new_name.into_inner();
+    let data = data.into_inner();
+
+    validate(&new_name)?;
+
+    web::block(move || {
+        let mut conn = pool.get().unwrap();
+
+        let user: i32 = app_users
+            .filter(user_acc_id.eq(data as i32))
+            .select(user_id)
+            .get_result(&mut conn)?;
+
+        let target_surah: i32 = quran_surahs
+            .filter(surah_uuid.eq(target_surah_uuid))
+            .select(surah_id)
+            .get_result(&mut conn)?;
+
+        NewQuranSurahName {
+            creator_user_id: user,
+            surah_id: target_surah,
+            language: &new_name.language,
+            pronunciation: new_name.pronunciation.as_deref(),
+            transliteration: new_name.transliteration.as_deref(),
+        }
+        .insert_into(quran_surahs_names)
+        .on_conflict((name_surah_id, language))
+        .do_update()
+        .set((
+            pronunciation.eq(excluded(pronunciation)),
+            transliteration.eq(excluded(transliteration)),
+        ))
+        .execute(&mut conn)?;
+
+        Ok("Modified")
+    })
+    .await
+    .unwrap()
+}
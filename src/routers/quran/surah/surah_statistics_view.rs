@@ -0,0 +1,52 @@
+use crate::error::RouterError;
+use crate::http_cache::ContentVersion;
+use crate::models::QuranSurah;
+use crate::statistics::{MushafStatistics, StatisticsCache};
+use crate::verse_key::mushaf_ayah_words;
+use crate::DbPool;
+use actix_web::web;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use super::SurahStatisticsView;
+
+/// Returns the word and letter statistics of a surah and its ayahs
+pub async fn surah_statistics_view(
+    path: web::Path<Uuid>,
+    pool: web::Data<DbPool>,
+    cache: web::Data<StatisticsCache>,
+    version: Option<web::ReqData<ContentVersion>>,
+) -> Result<web::Json<SurahStatisticsView>, RouterError> {
+    use crate::schema::quran_surahs::dsl::{quran_surahs, uuid as surah_uuid};
+
+    let target_surah_uuid = path.into_inner();
+    let version = version.map(|version| version.into_inner().0);
+
+    web::block(move || {
+        let mut conn = pool.get().unwrap();
+
+        let surah: QuranSurah = quran_surahs
+            .filter(surah_uuid.eq(target_surah_uuid))
+            .get_result(&mut conn)?;
+
+        let statistics = cache.get_or_compute(surah.mushaf_id, version, || {
+            Ok::<_, RouterError>(MushafStatistics::compute(&mushaf_ayah_words(
+                &mut conn,
+                surah.mushaf_id,
+            )?))
+        })?;
+
+        let ayahs_statistics = statistics.surah_ayahs(surah.number);
+
+        Ok(web::Json(SurahStatisticsView {
+            uuid: surah.uuid,
+            number: surah.number,
+            ayahs: ayahs_statistics.len() as u32,
+            words: ayahs_statistics.iter().map(|ayah| ayah.words).sum(),
+            letters: ayahs_statistics.iter().map(|ayah| ayah.letters).sum(),
+            ayahs_statistics,
+        }))
+    })
+    .await
+    .unwrap()
+}
@@ -1,16 +1,21 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use super::{
-    AyahWord, Format, GetSurahQuery, QuranResponseData, SimpleAyah, SingleSurahResponse, SurahName,
+    fallback_languages, surah_names, AyahWord, Format, GetSurahQuery, NameLanguages, Period,
+    QuranResponseData, SimpleAyah, SingleSurahResponse,
 };
+use crate::http_cache::ContentVersion;
 use crate::models::{
     QuranAyah, QuranAyahBreaker, QuranMushaf, QuranSurah, QuranWord, QuranWordBreaker,
 };
 use crate::routers::multip;
-use crate::routers::quran::word::WordBreaker;
+use crate::routers::quran::word::{
+    apply_script, words_tajweed, words_transliteration, WordBreaker,
+};
+use crate::view_cache::ViewCache;
 use crate::{error::RouterError, DbPool};
 use crate::{AyahBismillah, AyahTy, Breaker, SingleSurahMushaf};
-use actix_web::web;
+use actix_web::{web, HttpResponse};
 use diesel::prelude::*;
 use uuid::Uuid;
 
@@ -19,11 +24,9 @@ pub async fn surah_view(
     path: web::Path<Uuid>,
     query: web::Query<GetSurahQuery>,
     pool: web::Data<DbPool>,
-) -> Result<web::Json<QuranResponseData>, RouterError> {
-    use crate::schema::app_phrase_translations::dsl::{
-        app_phrase_translations, language as p_t_lang, text as p_t_text,
-    };
-    use crate::schema::app_phrases::dsl::{app_phrases, phrase as p_phrase};
+    view_cache: web::Data<ViewCache>,
+    version: Option<web::ReqData<ContentVersion>>,
+) -> Result<HttpResponse, RouterError> {
     use crate::schema::quran_ayahs::dsl::quran_ayahs;
     use crate::schema::quran_ayahs_breakers::dsl::quran_ayahs_breakers;
     use crate::schema::quran_mushafs::dsl::{id as mushaf_id, quran_mushafs};
@@ -34,19 +37,29 @@ pub async fn surah_view(
 
     let query = query.into_inner();
     let requested_surah_uuid = path.into_inner();
+    let version = version.map(|version| version.into_inner());
+
+    // Annotations are on the mushaf text, their indexes don't match the other scripts
+    if query.tajweed && query.script.is_some() {
+        return Err(RouterError::from_predefined("TAJWEED_SCRIPT_NOT_SUPPORTED"));
+    }
+
+    let view_key = query.view_key(requested_surah_uuid);
+    if let Some(cached) = view_cache.cached(version.as_ref(), &view_key) {
+        return Ok(cached);
+    }
 
-    web::block(move || {
+    let surah = web::block(move || {
         let mut conn = pool.get().unwrap();
 
         // [{ayah_id, name}...]
         // Also need to count name
         let breakers: Vec<QuranAyahBreaker> = quran_ayahs_breakers.get_results(&mut conn)?;
-        let mut breakers = breakers.into_iter();
 
         let mut breakers_count: HashMap<String, u32> = HashMap::new();
         let mut map = HashMap::<i32, Vec<Breaker>>::new();
 
-        while let Some(breaker) = breakers.next() {
+        for breaker in breakers {
             breakers_count
                 .entry(breaker.name)
                 .and_modify(|v| *v += 1)
@@ -61,7 +74,7 @@ pub async fn surah_view(
             map.entry(breaker.ayah_id).insert_entry(val);
         }
 
-        let ayahs_words = quran_surahs
+        let mut ayahs_words = quran_surahs
             .filter(surah_uuid.eq(requested_surah_uuid))
             .inner_join(
                 quran_ayahs
@@ -71,6 +84,30 @@ pub async fn surah_view(
             .select((QuranAyah::as_select(), QuranWord::as_select()))
             .load::<(QuranAyah, QuranWord)>(&mut conn)?;
 
+        // Transliterated from the mushaf text, before applying the script
+        let mut words_transliteration = if query.transliteration {
+            let mut ayahs: BTreeMap<i32, Vec<&QuranWord>> = BTreeMap::new();
+            for (ayah, word) in &ayahs_words {
+                ayahs.entry(ayah.id).or_default().push(word);
+            }
+
+            for words in ayahs.values_mut() {
+                words.sort_unstable_by_key(|w| w.id);
+            }
+
+            Some(words_transliteration(&mut conn, ayahs.into_values())?)
+        } else {
+            None
+        };
+
+        if let Some(ref target_script) = query.script {
+            apply_script(
+                &mut conn,
+                target_script,
+                ayahs_words.iter_mut().map(|(_, w)| w),
+            )?;
+        }
+
         let ayahs_words = ayahs_words
             .into_iter()
             .map(|(ayah, word)| {
@@ -82,7 +119,7 @@ pub async fn surah_view(
                             ayah.is_bismillah,
                             ayah.bismillah_text,
                         ),
-                        breakers: map.get(&ayah.id).clone().cloned(),
+                        breakers: map.get(&ayah.id).cloned(),
                         number: ayah.ayah_number as u32,
                         sajdah: ayah.sajdah,
                     },
@@ -95,11 +132,10 @@ pub async fn surah_view(
 
         let words_breakers = if matches!(query.format, Format::Word) {
             let breakers: Vec<QuranWordBreaker> = quran_words_breakers.get_results(&mut conn)?;
-            let mut breakers = breakers.into_iter();
             // (i32)
             let mut collected_breakers: HashMap<i32, Vec<WordBreaker>> = HashMap::new();
 
-            while let Some(breaker) = breakers.next() {
+            for breaker in breakers {
                 collected_breakers
                     .entry(breaker.word_id)
                     .and_modify(|v| {
@@ -115,11 +151,30 @@ pub async fn surah_view(
             None
         };
 
+        let mut words_tajweed = if matches!(query.format, Format::Word) && query.tajweed {
+            Some(words_tajweed(
+                &mut conn,
+                ayahs_as_map
+                    .values()
+                    .flat_map(|words| words.iter().map(|w| w.id))
+                    .collect(),
+            )?)
+        } else {
+            None
+        };
+
         let final_ayahs = ayahs_as_map
             .into_iter()
             .map(|(ayah, words)| match query.format {
                 Format::Text => AyahTy::Text(crate::AyahWithText {
                     ayah,
+                    transliteration: words_transliteration.as_mut().map(|t| {
+                        words
+                            .iter()
+                            .filter_map(|w| t.remove(&w.id))
+                            .collect::<Vec<String>>()
+                            .join(" ")
+                    }),
                     text: words
                         .into_iter()
                         .map(|w| w.word)
@@ -131,7 +186,13 @@ pub async fn surah_view(
                     words: words
                         .into_iter()
                         .map(|w| AyahWord {
-                            breakers: words_breakers.clone().unwrap().get(&w.id).clone().cloned(),
+                            breakers: words_breakers.clone().unwrap().get(&w.id).cloned(),
+                            tajweed: words_tajweed
+                                .as_mut()
+                                .map(|t| t.remove(&w.id).unwrap_or_default()),
+                            transliteration: words_transliteration
+                                .as_mut()
+                                .and_then(|t| t.remove(&w.id)),
                             word: w.word,
                         })
                         .collect(),
@@ -149,46 +210,38 @@ pub async fn surah_view(
             .filter(mushaf_id.eq(surah.mushaf_id))
             .get_result::<QuranMushaf>(&mut conn)?;
 
-        let translation = if let Some(ref phrase) = surah.name_translation_phrase {
-            let mut p = app_phrases.left_join(app_phrase_translations).into_boxed();
+        let names = surah_names(
+            &mut conn,
+            std::slice::from_ref(&surah),
+            &NameLanguages::from_query(query.lang_code.as_deref()),
+            &fallback_languages(query.fallback.as_deref()),
+        )?
+        .remove(&surah.id)
+        .unwrap_or_default();
 
-            if let Some(ref l) = query.lang_code {
-                p = p.filter(p_t_lang.eq(l));
-            } else {
-                p = p.filter(p_t_lang.eq("en"));
-            }
-
-            p.filter(p_phrase.eq(phrase))
-                .select(p_t_text.nullable())
-                .get_result(&mut conn)?
-        } else {
-            None
-        };
         let surah_search_terms = surah.search_terms.map(|st| {
             st.into_iter()
                 .map(|s| s.unwrap_or(String::new()))
                 .collect::<Vec<String>>()
         });
 
-        Ok(web::Json(QuranResponseData {
+        Ok::<_, RouterError>(QuranResponseData {
             surah: SingleSurahResponse {
                 mushaf: SingleSurahMushaf::from(mushaf),
                 bismillah: final_ayahs.first().unwrap().format_bismillah_for_surah(),
-                names: vec![SurahName {
-                    arabic: surah.name,
-                    translation,
-                    translation_phrase: surah.name_translation_phrase,
-                    pronunciation: surah.name_pronunciation,
-                    transliteration: surah.name_transliteration,
-                }],
-                period: surah.period,
+                names,
+                period: Period::from_option_string(surah.period),
+                revelation_order: surah.revelation_order,
+                ruku_count: surah.ruku_count,
                 number: surah.number as u32,
                 number_of_ayahs: final_ayahs.len() as u32,
                 search_terms: surah_search_terms,
             },
             ayahs: final_ayahs,
-        }))
+        })
     })
     .await
-    .unwrap()
+    .unwrap()?;
+
+    Ok(view_cache.store(version.as_ref(), view_key, &surah))
 }
@@ -1,9 +1,22 @@
 pub mod word_add;
 pub mod word_delete;
 pub mod word_edit;
+pub mod word_script_delete;
+pub mod word_script_modify;
+pub mod word_transliteration_delete;
+pub mod word_transliteration_modify;
 pub mod word_view;
 
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Display;
+use std::str::FromStr;
+
+use diesel::prelude::*;
 use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::models::QuranWord;
+use crate::transliteration::transliterate_ayah;
 
 #[derive(Deserialize)]
 pub struct SimpleWord {
@@ -14,3 +27,381 @@ pub struct SimpleWord {
 pub struct WordBreaker {
     pub name: String,
 }
+
+/// Text of a word in another script
+#[derive(Deserialize, Serialize, Validate)]
+pub struct SimpleWordRendition {
+    #[validate(length(min = 1, max = 50))]
+    pub script: String,
+    pub text: String,
+}
+
+/// Curated transliteration of a word
+#[derive(Deserialize, Validate)]
+pub struct SimpleWordTransliteration {
+    #[validate(length(min = 1))]
+    pub text: String,
+}
+
+/// the query for the /word/{uuid}
+#[derive(Deserialize)]
+pub struct WordViewQuery {
+    /// Return the latin transliteration of the word
+    #[serde(default)]
+    pub transliteration: bool,
+}
+
+/// the query for the /word/script/{uuid}
+/// example /word/script/{uuid}?script=imlaei
+#[derive(Deserialize)]
+pub struct WordScriptQuery {
+    pub script: String,
+}
+
+/// The response type for /word/{uuid}
+#[derive(Serialize)]
+pub struct WordWithRenditions {
+    #[serde(flatten)]
+    pub word: QuranWord,
+
+    /// script name -> text
+    pub renditions: BTreeMap<String, String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transliteration: Option<String>,
+}
+
+/// Replaces the text of the words with their rendition in the `target_script`
+///
+/// Words without a rendition in that script keep their own text
+pub fn apply_script<'a, I>(
+    conn: &mut PgConnection,
+    target_script: &str,
+    words: I,
+) -> QueryResult<()>
+where
+    I: IntoIterator<Item = &'a mut QuranWord>,
+{
+    use crate::schema::quran_words_renditions::dsl::{
+        quran_words_renditions, script, text, word_id,
+    };
+
+    let mut words: Vec<&mut QuranWord> = words.into_iter().collect();
+    let ids: Vec<i32> = words.iter().map(|w| w.id).collect();
+
+    let mut renditions: HashMap<i32, String> = quran_words_renditions
+        .filter(script.eq(target_script))
+        .filter(word_id.eq_any(ids))
+        .select((word_id, text))
+        .get_results::<(i32, String)>(conn)?
+        .into_iter()
+        .collect();
+
+    for word in words.iter_mut() {
+        if let Some(rendition) = renditions.remove(&word.id) {
+            word.word = rendition;
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the latin transliteration of the words by their id
+///
+/// Every item of `ayahs` is the words of an ayah in order, words with a
+/// curated transliteration get that instead of the generated one
+pub fn words_transliteration<'a, I, W>(
+    conn: &mut PgConnection,
+    ayahs: I,
+) -> QueryResult<HashMap<i32, String>>
+where
+    I: IntoIterator<Item = W>,
+    W: IntoIterator<Item = &'a QuranWord>,
+{
+    use crate::schema::quran_words_transliterations::dsl::{
+        quran_words_transliterations, text, word_id,
+    };
+
+    let mut result = HashMap::new();
+
+    for words in ayahs {
+        let words: Vec<&QuranWord> = words.into_iter().collect();
+        let transliterated = transliterate_ayah(words.iter().map(|w| w.word.as_str()));
+
+        result.extend(words.iter().map(|w| w.id).zip(transliterated));
+    }
+
+    let overrides: Vec<(i32, String)> = quran_words_transliterations
+        .filter(word_id.eq_any(result.keys().copied().collect::<Vec<i32>>()))
+        .select((word_id, text))
+        .get_results(conn)?;
+
+    result.extend(overrides);
+
+    Ok(result)
+}
+
+/// Tajweed rules, names are the same as the common tajweed annotation files
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TajweedRule {
+    Ghunnah,
+    HamzatWasl,
+    LamShamsiyyah,
+    Silent,
+    Ikhfa,
+    IkhfaShafawi,
+    Idghaam,
+    IdghaamGhunnah,
+    IdghaamNoGhunnah,
+    IdghaamMutajanisayn,
+    IdghaamMutaqaribayn,
+    IdghaamShafawi,
+    Iqlab,
+    Qalqalah,
+    #[serde(rename = "madd_2")]
+    Madd2,
+    #[serde(rename = "madd_246")]
+    Madd246,
+    #[serde(rename = "madd_6")]
+    Madd6,
+    MaddMuttasil,
+    MaddMunfasil,
+}
+
+impl TajweedRule {
+    pub const ALL: [Self; 19] = [
+        Self::Ghunnah,
+        Self::HamzatWasl,
+        Self::LamShamsiyyah,
+        Self::Silent,
+        Self::Ikhfa,
+        Self::IkhfaShafawi,
+        Self::Idghaam,
+        Self::IdghaamGhunnah,
+        Self::IdghaamNoGhunnah,
+        Self::IdghaamMutajanisayn,
+        Self::IdghaamMutaqaribayn,
+        Self::IdghaamShafawi,
+        Self::Iqlab,
+        Self::Qalqalah,
+        Self::Madd2,
+        Self::Madd246,
+        Self::Madd6,
+        Self::MaddMuttasil,
+        Self::MaddMunfasil,
+    ];
+}
+
+impl Display for TajweedRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Ghunnah => "ghunnah",
+            Self::HamzatWasl => "hamzat_wasl",
+            Self::LamShamsiyyah => "lam_shamsiyyah",
+            Self::Silent => "silent",
+            Self::Ikhfa => "ikhfa",
+            Self::IkhfaShafawi => "ikhfa_shafawi",
+            Self::Idghaam => "idghaam",
+            Self::IdghaamGhunnah => "idghaam_ghunnah",
+            Self::IdghaamNoGhunnah => "idghaam_no_ghunnah",
+            Self::IdghaamMutajanisayn => "idghaam_mutajanisayn",
+            Self::IdghaamMutaqaribayn => "idghaam_mutaqaribayn",
+            Self::IdghaamShafawi => "idghaam_shafawi",
+            Self::Iqlab => "iqlab",
+            Self::Qalqalah => "qalqalah",
+            Self::Madd2 => "madd_2",
+            Self::Madd246 => "madd_246",
+            Self::Madd6 => "madd_6",
+            Self::MaddMuttasil => "madd_muttasil",
+            Self::MaddMunfasil => "madd_munfasil",
+        };
+
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for TajweedRule {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|rule| rule.to_string() == s)
+            .ok_or(())
+    }
+}
+
+/// A tajweed rule on a character range of the text
+///
+/// `start` and `end` (exclusive) are character offsets
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct TajweedAnnotation {
+    pub rule: TajweedRule,
+    pub start: i32,
+    pub end: i32,
+}
+
+/// Splits the annotations of an ayah into the annotations of its words
+///
+/// Offsets of the ayah annotations are in the ayah text (words joined
+/// with a single space). Returns the word index with the annotation,
+/// or None if an annotation is out of the ayah text
+pub fn split_tajweed(
+    words: &[String],
+    annotations: &[TajweedAnnotation],
+) -> Option<Vec<(usize, TajweedAnnotation)>> {
+    let mut ranges = vec![];
+    let mut offset = 0;
+
+    for word in words {
+        let length = word.chars().count() as i32;
+
+        ranges.push((offset, offset + length));
+        offset += length + 1;
+    }
+
+    let text_length = ranges.last().map(|(_, end)| *end).unwrap_or(0);
+    let mut result = vec![];
+
+    for annotation in annotations {
+        if annotation.start < 0
+            || annotation.end <= annotation.start
+            || annotation.end > text_length
+        {
+            return None;
+        }
+
+        for (index, (word_start, word_end)) in ranges.iter().enumerate() {
+            let start = annotation.start.max(*word_start);
+            let end = annotation.end.min(*word_end);
+
+            if start < end {
+                result.push((
+                    index,
+                    TajweedAnnotation {
+                        rule: annotation.rule,
+                        start: start - word_start,
+                        end: end - word_start,
+                    },
+                ));
+            }
+        }
+    }
+
+    Some(result)
+}
+
+/// Returns the tajweed annotations of the words by word id
+///
+/// Annotations are on the word's own text, not on its renditions
+pub fn words_tajweed(
+    conn: &mut PgConnection,
+    ids: Vec<i32>,
+) -> QueryResult<HashMap<i32, Vec<TajweedAnnotation>>> {
+    use crate::schema::quran_words_tajweed::dsl::{
+        end_index, quran_words_tajweed, rule, start_index, word_id,
+    };
+
+    let annotations: Vec<(i32, String, i32, i32)> = quran_words_tajweed
+        .filter(word_id.eq_any(ids))
+        .order((word_id.asc(), start_index.asc()))
+        .select((word_id, rule, start_index, end_index))
+        .get_results(conn)?;
+
+    let mut result: HashMap<i32, Vec<TajweedAnnotation>> = HashMap::new();
+
+    for (id, rule_name, start, end) in annotations {
+        // Rules are validated on import
+        let Ok(rule_name) = rule_name.parse() else {
+            continue;
+        };
+
+        result.entry(id).or_default().push(TajweedAnnotation {
+            rule: rule_name,
+            start,
+            end,
+        });
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(text: &str) -> Vec<String> {
+        text.split(' ').map(|w| w.to_string()).collect()
+    }
+
+    #[test]
+    fn test_tajweed_rule_names() {
+        for rule in TajweedRule::ALL {
+            assert_eq!(rule.to_string().parse::<TajweedRule>(), Ok(rule));
+            assert_eq!(
+                serde_json::to_string(&rule).unwrap(),
+                format!("\"{}\"", rule)
+            );
+        }
+    }
+
+    #[test]
+    fn test_split_tajweed() {
+        let annotations = vec![
+            TajweedAnnotation {
+                rule: TajweedRule::Ghunnah,
+                start: 1,
+                end: 3,
+            },
+            TajweedAnnotation {
+                rule: TajweedRule::Ikhfa,
+                start: 2,
+                end: 6,
+            },
+        ];
+
+        let expected = vec![
+            (
+                0,
+                TajweedAnnotation {
+                    rule: TajweedRule::Ghunnah,
+                    start: 1,
+                    end: 3,
+                },
+            ),
+            (
+                0,
+                TajweedAnnotation {
+                    rule: TajweedRule::Ikhfa,
+                    start: 2,
+                    end: 3,
+                },
+            ),
+            (
+                1,
+                TajweedAnnotation {
+                    rule: TajweedRule::Ikhfa,
+                    start: 0,
+                    end: 2,
+                },
+            ),
+        ];
+
+        assert_eq!(
+            split_tajweed(&words("abc defg"), &annotations),
+            Some(expected)
+        );
+    }
+
+    #[test]
+    fn test_split_tajweed_out_of_text() {
+        let annotations = vec![TajweedAnnotation {
+            rule: TajweedRule::Qalqalah,
+            start: 3,
+            end: 9,
+        }];
+
+        assert_eq!(split_tajweed(&words("abc defg"), &annotations), None);
+    }
+}
@@ -4,8 +4,10 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::{
+    checksum::MushafReference,
     error::RouterError,
     models::NewQuranWord,
+    verse_key::VerseKey,
     DbPool,
 };
 
@@ -17,32 +19,67 @@ pub struct ReqWord {
 
 pub async fn word_add(
     pool: web::Data<DbPool>,
-    user_id: web::ReqData<u32>,
+    data: web::ReqData<u32>,
     new_word: web::Json<ReqWord>,
 ) -> Result<&'static str, RouterError> {
-    use crate::schema::quran_ayahs::dsl::{id as ayah_id, quran_ayahs, uuid as ayah_uid};
-    use crate::schema::quran_words::dsl::quran_words;
+    use crate::schema::app_users::dsl::{account_id as user_acc_id, app_users, id as user_id};
+    use crate::schema::quran_ayahs::dsl::{
+        ayah_number, id as ayah_id, quran_ayahs, uuid as ayah_uid,
+    };
+    use crate::schema::quran_surahs::dsl::{mushaf_id, number as surah_number, quran_surahs};
+    use crate::schema::quran_words::dsl::{
+        ayah_id as word_ayah_id, id as word_id, quran_words, word,
+    };
 
-    let user_id = user_id.into_inner();
+    let user_account_id = data.into_inner();
     let new_word = new_word.into_inner();
 
     web::block(move || {
         let mut conn = pool.get().unwrap();
 
-        let target_ayah_id: i32 = quran_ayahs
-            .filter(ayah_uid.eq(new_word.ayah_uuid))
-            .select(ayah_id)
-            .get_result(&mut conn)?;
+        conn.transaction(|conn| {
+            // Creator user_id
+            let user: i32 = app_users
+                .filter(user_acc_id.eq(user_account_id as i32))
+                .select(user_id)
+                .get_result(conn)?;
 
-        NewQuranWord {
-            ayah_id: target_ayah_id,
-            word: new_word.word.as_str(),
-            creator_user_id: user_id as i32,
-        }
-        .insert_into(quran_words)
-        .execute(&mut conn)?;
+            let (target_ayah_id, key, target_mushaf): (i32, (i32, i32), i32) = quran_ayahs
+                .inner_join(quran_surahs)
+                .filter(ayah_uid.eq(new_word.ayah_uuid))
+                .select((ayah_id, (surah_number, ayah_number), mushaf_id))
+                .get_result(conn)?;
 
-        Ok("added")
+            let old_words: Vec<String> = quran_words
+                .filter(word_ayah_id.eq(target_ayah_id))
+                .order(word_id.asc())
+                .select(word)
+                .get_results(conn)?;
+
+            NewQuranWord {
+                ayah_id: target_ayah_id,
+                word: new_word.word.as_str(),
+                creator_user_id: user,
+            }
+            .insert_into(quran_words)
+            .execute(conn)?;
+
+            // The word is appended to the reference of the ayah
+            let mut new_words = old_words.clone();
+            new_words.push(new_word.word.clone());
+
+            MushafReference::new(target_mushaf, user).change_ayah(
+                conn,
+                VerseKey {
+                    surah: key.0,
+                    ayah: key.1,
+                },
+                &old_words,
+                &new_words,
+            )?;
+
+            Ok("added")
+        })
     })
     .await
     .unwrap()
@@ -0,0 +1,42 @@
+use crate::error::RouterError;
+use crate::DbPool;
+use ::uuid::Uuid;
+use actix_web::web;
+use diesel::prelude::*;
+
+use super::WordScriptQuery;
+
+/// Delete's the rendition of a word in a script
+pub async fn word_script_delete(
+    path: web::Path<Uuid>,
+    pool: web::Data<DbPool>,
+    query: web::Query<WordScriptQuery>,
+) -> Result<&'static str, RouterError> {
+    use crate::schema::quran_words::dsl::{id as word_id, quran_words, uuid as word_uuid};
+    use crate::schema::quran_words_renditions::dsl::{
+        quran_words_renditions, script, word_id as rendition_word_id,
+    };
+
+    let target_word_uuid = path.into_inner();
+    let query = query.into_inner();
+
+    web::block(move || {
+        let mut conn = pool.get().unwrap();
+
+        let target_word: i32 = quran_words
+            .filter(word_uuid.eq(target_word_uuid))
+            .select(word_id)
+            .get_result(&mut conn)?;
+
+        diesel::delete(
+            quran_words_renditions
+                .filter(rendition_word_id.eq(target_word))
+                .filter(script.eq(query.script)),
+        )
+        .execute(&mut conn)?;
+
+        Ok("Deleted")
+    })
+    .await
+    .unwrap()
+}
@@ -0,0 +1,63 @@
+use crate::error::RouterError;
+use crate::models::NewQuranWordRendition;
+use crate::validate::validate;
+use crate::DbPool;
+use actix_web::web;
+use diesel::prelude::*;
+use diesel::upsert::excluded;
+use uuid::Uuid;
+
+use super::SimpleWordRendition;
+
+/// Modify the text of a word in another script,
+///
+/// If the word has a rendition in the script update it,
+/// otherwise add.
+pub async fn word_script_modify(
+    path: web::Path<Uuid>,
+    new_rendition: web::Json<SimpleWordRendition>,
+    pool: web::Data<DbPool>,
+    data: web::ReqData<u32>,
+) -> Result<&'static str, RouterError> {
+    use crate::schema::app_users::dsl::{account_id as user_acc_id, app_users, id as user_id};
+    use crate::schema::quran_words::dsl::{id as word_id, quran_words, uuid as word_uuid};
+    use crate::schema::quran_words_renditions::dsl::{
+        quran_words_renditions, script, text, word_id as rendition_word_id,
+    };
+
+    let target_word_uuid = path.into_inner();
+    let new_rendition = new_rendition.into_inner();
+    let data = data.into_inner();
+
+    validate(&new_rendition)?;
+
+    web::block(move || {
+        let mut conn = pool.get().unwrap();
+
+        let user: i32 = app_users
+            .filter(user_acc_id.eq(data as i32))
+            .select(user_id)
+            .get_result(&mut conn)?;
+
+        let target_word: i32 = quran_words
+            .filter(word_uuid.eq(target_word_uuid))
+            .select(word_id)
+            .get_result(&mut conn)?;
+
+        NewQuranWordRendition {
+            creator_user_id: user,
+            word_id: target_word,
+            script: &new_rendition.script,
+            text: &new_rendition.text,
+        }
+        .insert_into(quran_words_renditions)
+        .on_conflict((rendition_word_id, script))
+        .do_update()
+        .set(text.eq(excluded(text)))
+        .execute(&mut conn)?;
+
+        Ok("Modified")
+    })
+    .await
+    .unwrap()
+}
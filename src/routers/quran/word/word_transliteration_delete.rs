@@ -0,0 +1,37 @@
+use crate::error::RouterError;
+use crate::DbPool;
+use ::uuid::Uuid;
+use actix_web::web;
+use diesel::prelude::*;
+
+/// Delete's the curated transliteration of a word,
+/// the generated one will be returned after that
+pub async fn word_transliteration_delete(
+    path: web::Path<Uuid>,
+    pool: web::Data<DbPool>,
+) -> Result<&'static str, RouterError> {
+    use crate::schema::quran_words::dsl::{id as word_id, quran_words, uuid as word_uuid};
+    use crate::schema::quran_words_transliterations::dsl::{
+        quran_words_transliterations, word_id as transliteration_word_id,
+    };
+
+    let target_word_uuid = path.into_inner();
+
+    web::block(move || {
+        let mut conn = pool.get().unwrap();
+
+        let target_word: i32 = quran_words
+            .filter(word_uuid.eq(target_word_uuid))
+            .select(word_id)
+            .get_result(&mut conn)?;
+
+        diesel::delete(
+            quran_words_transliterations.filter(transliteration_word_id.eq(target_word)),
+        )
+        .execute(&mut conn)?;
+
+        Ok("Deleted")
+    })
+    .await
+    .unwrap()
+}
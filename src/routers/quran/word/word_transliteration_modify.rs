@@ -0,0 +1,62 @@
+use crate::error::RouterError;
+use crate::models::NewQuranWordTransliteration;
+use crate::validate::validate;
+use crate::DbPool;
+use actix_web::web;
+use diesel::prelude::*;
+use diesel::upsert::excluded;
+use uuid::Uuid;
+
+use super::SimpleWordTransliteration;
+
+/// Modify the curated transliteration of a word,
+///
+/// If the word has a transliteration update it,
+/// otherwise add.
+pub async fn word_transliteration_modify(
+    path: web::Path<Uuid>,
+    new_transliteration: web::Json<SimpleWordTransliteration>,
+    pool: web::Data<DbPool>,
+    data: web::ReqData<u32>,
+) -> Result<&'static str, RouterError> {
+    use crate::schema::app_users::dsl::{account_id as user_acc_id, app_users, id as user_id};
+    use crate::schema::quran_words::dsl::{id as word_id, quran_words, uuid as word_uuid};
+    use crate::schema::quran_words_transliterations::dsl::{
+        quran_words_transliterations, text, word_id as transliteration_word_id,
+    };
+
+    let target_word_uuid = path.into_inner();
+    let new_transliteration = new_transliteration.into_inner();
+    let data = data.into_inner();
+
+    validate(&new_transliteration)?;
+
+    web::block(move || {
+        let mut conn = pool.get().unwrap();
+
+        let user: i32 = app_users
+            .filter(user_acc_id.eq(data as i32))
+            .select(user_id)
+            .get_result(&mut conn)?;
+
+        let target_word: i32 = quran_words
+            .filter(word_uuid.eq(target_word_uuid))
+            .select(word_id)
+            .get_result(&mut conn)?;
+
+        NewQuranWordTransliteration {
+            creator_user_id: user,
+            word_id: target_word,
+            text: &new_transliteration.text,
+        }
+        .insert_into(quran_words_transliterations)
+        .on_conflict(transliteration_word_id)
+        .do_update()
+        .set(text.eq(excluded(text)))
+        .execute(&mut conn)?;
+
+        Ok("Modified")
+    })
+    .await
+    .unwrap()
+}
@@ -1,18 +1,22 @@
 use crate::error::RouterError;
-use crate::models::QuranWord;
+use crate::models::{QuranWord, QuranWordRendition};
 use crate::DbPool;
 use ::uuid::Uuid;
 use actix_web::web;
 use diesel::prelude::*;
 
+use super::{words_transliteration, WordViewQuery, WordWithRenditions};
+
 /// Return's a single word
 pub async fn word_view(
     path: web::Path<Uuid>,
+    query: web::Query<WordViewQuery>,
     pool: web::Data<DbPool>,
-) -> Result<web::Json<QuranWord>, RouterError> {
+) -> Result<web::Json<WordWithRenditions>, RouterError> {
     use crate::schema::quran_words::dsl::{quran_words, uuid as word_uuid};
 
     let requested_word_uuid = path.into_inner();
+    let query = query.into_inner();
 
     web::block(move || {
         let mut conn = pool.get().unwrap();
@@ -22,7 +26,25 @@ pub async fn word_view(
             .filter(word_uuid.eq(requested_word_uuid))
             .get_result(&mut conn)?;
 
-        Ok(web::Json(quran_word))
+        // And its text in the other scripts
+        let renditions: Vec<QuranWordRendition> =
+            QuranWordRendition::belonging_to(&quran_word).get_results(&mut conn)?;
+
+        // The word is transliterated as it is read alone
+        let transliteration = if query.transliteration {
+            words_transliteration(&mut conn, [[&quran_word]])?.remove(&quran_word.id)
+        } else {
+            None
+        };
+
+        Ok(web::Json(WordWithRenditions {
+            word: quran_word,
+            renditions: renditions
+                .into_iter()
+                .map(|rendition| (rendition.script, rendition.text))
+                .collect(),
+            transliteration,
+        }))
     })
     .await
     .unwrap()
@@ -0,0 +1,269 @@
+pub mod reciter_add;
+pub mod reciter_audio_delete;
+pub mod reciter_audio_modify;
+pub mod reciter_audio_view;
+pub mod reciter_delete;
+pub mod reciter_edit;
+pub mod reciter_list;
+pub mod reciter_timing_import;
+pub mod reciter_view;
+
+use std::env;
+
+use diesel::prelude::*;
+use diesel::upsert::excluded;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::error::RouterError;
+use crate::filter::{Filters, Order};
+use crate::models::{NewQuranReciterAyah, NewQuranReciterSegment, QuranReciter};
+use crate::verse_key::VerseKey;
+
+/// Base of the audio file paths, from the AUDIO_BASE_URL env
+///
+/// Can be a local directory or an url
+#[derive(Clone, Debug, Default)]
+pub struct AudioBase(pub String);
+
+impl AudioBase {
+    pub fn from_env() -> Self {
+        Self(env::var("AUDIO_BASE_URL").unwrap_or_default())
+    }
+
+    /// Joins the path to the base, absolute urls are returned as is
+    pub fn url(&self, path: &str) -> String {
+        if self.0.is_empty() || path.contains("://") {
+            return path.to_string();
+        }
+
+        format!(
+            "{}/{}",
+            self.0.trim_end_matches('/'),
+            path.trim_start_matches('/')
+        )
+    }
+}
+
+#[derive(Deserialize, Validate)]
+pub struct SimpleReciter {
+    pub mushaf_uuid: Uuid,
+
+    #[validate(length(min = 1, max = 200))]
+    pub name: String,
+
+    #[validate(length(min = 1, max = 50))]
+    pub style: Option<String>,
+}
+
+#[derive(Deserialize, Validate)]
+pub struct EditableSimpleReciter {
+    #[validate(length(min = 1, max = 200))]
+    pub name: String,
+
+    #[validate(length(min = 1, max = 50))]
+    pub style: Option<String>,
+}
+
+/// `[word position, start ms, end ms]` as in the common timing files,
+/// word position starts from 1
+#[derive(Deserialize, Serialize, Clone, Copy, Debug)]
+pub struct WordSegment(pub i32, pub i32, pub i32);
+
+/// The request body for /reciter/audio/{uuid}
+#[derive(Deserialize)]
+pub struct SimpleAyahAudio {
+    pub path: String,
+    pub duration_ms: Option<i32>,
+
+    /// Replaces the word segments of the ayah, if given
+    pub segments: Option<Vec<WordSegment>>,
+}
+
+/// An ayah of the timing import
+#[derive(Deserialize)]
+pub struct AyahTiming {
+    pub key: VerseKey,
+    pub path: String,
+    pub duration_ms: Option<i32>,
+
+    #[serde(default)]
+    pub segments: Vec<WordSegment>,
+}
+
+#[derive(Deserialize)]
+pub struct AudioAyahQuery {
+    pub ayah_uuid: Uuid,
+}
+
+#[derive(Deserialize)]
+pub struct AudioSurahQuery {
+    pub surah_uuid: Uuid,
+}
+
+#[derive(Serialize)]
+pub struct AudioSegment {
+    pub word_uuid: Uuid,
+    pub start_ms: i32,
+    pub end_ms: i32,
+}
+
+#[derive(Serialize)]
+pub struct AyahAudio {
+    pub ayah_uuid: Uuid,
+    pub number: u32,
+    pub url: String,
+    pub duration_ms: Option<i32>,
+    pub segments: Vec<AudioSegment>,
+}
+
+/// The response type for /reciter/audio/{uuid}
+#[derive(Serialize)]
+pub struct SurahAudio {
+    pub reciter: QuranReciter,
+    pub surah_uuid: Uuid,
+    pub ayahs: Vec<AyahAudio>,
+}
+
+#[derive(Deserialize, Clone)]
+pub struct ReciterListQuery {
+    mushaf: String,
+
+    sort: Option<String>,
+    order: Option<Order>,
+
+    from: Option<u64>,
+    to: Option<u64>,
+}
+
+impl Filters for ReciterListQuery {
+    fn sort(&self) -> Option<String> {
+        self.sort.clone()
+    }
+
+    fn order(&self) -> Option<Order> {
+        self.order.clone()
+    }
+
+    fn from(&self) -> Option<u64> {
+        self.from
+    }
+
+    fn to(&self) -> Option<u64> {
+        self.to
+    }
+}
+
+/// Returns the id of the ayah, the ayah must be in the mushaf of the reciter
+pub fn reciter_mushaf_ayah(
+    conn: &mut PgConnection,
+    reciter_mushaf: i32,
+    target_ayah_uuid: Uuid,
+) -> Result<i32, RouterError> {
+    use crate::schema::quran_ayahs::dsl::{id as ayah_id, quran_ayahs, uuid as ayah_uuid};
+    use crate::schema::quran_surahs::dsl::{mushaf_id, quran_surahs};
+
+    let ayah: Option<i32> = quran_ayahs
+        .inner_join(quran_surahs)
+        .filter(ayah_uuid.eq(target_ayah_uuid))
+        .filter(mushaf_id.eq(reciter_mushaf))
+        .select(ayah_id)
+        .get_result(conn)
+        .optional()?;
+
+    ayah.ok_or_else(|| {
+        RouterError::from_predefined_with_detail("AYAH_NOT_FOUND", &target_ayah_uuid.to_string())
+    })
+}
+
+/// Adds or updates the audio of an ayah
+///
+/// `words` are the word ids of the ayah in order, if segments is given
+/// the existing segments of the audio will be replaced
+pub fn save_ayah_audio(
+    conn: &mut PgConnection,
+    audio: NewQuranReciterAyah,
+    words: &[i32],
+    segments: Option<&[WordSegment]>,
+) -> Result<(), RouterError> {
+    use crate::schema::quran_reciters_ayahs::dsl::{
+        ayah_id, duration_ms, id as audio_id, path, quran_reciters_ayahs, reciter_id,
+    };
+    use crate::schema::quran_reciters_segments::dsl::{quran_reciters_segments, reciter_ayah_id};
+
+    let creator_user_id = audio.creator_user_id;
+
+    let saved_audio: i32 = audio
+        .insert_into(quran_reciters_ayahs)
+        .on_conflict((reciter_id, ayah_id))
+        .do_update()
+        .set((
+            path.eq(excluded(path)),
+            duration_ms.eq(excluded(duration_ms)),
+        ))
+        .returning(audio_id)
+        .get_result(conn)?;
+
+    let Some(segments) = segments else {
+        return Ok(());
+    };
+
+    let mut new_segments = vec![];
+
+    for WordSegment(position, start_ms, end_ms) in segments.iter().copied() {
+        if start_ms < 0 || end_ms < start_ms {
+            return Err(RouterError::from_predefined_with_detail(
+                "AUDIO_SEGMENT_NOT_VALID",
+                &format!("{}:{}-{}", position, start_ms, end_ms),
+            ));
+        }
+
+        let Some(word_id) = usize::try_from(position - 1)
+            .ok()
+            .and_then(|index| words.get(index))
+        else {
+            return Err(RouterError::from_predefined_with_detail(
+                "WORD_NOT_FOUND",
+                &position.to_string(),
+            ));
+        };
+
+        new_segments.push(NewQuranReciterSegment {
+            creator_user_id,
+            reciter_ayah_id: saved_audio,
+            word_id: *word_id,
+            start_ms,
+            end_ms,
+        });
+    }
+
+    diesel::delete(quran_reciters_segments.filter(reciter_ayah_id.eq(saved_audio)))
+        .execute(conn)?;
+
+    diesel::insert_into(quran_reciters_segments)
+        .values(new_segments)
+        .execute(conn)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AudioBase;
+
+    #[test]
+    fn test_audio_url() {
+        let base = AudioBase("https://audio.example/files/".to_string());
+
+        assert_eq!(
+            base.url("/alafasy/001001.mp3"),
+            "https://audio.example/files/alafasy/001001.mp3"
+        );
+        assert_eq!(
+            base.url("https://other.example/001001.mp3"),
+            "https://other.example/001001.mp3"
+        );
+        assert_eq!(AudioBase::default().url("001001.mp3"), "001001.mp3");
+    }
+}
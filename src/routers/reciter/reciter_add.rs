@@ -0,0 +1,51 @@
+use crate::models::NewQuranReciter;
+use crate::validate::validate;
+use crate::{error::RouterError, DbPool};
+use actix_web::web;
+use diesel::prelude::*;
+
+use super::SimpleReciter;
+
+/// Add's a new reciter to the mushaf
+pub async fn reciter_add(
+    new_reciter: web::Json<SimpleReciter>,
+    pool: web::Data<DbPool>,
+    data: web::ReqData<u32>,
+) -> Result<&'static str, RouterError> {
+    use crate::schema::app_users::dsl::{account_id as user_acc_id, app_users, id as user_id};
+    use crate::schema::quran_mushafs::dsl::{id as mushaf_id, quran_mushafs, uuid as mushaf_uuid};
+    use crate::schema::quran_reciters::dsl::quran_reciters;
+
+    let new_reciter = new_reciter.into_inner();
+    let data = data.into_inner();
+
+    validate(&new_reciter)?;
+
+    web::block(move || {
+        let mut conn = pool.get().unwrap();
+
+        // Get the creator user-id
+        let user: i32 = app_users
+            .filter(user_acc_id.eq(data as i32))
+            .select(user_id)
+            .get_result(&mut conn)?;
+
+        let mushaf: i32 = quran_mushafs
+            .filter(mushaf_uuid.eq(new_reciter.mushaf_uuid))
+            .select(mushaf_id)
+            .get_result(&mut conn)?;
+
+        NewQuranReciter {
+            creator_user_id: user,
+            mushaf_id: mushaf,
+            name: new_reciter.name,
+            style: new_reciter.style,
+        }
+        .insert_into(quran_reciters)
+        .execute(&mut conn)?;
+
+        Ok("Added")
+    })
+    .await
+    .unwrap()
+}
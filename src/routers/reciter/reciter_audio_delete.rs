@@ -0,0 +1,45 @@
+use crate::{error::RouterError, DbPool};
+use actix_web::web;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use super::{reciter_mushaf_ayah, AudioAyahQuery};
+
+/// Delete's the audio of an ayah with its segments
+pub async fn reciter_audio_delete(
+    path: web::Path<Uuid>,
+    query: web::Query<AudioAyahQuery>,
+    pool: web::Data<DbPool>,
+) -> Result<&'static str, RouterError> {
+    use crate::schema::quran_reciters::dsl::{
+        id as reciter_id, mushaf_id as reciter_mushaf_id, quran_reciters, uuid as reciter_uuid,
+    };
+    use crate::schema::quran_reciters_ayahs::dsl::{
+        ayah_id as audio_ayah_id, quran_reciters_ayahs, reciter_id as audio_reciter_id,
+    };
+
+    let path = path.into_inner();
+    let query = query.into_inner();
+
+    web::block(move || {
+        let mut conn = pool.get().unwrap();
+
+        let (reciter, mushaf): (i32, i32) = quran_reciters
+            .filter(reciter_uuid.eq(path))
+            .select((reciter_id, reciter_mushaf_id))
+            .get_result(&mut conn)?;
+
+        let ayah = reciter_mushaf_ayah(&mut conn, mushaf, query.ayah_uuid)?;
+
+        diesel::delete(
+            quran_reciters_ayahs
+                .filter(audio_reciter_id.eq(reciter))
+                .filter(audio_ayah_id.eq(ayah)),
+        )
+        .execute(&mut conn)?;
+
+        Ok("Deleted")
+    })
+    .await
+    .unwrap()
+}
@@ -0,0 +1,72 @@
+use crate::models::NewQuranReciterAyah;
+use crate::{error::RouterError, DbPool};
+use actix_web::web;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use super::{reciter_mushaf_ayah, save_ayah_audio, AudioAyahQuery, SimpleAyahAudio};
+
+/// Modify the audio of an ayah,
+///
+/// If the reciter has an audio for the ayah update it,
+/// otherwise add.
+pub async fn reciter_audio_modify(
+    new_audio: web::Json<SimpleAyahAudio>,
+    pool: web::Data<DbPool>,
+    data: web::ReqData<u32>,
+    // reciter uuid
+    path: web::Path<Uuid>,
+    query: web::Query<AudioAyahQuery>,
+) -> Result<&'static str, RouterError> {
+    use crate::schema::app_users::dsl::{account_id as user_acc_id, app_users, id as user_id};
+    use crate::schema::quran_reciters::dsl::{
+        id as reciter_id, mushaf_id as reciter_mushaf_id, quran_reciters, uuid as reciter_uuid,
+    };
+    use crate::schema::quran_words::dsl::{ayah_id as word_ayah_id, id as word_id, quran_words};
+
+    let new_audio = new_audio.into_inner();
+    let path = path.into_inner();
+    let creator_id = data.into_inner();
+    let query = query.into_inner();
+
+    web::block(move || {
+        let mut conn = pool.get().unwrap();
+
+        let user: i32 = app_users
+            .filter(user_acc_id.eq(creator_id as i32))
+            .select(user_id)
+            .get_result(&mut conn)?;
+
+        let (reciter, mushaf): (i32, i32) = quran_reciters
+            .filter(reciter_uuid.eq(path))
+            .select((reciter_id, reciter_mushaf_id))
+            .get_result(&mut conn)?;
+
+        let ayah = reciter_mushaf_ayah(&mut conn, mushaf, query.ayah_uuid)?;
+
+        let words: Vec<i32> = quran_words
+            .filter(word_ayah_id.eq(ayah))
+            .order(word_id.asc())
+            .select(word_id)
+            .get_results(&mut conn)?;
+
+        conn.transaction(|conn| {
+            save_ayah_audio(
+                conn,
+                NewQuranReciterAyah {
+                    creator_user_id: user,
+                    reciter_id: reciter,
+                    ayah_id: ayah,
+                    path: &new_audio.path,
+                    duration_ms: new_audio.duration_ms,
+                },
+                &words,
+                new_audio.segments.as_deref(),
+            )
+        })?;
+
+        Ok("Modified")
+    })
+    .await
+    .unwrap()
+}
@@ -0,0 +1,90 @@
+use std::collections::HashMap;
+
+use crate::models::{QuranReciter, QuranReciterAyah};
+use crate::{error::RouterError, DbPool};
+use actix_web::web;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use super::{AudioBase, AudioSegment, AudioSurahQuery, AyahAudio, SurahAudio};
+
+/// Returns the audio of the surah ayahs with their word segments
+pub async fn reciter_audio_view(
+    path: web::Path<Uuid>,
+    query: web::Query<AudioSurahQuery>,
+    pool: web::Data<DbPool>,
+    audio_base: web::Data<AudioBase>,
+) -> Result<web::Json<SurahAudio>, RouterError> {
+    use crate::schema::quran_ayahs::dsl::{ayah_number, quran_ayahs, surah_id, uuid as ayah_uuid};
+    use crate::schema::quran_reciters::dsl::{quran_reciters, uuid as reciter_uuid};
+    use crate::schema::quran_reciters_ayahs::dsl::{
+        quran_reciters_ayahs, reciter_id as audio_reciter_id,
+    };
+    use crate::schema::quran_reciters_segments::dsl::{
+        end_ms, id as segment_id, quran_reciters_segments, reciter_ayah_id, start_ms,
+    };
+    use crate::schema::quran_surahs::dsl::{id as surah_id_col, quran_surahs, uuid as surah_uuid};
+    use crate::schema::quran_words::dsl::{quran_words, uuid as word_uuid};
+
+    let path = path.into_inner();
+    let query = query.into_inner();
+
+    web::block(move || {
+        let mut conn = pool.get().unwrap();
+
+        let reciter: QuranReciter = quran_reciters
+            .filter(reciter_uuid.eq(path))
+            .get_result(&mut conn)?;
+
+        let surah: i32 = quran_surahs
+            .filter(surah_uuid.eq(query.surah_uuid))
+            .select(surah_id_col)
+            .get_result(&mut conn)?;
+
+        let audios: Vec<(QuranReciterAyah, Uuid, i32)> = quran_reciters_ayahs
+            .inner_join(quran_ayahs)
+            .filter(audio_reciter_id.eq(reciter.id))
+            .filter(surah_id.eq(surah))
+            .order(ayah_number.asc())
+            .select((QuranReciterAyah::as_select(), ayah_uuid, ayah_number))
+            .get_results(&mut conn)?;
+
+        let audio_ids: Vec<i32> = audios.iter().map(|(audio, _, _)| audio.id).collect();
+
+        let segments: Vec<(i32, Uuid, i32, i32)> = quran_reciters_segments
+            .inner_join(quran_words)
+            .filter(reciter_ayah_id.eq_any(audio_ids))
+            .order((start_ms.asc(), segment_id.asc()))
+            .select((reciter_ayah_id, word_uuid, start_ms, end_ms))
+            .get_results(&mut conn)?;
+
+        let mut segments_map: HashMap<i32, Vec<AudioSegment>> = HashMap::new();
+
+        for (audio, word, start, end) in segments {
+            segments_map.entry(audio).or_default().push(AudioSegment {
+                word_uuid: word,
+                start_ms: start,
+                end_ms: end,
+            });
+        }
+
+        let ayahs = audios
+            .into_iter()
+            .map(|(audio, a_uuid, number)| AyahAudio {
+                ayah_uuid: a_uuid,
+                number: number as u32,
+                url: audio_base.url(&audio.path),
+                duration_ms: audio.duration_ms,
+                segments: segments_map.remove(&audio.id).unwrap_or_default(),
+            })
+            .collect();
+
+        Ok(web::Json(SurahAudio {
+            reciter,
+            surah_uuid: query.surah_uuid,
+            ayahs,
+        }))
+    })
+    .await
+    .unwrap()
+}
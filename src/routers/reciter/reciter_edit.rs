@@ -0,0 +1,39 @@
+use crate::error::RouterError;
+use crate::validate::validate;
+use crate::DbPool;
+use actix_web::web;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use super::EditableSimpleReciter;
+
+/// Update's single reciter
+pub async fn reciter_edit(
+    path: web::Path<Uuid>,
+    new_reciter: web::Json<EditableSimpleReciter>,
+    pool: web::Data<DbPool>,
+) -> Result<&'static str, RouterError> {
+    use crate::schema::quran_reciters::dsl::{
+        name as reciter_name, quran_reciters, style as reciter_style, uuid as reciter_uuid,
+    };
+
+    let new_reciter = new_reciter.into_inner();
+    let path = path.into_inner();
+
+    validate(&new_reciter)?;
+
+    web::block(move || {
+        let mut conn = pool.get().unwrap();
+
+        diesel::update(quran_reciters.filter(reciter_uuid.eq(path)))
+            .set((
+                reciter_name.eq(new_reciter.name),
+                reciter_style.eq(new_reciter.style),
+            ))
+            .execute(&mut conn)?;
+
+        Ok("Edited")
+    })
+    .await
+    .unwrap()
+}
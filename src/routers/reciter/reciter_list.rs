@@ -0,0 +1,46 @@
+use crate::error::{RouterError, RouterErrorDetailBuilder};
+use crate::filter::Filter;
+use crate::models::QuranReciter;
+use crate::DbPool;
+use actix_web::{web, HttpRequest};
+use diesel::prelude::*;
+
+use super::ReciterListQuery;
+
+/// Returns the list of the mushaf reciters
+pub async fn reciter_list(
+    pool: web::Data<DbPool>,
+    web::Query(query): web::Query<ReciterListQuery>,
+    req: HttpRequest,
+) -> Result<web::Json<Vec<QuranReciter>>, RouterError> {
+    use crate::schema::quran_mushafs::dsl::{
+        id as mushaf_id, quran_mushafs, short_name as mushaf_short_name,
+    };
+    use crate::schema::quran_reciters::dsl::mushaf_id as reciter_mushaf_id;
+
+    let pool = pool.into_inner();
+
+    let error_detail = RouterErrorDetailBuilder::from_http_request(&req).build();
+
+    web::block(move || {
+        let mut conn = pool.get().unwrap();
+
+        let mushaf: i32 = quran_mushafs
+            .filter(mushaf_short_name.eq(query.mushaf.clone()))
+            .select(mushaf_id)
+            .get_result(&mut conn)?;
+
+        let reciters = match QuranReciter::filter(Box::from(query)) {
+            Ok(filtred) => filtred,
+            Err(err) => return Err(err.log_to_db(pool, error_detail)),
+        };
+
+        let reciters = reciters
+            .filter(reciter_mushaf_id.eq(mushaf))
+            .get_results(&mut conn)?;
+
+        Ok(web::Json(reciters))
+    })
+    .await
+    .unwrap()
+}
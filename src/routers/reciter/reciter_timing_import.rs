@@ -0,0 +1,85 @@
+use std::collections::HashMap;
+
+use crate::models::NewQuranReciterAyah;
+use crate::verse_key::{mushaf_ayah_ids, mushaf_word_ids, VerseKey};
+use crate::{error::RouterError, DbPool};
+use actix_web::web;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use super::{save_ayah_audio, AyahTiming};
+
+/// Imports the audio and word timings of the reciter ayahs
+///
+/// Ayahs are addressed by their `surah:ayah` key in the reciter mushaf,
+/// existing audios of the imported ayahs will be replaced
+pub async fn reciter_timing_import(
+    path: web::Path<Uuid>,
+    import: web::Json<Vec<AyahTiming>>,
+    pool: web::Data<DbPool>,
+    data: web::ReqData<u32>,
+) -> Result<&'static str, RouterError> {
+    use crate::schema::app_users::dsl::{account_id as user_acc_id, app_users, id as user_id};
+    use crate::schema::quran_reciters::dsl::{
+        id as reciter_id, mushaf_id as reciter_mushaf_id, quran_reciters, uuid as reciter_uuid,
+    };
+
+    let target_reciter_uuid = path.into_inner();
+    let import = import.into_inner();
+    let data = data.into_inner();
+
+    web::block(move || {
+        let mut conn = pool.get().unwrap();
+
+        let user: i32 = app_users
+            .filter(user_acc_id.eq(data as i32))
+            .select(user_id)
+            .get_result(&mut conn)?;
+
+        let (reciter, mushaf): (i32, i32) = quran_reciters
+            .filter(reciter_uuid.eq(target_reciter_uuid))
+            .select((reciter_id, reciter_mushaf_id))
+            .get_result(&mut conn)?;
+
+        let ayah_ids = mushaf_ayah_ids(&mut conn, mushaf)?;
+
+        // Word ids of every ayah, in order of their position
+        let mut words: Vec<_> = mushaf_word_ids(&mut conn, mushaf)?.into_iter().collect();
+        words.sort_unstable_by_key(|(key, _)| *key);
+
+        let mut ayah_words: HashMap<VerseKey, Vec<i32>> = HashMap::new();
+        for (key, id) in words {
+            ayah_words.entry(key.verse()).or_default().push(id);
+        }
+
+        conn.transaction(|conn| {
+            for timing in &import {
+                let Some(ayah) = ayah_ids.get(&timing.key) else {
+                    return Err(RouterError::from_predefined_with_detail(
+                        "AYAH_NOT_FOUND",
+                        &timing.key.to_string(),
+                    ));
+                };
+
+                save_ayah_audio(
+                    conn,
+                    NewQuranReciterAyah {
+                        creator_user_id: user,
+                        reciter_id: reciter,
+                        ayah_id: *ayah,
+                        path: &timing.path,
+                        duration_ms: timing.duration_ms,
+                    },
+                    ayah_words.get(&timing.key).map_or(&[], |w| w.as_slice()),
+                    Some(&timing.segments),
+                )?;
+            }
+
+            Ok(())
+        })?;
+
+        Ok("Imported")
+    })
+    .await
+    .unwrap()
+}
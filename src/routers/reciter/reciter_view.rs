@@ -0,0 +1,27 @@
+use crate::models::QuranReciter;
+use crate::{error::RouterError, DbPool};
+use actix_web::web;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+/// Return's a single reciter
+pub async fn reciter_view(
+    path: web::Path<Uuid>,
+    pool: web::Data<DbPool>,
+) -> Result<web::Json<QuranReciter>, RouterError> {
+    use crate::schema::quran_reciters::dsl::{quran_reciters, uuid as reciter_uuid};
+
+    let path = path.into_inner();
+
+    web::block(move || {
+        let mut conn = pool.get().unwrap();
+
+        let reciter: QuranReciter = quran_reciters
+            .filter(reciter_uuid.eq(path))
+            .get_result(&mut conn)?;
+
+        Ok(web::Json(reciter))
+    })
+    .await
+    .unwrap()
+}
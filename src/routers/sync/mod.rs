@@ -0,0 +1,258 @@
+pub mod sync_changes;
+
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::error::RouterError;
+use crate::verse_key::VerseKey;
+
+/// Synced table of the change feed
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncTable {
+    Surah,
+    Ayah,
+    Word,
+    Breaker,
+    TranslationAyah,
+}
+
+impl SyncTable {
+    /// Returns the synced table by its database name
+    pub fn from_table_name(name: &str) -> Option<Self> {
+        match name {
+            "quran_surahs" => Some(Self::Surah),
+            "quran_ayahs" => Some(Self::Ayah),
+            "quran_words" => Some(Self::Word),
+            "quran_ayahs_breakers" => Some(Self::Breaker),
+            "quran_translations_ayahs" => Some(Self::TranslationAyah),
+
+            _ => None,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SyncOperation {
+    Insert,
+    Update,
+
+    /// Tombstone, the row is deleted
+    Delete,
+}
+
+impl FromStr for SyncOperation {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "insert" => Ok(Self::Insert),
+            "update" => Ok(Self::Update),
+            "delete" => Ok(Self::Delete),
+
+            _ => Err(()),
+        }
+    }
+}
+
+/// Position of the client in the change feed
+///
+/// example: `7421.1093`, transaction id and change id
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct SyncCursor {
+    pub transaction: i64,
+    pub change: i64,
+}
+
+impl FromStr for SyncCursor {
+    type Err = RouterError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let not_valid = || RouterError::from_predefined_with_detail("SYNC_CURSOR_NOT_VALID", s);
+
+        let (transaction, change) = s.split_once('.').ok_or_else(not_valid)?;
+
+        Ok(Self {
+            transaction: transaction.parse().map_err(|_| not_valid())?,
+            change: change.parse().map_err(|_| not_valid())?,
+        })
+    }
+}
+
+impl TryFrom<String> for SyncCursor {
+    type Error = RouterError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl Display for SyncCursor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}", self.transaction, self.change)
+    }
+}
+
+impl From<SyncCursor> for String {
+    fn from(value: SyncCursor) -> Self {
+        value.to_string()
+    }
+}
+
+#[derive(Deserialize, Validate)]
+pub struct SyncQuery {
+    /// Cursor of the last sync, every change if not set
+    pub since: Option<SyncCursor>,
+
+    #[validate(range(min = 1, max = 10000))]
+    pub limit: Option<i64>,
+}
+
+#[derive(Serialize)]
+pub struct SyncSurah {
+    pub mushaf_uuid: Uuid,
+    pub number: i32,
+    pub name: String,
+    pub period: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct SyncAyah {
+    pub surah_uuid: Uuid,
+    pub key: VerseKey,
+    pub sajdah: Option<String>,
+    pub is_bismillah: bool,
+    pub bismillah_text: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct SyncWord {
+    pub ayah_uuid: Uuid,
+    pub word: String,
+
+    /// Stable order key of the word, the words of an ayah are positioned by it
+    ///
+    /// Unlike the position, it doesn't change when the other words are added or deleted
+    pub order: i32,
+}
+
+#[derive(Serialize)]
+pub struct SyncBreaker {
+    pub ayah_uuid: Uuid,
+    pub name: String,
+}
+
+#[derive(Serialize)]
+pub struct SyncTranslationAyah {
+    pub translation_uuid: Uuid,
+    pub ayah_uuid: Uuid,
+    pub text: String,
+    pub bismillah: Option<String>,
+}
+
+/// Current data of the changed row
+#[derive(Serialize)]
+#[serde(untagged)]
+pub enum SyncData {
+    Surah(SyncSurah),
+    Ayah(SyncAyah),
+    Word(SyncWord),
+    Breaker(SyncBreaker),
+    TranslationAyah(SyncTranslationAyah),
+}
+
+#[derive(Serialize)]
+pub struct SyncChange {
+    pub table: SyncTable,
+    pub operation: SyncOperation,
+    pub uuid: Uuid,
+
+    /// None for the deletes
+    pub data: Option<SyncData>,
+}
+
+/// The response type for GET /sync/changes
+#[derive(Serialize)]
+pub struct SyncChanges {
+    /// The `since` of the next sync
+    pub cursor: SyncCursor,
+
+    /// There are more changes after the cursor, sync again
+    pub has_more: bool,
+    pub changes: Vec<SyncChange>,
+}
+
+/// Keeps the last change of every row, in the order of the last changes
+///
+/// A row inserted and then updated is still an insert for the client
+pub fn collapse_changes(
+    changes: Vec<(SyncTable, SyncOperation, Uuid)>,
+) -> Vec<(SyncTable, SyncOperation, Uuid)> {
+    let mut last: HashMap<(SyncTable, Uuid), (usize, SyncOperation)> = HashMap::new();
+
+    for (index, (table, operation, uuid)) in changes.into_iter().enumerate() {
+        let operation = match (last.get(&(table, uuid)), operation) {
+            (Some((_, SyncOperation::Insert)), SyncOperation::Update) => SyncOperation::Insert,
+            (_, operation) => operation,
+        };
+
+        last.insert((table, uuid), (index, operation));
+    }
+
+    let mut result: Vec<(usize, (SyncTable, SyncOperation, Uuid))> = last
+        .into_iter()
+        .map(|((table, uuid), (index, operation))| (index, (table, operation, uuid)))
+        .collect();
+
+    result.sort_by_key(|(index, _)| *index);
+
+    result.into_iter().map(|(_, change)| change).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_sync_cursor() {
+        let cursor: SyncCursor = "7421.1093".parse().unwrap();
+
+        assert_eq!(
+            cursor,
+            SyncCursor {
+                transaction: 7421,
+                change: 1093
+            }
+        );
+        assert_eq!(cursor.to_string(), "7421.1093");
+    }
+
+    #[test]
+    fn test_collapse_changes() {
+        let (a, b) = (Uuid::from_u128(1), Uuid::from_u128(2));
+
+        let changes = collapse_changes(vec![
+            (SyncTable::Ayah, SyncOperation::Insert, a),
+            (SyncTable::Word, SyncOperation::Update, b),
+            (SyncTable::Ayah, SyncOperation::Update, a),
+            (SyncTable::Word, SyncOperation::Delete, b),
+            (SyncTable::Word, SyncOperation::Insert, a),
+        ]);
+
+        assert_eq!(
+            changes,
+            vec![
+                (SyncTable::Ayah, SyncOperation::Insert, a),
+                (SyncTable::Word, SyncOperation::Delete, b),
+                (SyncTable::Word, SyncOperation::Insert, a),
+            ]
+        );
+    }
+}
@@ -0,0 +1,270 @@
+use std::collections::HashMap;
+
+use diesel::dsl::sql;
+use diesel::prelude::*;
+use diesel::sql_types::BigInt;
+use uuid::Uuid;
+
+use crate::error::RouterError;
+use crate::models::Change;
+use crate::validate::validate;
+use crate::verse_key::VerseKey;
+use crate::DbPool;
+use actix_web::web;
+
+use super::{
+    collapse_changes, SyncAyah, SyncBreaker, SyncChange, SyncChanges, SyncCursor, SyncData,
+    SyncOperation, SyncQuery, SyncSurah, SyncTable, SyncTranslationAyah, SyncWord,
+};
+
+const DEFAULT_LIMIT: i64 = 1000;
+
+/// Returns the changes of the mushafs and translations since the cursor
+///
+/// Only the changes of the transactions older than every running transaction are returned,
+/// so a change committed later never gets a position before the returned cursor
+pub async fn sync_changes(
+    pool: web::Data<DbPool>,
+    web::Query(query): web::Query<SyncQuery>,
+) -> Result<web::Json<SyncChanges>, RouterError> {
+    use crate::schema::app_changes::dsl::{app_changes, id as change_id, transaction_id};
+
+    validate(&query)?;
+
+    let since = query.since.unwrap_or_default();
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT);
+
+    web::block(move || {
+        let mut conn = pool.get().unwrap();
+
+        // Oldest running transaction, changes before it are all committed
+        let visible_before: i64 =
+            diesel::select(sql::<BigInt>("txid_snapshot_xmin(txid_current_snapshot())"))
+                .get_result(&mut conn)?;
+
+        let mut changes: Vec<Change> = app_changes
+            .filter(
+                transaction_id.gt(since.transaction).or(transaction_id
+                    .eq(since.transaction)
+                    .and(change_id.gt(since.change))),
+            )
+            .filter(transaction_id.lt(visible_before))
+            .order((transaction_id.asc(), change_id.asc()))
+            .limit(limit + 1)
+            .get_results(&mut conn)?;
+
+        let has_more = changes.len() as i64 > limit;
+        changes.truncate(limit as usize);
+
+        let cursor = match changes.last() {
+            Some(last) if has_more => SyncCursor {
+                transaction: last.transaction_id,
+                change: last.id,
+            },
+
+            _ => SyncCursor {
+                transaction: visible_before.max(since.transaction),
+                change: 0,
+            },
+        };
+
+        let changes = collapse_changes(
+            changes
+                .into_iter()
+                .filter_map(|change| {
+                    Some((
+                        SyncTable::from_table_name(&change.table_name)?,
+                        change.operation.parse().ok()?,
+                        change.row_uuid,
+                    ))
+                })
+                .collect(),
+        );
+
+        let mut uuids: HashMap<SyncTable, Vec<Uuid>> = HashMap::new();
+        for (table, operation, uuid) in &changes {
+            if *operation != SyncOperation::Delete {
+                uuids.entry(*table).or_default().push(*uuid);
+            }
+        }
+
+        let mut data = changed_rows(&mut conn, uuids)?;
+
+        Ok(web::Json(SyncChanges {
+            cursor,
+            has_more,
+            changes: changes
+                .into_iter()
+                .filter_map(|(table, operation, uuid)| {
+                    let row = match operation {
+                        SyncOperation::Delete => None,
+
+                        // Row is deleted after the cursor, its tombstone comes with the next sync
+                        _ => Some(data.remove(&uuid)?),
+                    };
+
+                    Some(SyncChange {
+                        table,
+                        operation,
+                        uuid,
+                        data: row,
+                    })
+                })
+                .collect(),
+        }))
+    })
+    .await
+    .unwrap()
+}
+
+/// Returns the current data of the changed rows by their uuid
+fn changed_rows(
+    conn: &mut PgConnection,
+    uuids: HashMap<SyncTable, Vec<Uuid>>,
+) -> QueryResult<HashMap<Uuid, SyncData>> {
+    use crate::schema::quran_ayahs::dsl::{
+        ayah_number, bismillah_text, is_bismillah, quran_ayahs, sajdah, uuid as ayah_uuid,
+    };
+    use crate::schema::quran_ayahs_breakers::dsl::{
+        name as breaker_name, owner_account_id, quran_ayahs_breakers, uuid as breaker_uuid,
+    };
+    use crate::schema::quran_mushafs::dsl::{quran_mushafs, uuid as mushaf_uuid};
+    use crate::schema::quran_surahs::dsl::{
+        name as surah_name, number as surah_number, period, quran_surahs, uuid as surah_uuid,
+    };
+    use crate::schema::quran_translations::dsl::{quran_translations, uuid as translation_uuid};
+    use crate::schema::quran_translations_ayahs::dsl::{
+        bismillah, quran_translations_ayahs, text, uuid as translation_ayah_uuid,
+    };
+    use crate::schema::quran_words::dsl::{id as word_id, quran_words, uuid as word_uuid, word};
+
+    let mut rows = HashMap::new();
+
+    for (table, uuids) in uuids {
+        match table {
+            SyncTable::Surah => rows.extend(
+                quran_surahs
+                    .inner_join(quran_mushafs)
+                    .filter(surah_uuid.eq_any(uuids))
+                    .select((surah_uuid, mushaf_uuid, surah_number, surah_name, period))
+                    .get_results::<(Uuid, Uuid, i32, String, Option<String>)>(conn)?
+                    .into_iter()
+                    .map(|(uuid, mushaf, number, name, surah_period)| {
+                        (
+                            uuid,
+                            SyncData::Surah(SyncSurah {
+                                mushaf_uuid: mushaf,
+                                number,
+                                name,
+                                period: surah_period,
+                            }),
+                        )
+                    }),
+            ),
+
+            SyncTable::Ayah => rows.extend(
+                quran_ayahs
+                    .inner_join(quran_surahs)
+                    .filter(ayah_uuid.eq_any(uuids))
+                    .select((
+                        ayah_uuid,
+                        surah_uuid,
+                        surah_number,
+                        ayah_number,
+                        sajdah,
+                        is_bismillah,
+                        bismillah_text,
+                    ))
+                    .get_results::<(Uuid, Uuid, i32, i32, Option<String>, bool, Option<String>)>(
+                        conn,
+                    )?
+                    .into_iter()
+                    .map(
+                        |(uuid, surah, surah_num, ayah_num, ayah_sajdah, is_bism, bism)| {
+                            (
+                                uuid,
+                                SyncData::Ayah(SyncAyah {
+                                    surah_uuid: surah,
+                                    key: VerseKey {
+                                        surah: surah_num,
+                                        ayah: ayah_num,
+                                    },
+                                    sajdah: ayah_sajdah,
+                                    is_bismillah: is_bism,
+                                    bismillah_text: bism,
+                                }),
+                            )
+                        },
+                    ),
+            ),
+
+            SyncTable::Word => rows.extend(
+                quran_words
+                    .inner_join(quran_ayahs)
+                    .filter(word_uuid.eq_any(uuids))
+                    .select((word_uuid, ayah_uuid, word, word_id))
+                    .get_results::<(Uuid, Uuid, String, i32)>(conn)?
+                    .into_iter()
+                    .map(|(uuid, ayah, text_of_word, order)| {
+                        (
+                            uuid,
+                            SyncData::Word(SyncWord {
+                                ayah_uuid: ayah,
+                                word: text_of_word,
+                                order,
+                            }),
+                        )
+                    }),
+            ),
+
+            // Breakers of the accounts are not synced
+            SyncTable::Breaker => rows.extend(
+                quran_ayahs_breakers
+                    .inner_join(quran_ayahs)
+                    .filter(breaker_uuid.eq_any(uuids))
+                    .filter(owner_account_id.is_null())
+                    .select((breaker_uuid, ayah_uuid, breaker_name))
+                    .get_results::<(Uuid, Uuid, String)>(conn)?
+                    .into_iter()
+                    .map(|(uuid, ayah, name)| {
+                        (
+                            uuid,
+                            SyncData::Breaker(SyncBreaker {
+                                ayah_uuid: ayah,
+                                name,
+                            }),
+                        )
+                    }),
+            ),
+
+            SyncTable::TranslationAyah => rows.extend(
+                quran_translations_ayahs
+                    .inner_join(quran_translations)
+                    .inner_join(quran_ayahs)
+                    .filter(translation_ayah_uuid.eq_any(uuids))
+                    .select((
+                        translation_ayah_uuid,
+                        translation_uuid,
+                        ayah_uuid,
+                        text,
+                        bismillah,
+                    ))
+                    .get_results::<(Uuid, Uuid, Uuid, String, Option<String>)>(conn)?
+                    .into_iter()
+                    .map(|(uuid, translation, ayah, ayah_text, ayah_bismillah)| {
+                        (
+                            uuid,
+                            SyncData::TranslationAyah(SyncTranslationAyah {
+                                translation_uuid: translation,
+                                ayah_uuid: ayah,
+                                text: ayah_text,
+                                bismillah: ayah_bismillah,
+                            }),
+                        )
+                    }),
+            ),
+        }
+    }
+
+    Ok(rows)
+}
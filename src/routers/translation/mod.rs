@@ -12,6 +12,7 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::filter::{Filters, Order};
+use crate::verse_key::VerseKey;
 
 #[derive(Serialize, Deserialize)]
 pub struct TranslatorData {
@@ -45,6 +46,11 @@ pub struct TranslationAyah {
     surah_number: u32,
     bismillah: Option<String>,
     text: Option<String>,
+
+    /// The ayah is a later part of a splitted ayah of the translation's mushaf,
+    /// the text of the splitted ayah is on its first part (this verse key)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    text_on: Option<VerseKey>,
 }
 
 #[derive(Serialize, Deserialize)]
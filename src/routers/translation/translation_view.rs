@@ -1,22 +1,50 @@
+use std::collections::HashMap;
+
+use crate::alignment::Alignment;
 use crate::error::RouterError;
 use crate::models::Translation;
+use crate::ndjson::{accepts_ndjson, stream_ndjson, NdjsonWriter, StreamLimit};
+use crate::verse_key::VerseKey;
 use crate::{DbPool, TranslationAyah, TranslationStatus, TranslatorData, ViewableTranslation};
 use ::uuid::Uuid;
-use actix_web::web;
+use actix_web::{web, HttpRequest, HttpResponse};
+use diesel::pg::PgRowByRowLoadingMode;
 use diesel::{prelude::*, query_dsl::boxed_dsl::BoxedDsl};
 use serde::{Deserialize, Serialize};
 
 #[derive(Deserialize, Serialize)]
 pub struct TranslationViewQuery {
     surah_uuid: Option<Uuid>,
+
+    /// Render the translation against this mushaf,
+    /// ayahs are mapped with the mushaf alignment
+    mushaf_uuid: Option<Uuid>,
+}
+
+/// (text, ayah uuid, ayah number, surah number, text uuid, bismillah)
+type TranslationRow = (Option<String>, Uuid, i32, i32, Option<Uuid>, Option<String>);
+
+/// (text, text uuid, bismillah)
+type AlignedText = (Option<String>, Option<Uuid>, Option<String>);
+
+/// A source ayah on an ayah of the target mushaf
+enum AlignedPart {
+    Text(AlignedText),
+
+    /// Later part of a splitted ayah, its text is on the first part
+    Continued(VerseKey),
 }
 
 /// Return's a single translation
+///
+/// Only the ayahs are streamed one per line if the client accepts `application/x-ndjson`
 pub async fn translation_view(
     path: web::Path<Uuid>,
     pool: web::Data<DbPool>,
     web::Query(query): web::Query<TranslationViewQuery>,
-) -> Result<web::Json<ViewableTranslation>, RouterError> {
+    stream_limit: web::Data<StreamLimit>,
+    req: HttpRequest,
+) -> Result<HttpResponse, RouterError> {
     use crate::schema::app_accounts::dsl::{
         app_accounts, id as account_table_id, username as acc_username, uuid as account_uuid,
     };
@@ -40,7 +68,27 @@ pub async fn translation_view(
 
     let path = path.into_inner();
 
-    web::block(move || {
+    if accepts_ndjson(&req) {
+        let pool = pool.into_inner();
+        let block_pool = pool.clone();
+
+        // The translation is checked before the response is started
+        let translation = web::block(move || {
+            let mut conn = block_pool.get().unwrap();
+
+            quran_translations
+                .filter(translation_uuid.eq(path))
+                .get_result::<Translation>(&mut conn)
+        })
+        .await
+        .unwrap()?;
+
+        return stream_ndjson(&stream_limit, move |writer| {
+            stream_translation(pool, translation, query, writer)
+        });
+    }
+
+    let translation = web::block(move || {
         let mut conn = pool.get().unwrap();
 
         // Get the single translation from the database
@@ -48,7 +96,7 @@ pub async fn translation_view(
             .filter(translation_uuid.eq(path))
             .get_result(&mut conn)?;
 
-        let mushaf_uuid: Uuid = quran_mushafs
+        let mut mushaf_uuid: Uuid = quran_mushafs
             .filter(mushaf_table_id.eq(translation.mushaf_id))
             .select(mushaf_table_uuid)
             .get_result(&mut conn)?;
@@ -65,11 +113,27 @@ pub async fn translation_view(
             ))
             .get_result::<(Uuid, String, Option<String>, Option<String>)>(&mut conn)?;
 
+        let aligned_mushaf = match query.mushaf_uuid {
+            Some(uuid) if uuid != mushaf_uuid => {
+                let id: i32 = quran_mushafs
+                    .filter(mushaf_table_uuid.eq(uuid))
+                    .select(mushaf_table_id)
+                    .get_result(&mut conn)?;
+
+                mushaf_uuid = uuid;
+
+                Some(id)
+            }
+
+            _ => None,
+        };
+
         let mut ayahs = quran_surahs
             .inner_join(quran_ayahs.left_outer_join(quran_translations_ayahs))
             .internal_into_boxed();
 
-        if let Some(uuid) = query.surah_uuid {
+        // The surah of an aligned mushaf is filtered after the alignment
+        if let (Some(uuid), None) = (query.surah_uuid, aligned_mushaf) {
             ayahs = ayahs.filter(surah_table_uuid.eq(uuid));
         }
 
@@ -80,7 +144,7 @@ pub async fn translation_view(
                     .eq(translation.id)
                     .or(translation_id.is_null()),
             )
-            .order(ayah_number.asc())
+            .order((surah_number.asc(), ayah_number.asc()))
             .select((
                 translation_ayah.nullable(),
                 ayah_uuid,
@@ -89,32 +153,45 @@ pub async fn translation_view(
                 translation_ayah_uuid.nullable(),
                 translation_ayah_bismillah.nullable(),
             ))
-            .get_results::<(Option<String>, Uuid, i32, i32, Option<Uuid>, Option<String>)>(
+            .get_results::<TranslationRow>(&mut conn)?;
+
+        let (result_ayahs, mut status) = match aligned_mushaf {
+            Some(target_mushaf) => align_translation(
                 &mut conn,
-            )?;
+                translation.mushaf_id,
+                target_mushaf,
+                query.surah_uuid,
+                result,
+            )?,
+
+            None => {
+                let mut result_ayahs = vec![];
+                let mut status = TranslationStatus::Ok;
 
-        let mut result_ayahs = vec![];
-        let mut status = TranslationStatus::Ok;
+                for (text, a_uuid, a_number, s_number, text_uuid, bismillah) in result {
+                    if text_uuid.is_none() {
+                        status = TranslationStatus::Incomplete;
+                    }
+                    result_ayahs.push(TranslationAyah {
+                        uuid: a_uuid,
+                        text,
+                        surah_number: s_number as u32,
+                        number: a_number as u32,
+                        text_uuid,
+                        bismillah,
+                        text_on: None,
+                    });
+                }
 
-        for (text, a_uuid, a_number, s_number, text_uuid, bismillah) in result {
-            if text_uuid.is_none() {
-                status = TranslationStatus::Incomplete;
+                (result_ayahs, status)
             }
-            result_ayahs.push(TranslationAyah {
-                uuid: a_uuid,
-                text,
-                surah_number: s_number as u32,
-                number: a_number as u32,
-                text_uuid,
-                bismillah,
-            });
-        }
+        };
 
         if matches!(status, TranslationStatus::Ok) && !translation.approved {
             status = TranslationStatus::NotApproved;
         }
 
-        Ok(web::Json(ViewableTranslation {
+        Ok::<_, RouterError>(ViewableTranslation {
             ayahs: result_ayahs,
             status,
             source: translation.source,
@@ -127,8 +204,222 @@ pub async fn translation_view(
                 first_name: translator.2,
                 last_name: translator.3,
             },
-        }))
+        })
     })
     .await
-    .unwrap()
+    .unwrap()?;
+
+    Ok(HttpResponse::Ok().json(translation))
+}
+
+/// Streams the ayahs of the translation as they are read
+///
+/// Aligned translations are mapped in memory before they are sent
+fn stream_translation(
+    pool: std::sync::Arc<DbPool>,
+    translation: Translation,
+    query: TranslationViewQuery,
+    writer: &NdjsonWriter,
+) -> Result<(), RouterError> {
+    use crate::schema::quran_ayahs::dsl::{ayah_number, quran_ayahs, uuid as ayah_uuid};
+    use crate::schema::quran_mushafs::dsl::{
+        id as mushaf_table_id, quran_mushafs, uuid as mushaf_table_uuid,
+    };
+    use crate::schema::quran_surahs::dsl::{
+        mushaf_id as surah_mushaf_id, number as surah_number, quran_surahs,
+        uuid as surah_table_uuid,
+    };
+    use crate::schema::quran_translations_ayahs::dsl::{
+        bismillah as translation_ayah_bismillah, quran_translations_ayahs,
+        text as translation_ayah, translation_id, uuid as translation_ayah_uuid,
+    };
+
+    let mut conn = pool.get()?;
+
+    let aligned_mushaf = match query.mushaf_uuid {
+        Some(uuid) => Some(
+            quran_mushafs
+                .filter(mushaf_table_uuid.eq(uuid))
+                .select(mushaf_table_id)
+                .get_result::<i32>(&mut conn)?,
+        )
+        .filter(|id| *id != translation.mushaf_id),
+
+        None => None,
+    };
+
+    let mut ayahs = quran_surahs
+        .inner_join(quran_ayahs.left_outer_join(quran_translations_ayahs))
+        .internal_into_boxed();
+
+    if let (Some(uuid), None) = (query.surah_uuid, aligned_mushaf) {
+        ayahs = ayahs.filter(surah_table_uuid.eq(uuid));
+    }
+
+    let ayahs = ayahs
+        .filter(surah_mushaf_id.eq(translation.mushaf_id))
+        .filter(
+            translation_id
+                .eq(translation.id)
+                .or(translation_id.is_null()),
+        )
+        .order((surah_number.asc(), ayah_number.asc()))
+        .select((
+            translation_ayah.nullable(),
+            ayah_uuid,
+            ayah_number,
+            surah_number,
+            translation_ayah_uuid.nullable(),
+            translation_ayah_bismillah.nullable(),
+        ));
+
+    if let Some(target_mushaf) = aligned_mushaf {
+        let rows = ayahs.get_results::<TranslationRow>(&mut conn)?;
+        let (result_ayahs, _) = align_translation(
+            &mut conn,
+            translation.mushaf_id,
+            target_mushaf,
+            query.surah_uuid,
+            rows,
+        )?;
+
+        for ayah in result_ayahs {
+            if !writer.write(&ayah) {
+                break;
+            }
+        }
+
+        return Ok(());
+    }
+
+    let rows = ayahs.load_iter::<TranslationRow, PgRowByRowLoadingMode>(&mut conn)?;
+
+    for row in rows {
+        let (text, a_uuid, a_number, s_number, text_uuid, bismillah) = row?;
+
+        let ayah = TranslationAyah {
+            uuid: a_uuid,
+            text,
+            surah_number: s_number as u32,
+            number: a_number as u32,
+            text_uuid,
+            bismillah,
+            text_on: None,
+        };
+
+        if !writer.write(&ayah) {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+/// Maps the translation of the source mushaf to the ayahs of the target mushaf
+///
+/// Texts of the merged ayahs are joined, and the text of a splitted
+/// ayah is shown on its first part, the other parts refer to it with `text_on`.
+/// Target ayahs without any source ayah make the translation incomplete
+fn align_translation(
+    conn: &mut PgConnection,
+    source_mushaf: i32,
+    target_mushaf: i32,
+    surah_uuid: Option<Uuid>,
+    rows: Vec<TranslationRow>,
+) -> Result<(Vec<TranslationAyah>, TranslationStatus), RouterError> {
+    use crate::schema::quran_ayahs::dsl::{ayah_number, quran_ayahs, uuid as ayah_uuid};
+    use crate::schema::quran_surahs::dsl::{
+        mushaf_id as surah_mushaf_id, number as surah_number, quran_surahs,
+        uuid as surah_table_uuid,
+    };
+
+    let alignment = Alignment::load(conn, source_mushaf, target_mushaf)?;
+
+    // target verse key -> parts of the source ayahs
+    let mut aligned: HashMap<VerseKey, Vec<AlignedPart>> = HashMap::new();
+
+    for (text, _, a_number, s_number, text_uuid, bismillah) in rows {
+        let key = VerseKey {
+            surah: s_number,
+            ayah: a_number,
+        };
+
+        let parts = alignment.translate(key);
+        let Some((first_part, other_parts)) = parts.split_first() else {
+            continue;
+        };
+
+        aligned
+            .entry(first_part.key)
+            .or_default()
+            .push(AlignedPart::Text((text, text_uuid, bismillah)));
+
+        for part in other_parts.iter().filter(|part| part.key != first_part.key) {
+            aligned
+                .entry(part.key)
+                .or_default()
+                .push(AlignedPart::Continued(first_part.key));
+        }
+    }
+
+    let mut target_ayahs = quran_surahs
+        .inner_join(quran_ayahs)
+        .filter(surah_mushaf_id.eq(target_mushaf))
+        .into_boxed();
+
+    if let Some(uuid) = surah_uuid {
+        target_ayahs = target_ayahs.filter(surah_table_uuid.eq(uuid));
+    }
+
+    let target_ayahs = target_ayahs
+        .order((surah_number.asc(), ayah_number.asc()))
+        .select((ayah_uuid, ayah_number, surah_number))
+        .get_results::<(Uuid, i32, i32)>(conn)?;
+
+    let mut result_ayahs = vec![];
+    let mut status = TranslationStatus::Ok;
+
+    for (a_uuid, a_number, s_number) in target_ayahs {
+        let parts = aligned
+            .remove(&VerseKey {
+                surah: s_number,
+                ayah: a_number,
+            })
+            .unwrap_or_default();
+
+        let texts: Vec<&AlignedText> = parts
+            .iter()
+            .filter_map(|part| match part {
+                AlignedPart::Text(text) => Some(text),
+                AlignedPart::Continued(_) => None,
+            })
+            .collect();
+
+        let text_on = parts.iter().find_map(|part| match part {
+            AlignedPart::Continued(first_part) => Some(*first_part),
+            AlignedPart::Text(_) => None,
+        });
+
+        // No source ayah is on this ayah, or a source ayah is not translated
+        if parts.is_empty() || texts.iter().any(|(_, text_uuid, _)| text_uuid.is_none()) {
+            status = TranslationStatus::Incomplete;
+        }
+
+        let joined: Vec<&str> = texts
+            .iter()
+            .filter_map(|(text, _, _)| text.as_deref())
+            .collect();
+
+        result_ayahs.push(TranslationAyah {
+            uuid: a_uuid,
+            text: (!joined.is_empty()).then(|| joined.join(" ")),
+            surah_number: s_number as u32,
+            number: a_number as u32,
+            text_uuid: texts.iter().find_map(|(_, text_uuid, _)| *text_uuid),
+            bismillah: texts.iter().find_map(|(_, _, bismillah)| bismillah.clone()),
+            text_on,
+        });
+    }
+
+    Ok((result_ayahs, status))
 }
@@ -10,6 +10,74 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    app_bookmarks (id) {
+        id -> Int4,
+        uuid -> Uuid,
+        account_id -> Int4,
+        ayah_id -> Int4,
+        note -> Nullable<Text>,
+        tags -> Array<Nullable<Text>>,
+        #[max_length = 20]
+        colour -> Nullable<Varchar>,
+        deleted_at -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+        synced_transaction -> Int8,
+    }
+}
+
+diesel::table! {
+    app_changes (id) {
+        id -> Int8,
+        transaction_id -> Int8,
+        #[max_length = 64]
+        table_name -> Varchar,
+        #[max_length = 10]
+        operation -> Varchar,
+        row_uuid -> Uuid,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    app_collections (id) {
+        id -> Int4,
+        uuid -> Uuid,
+        creator_user_id -> Int4,
+        owner_account_id -> Int4,
+        organization_account_id -> Nullable<Int4>,
+        #[max_length = 200]
+        name -> Varchar,
+        description -> Nullable<Text>,
+        public -> Bool,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    app_collections_ayahs (id) {
+        id -> Int4,
+        uuid -> Uuid,
+        creator_user_id -> Int4,
+        collection_id -> Int4,
+        position -> Int4,
+        first_ayah_id -> Int4,
+        last_ayah_id -> Int4,
+        note -> Nullable<Text>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    app_content_changes (transaction_id) {
+        transaction_id -> Int8,
+        created_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     app_emails (id) {
         id -> Int4,
@@ -60,6 +128,45 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    app_hifz_mistakes (id) {
+        id -> Int4,
+        review_id -> Int4,
+        word_id -> Int4,
+        mistakes -> Int4,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    app_hifz_ranges (id) {
+        id -> Int4,
+        uuid -> Uuid,
+        account_id -> Int4,
+        first_ayah_id -> Int4,
+        last_ayah_id -> Int4,
+        repetitions -> Int4,
+        interval_days -> Int4,
+        ease_factor -> Float8,
+        due_on -> Date,
+        last_reviewed_at -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    app_hifz_reviews (id) {
+        id -> Int4,
+        uuid -> Uuid,
+        range_id -> Int4,
+        grade -> Int4,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     app_organization_names (id) {
         id -> Int4,
@@ -141,6 +248,18 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    app_reading_positions (id) {
+        id -> Int4,
+        uuid -> Uuid,
+        account_id -> Int4,
+        mushaf_id -> Int4,
+        ayah_id -> Int4,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     app_tokens (id) {
         id -> Int4,
@@ -223,6 +342,21 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    quran_ayahs_relations (id) {
+        id -> Int4,
+        uuid -> Uuid,
+        creator_user_id -> Int4,
+        ayah_id -> Int4,
+        related_ayah_id -> Int4,
+        #[max_length = 50]
+        relation_type -> Varchar,
+        source -> Nullable<Text>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     quran_mushafs (id) {
         id -> Int4,
@@ -239,6 +373,132 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    quran_mushafs_alignments (id) {
+        id -> Int4,
+        uuid -> Uuid,
+        creator_user_id -> Int4,
+        source_mushaf_id -> Int4,
+        source_ayah_id -> Int4,
+        source_word_from -> Nullable<Int4>,
+        source_word_to -> Nullable<Int4>,
+        target_mushaf_id -> Int4,
+        target_ayah_id -> Int4,
+        target_word_from -> Nullable<Int4>,
+        target_word_to -> Nullable<Int4>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    quran_mushafs_checksums (id) {
+        id -> Int4,
+        uuid -> Uuid,
+        creator_user_id -> Int4,
+        mushaf_id -> Int4,
+        surah_number -> Nullable<Int4>,
+        ayah_number -> Nullable<Int4>,
+        #[max_length = 64]
+        checksum -> Varchar,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    quran_mushafs_lines (id) {
+        id -> Int4,
+        uuid -> Uuid,
+        creator_user_id -> Int4,
+        mushaf_id -> Int4,
+        page -> Int4,
+        line_number -> Int4,
+        #[max_length = 20]
+        line_type -> Varchar,
+        surah_number -> Nullable<Int4>,
+        first_word_id -> Nullable<Int4>,
+        last_word_id -> Nullable<Int4>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    quran_plans (id) {
+        id -> Int4,
+        uuid -> Uuid,
+        creator_user_id -> Int4,
+        mushaf_id -> Int4,
+        days -> Int4,
+        #[max_length = 20]
+        snap -> Varchar,
+        #[max_length = 256]
+        breaker_name -> Nullable<Varchar>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    quran_plans_days (id) {
+        id -> Int4,
+        uuid -> Uuid,
+        creator_user_id -> Int4,
+        plan_id -> Int4,
+        day -> Int4,
+        first_ayah_id -> Int4,
+        last_ayah_id -> Int4,
+        words -> Int4,
+        completed_at -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    quran_reciters (id) {
+        id -> Int4,
+        uuid -> Uuid,
+        creator_user_id -> Int4,
+        mushaf_id -> Int4,
+        #[max_length = 200]
+        name -> Varchar,
+        #[max_length = 50]
+        style -> Nullable<Varchar>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    quran_reciters_ayahs (id) {
+        id -> Int4,
+        uuid -> Uuid,
+        creator_user_id -> Int4,
+        reciter_id -> Int4,
+        ayah_id -> Int4,
+        path -> Text,
+        duration_ms -> Nullable<Int4>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    quran_reciters_segments (id) {
+        id -> Int4,
+        uuid -> Uuid,
+        creator_user_id -> Int4,
+        reciter_ayah_id -> Int4,
+        word_id -> Int4,
+        start_ms -> Int4,
+        end_ms -> Int4,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     quran_surahs (id) {
         id -> Int4,
@@ -256,6 +516,23 @@ diesel::table! {
         search_terms -> Nullable<Array<Nullable<Text>>>,
         created_at -> Timestamptz,
         updated_at -> Timestamptz,
+        revelation_order -> Nullable<Int4>,
+        ruku_count -> Nullable<Int4>,
+    }
+}
+
+diesel::table! {
+    quran_surahs_names (id) {
+        id -> Int4,
+        uuid -> Uuid,
+        creator_user_id -> Int4,
+        surah_id -> Int4,
+        #[max_length = 3]
+        language -> Varchar,
+        pronunciation -> Nullable<Text>,
+        transliteration -> Nullable<Text>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
     }
 }
 
@@ -317,9 +594,59 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    quran_words_renditions (id) {
+        id -> Int4,
+        uuid -> Uuid,
+        creator_user_id -> Int4,
+        word_id -> Int4,
+        #[max_length = 50]
+        script -> Varchar,
+        text -> Text,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    quran_words_tajweed (id) {
+        id -> Int4,
+        uuid -> Uuid,
+        creator_user_id -> Int4,
+        word_id -> Int4,
+        #[max_length = 50]
+        rule -> Varchar,
+        start_index -> Int4,
+        end_index -> Int4,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    quran_words_transliterations (id) {
+        id -> Int4,
+        uuid -> Uuid,
+        creator_user_id -> Int4,
+        word_id -> Int4,
+        text -> Text,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::joinable!(app_bookmarks -> app_accounts (account_id));
+diesel::joinable!(app_bookmarks -> quran_ayahs (ayah_id));
+diesel::joinable!(app_collections -> app_users (creator_user_id));
+diesel::joinable!(app_collections_ayahs -> app_collections (collection_id));
+diesel::joinable!(app_collections_ayahs -> app_users (creator_user_id));
 diesel::joinable!(app_emails -> app_accounts (account_id));
 diesel::joinable!(app_emails -> app_users (creator_user_id));
 diesel::joinable!(app_employees -> app_users (creator_user_id));
+diesel::joinable!(app_hifz_mistakes -> app_hifz_reviews (review_id));
+diesel::joinable!(app_hifz_mistakes -> quran_words (word_id));
+diesel::joinable!(app_hifz_ranges -> app_accounts (account_id));
+diesel::joinable!(app_hifz_reviews -> app_hifz_ranges (range_id));
 diesel::joinable!(app_organization_names -> app_accounts (account_id));
 diesel::joinable!(app_organization_names -> app_users (creator_user_id));
 diesel::joinable!(app_organizations -> app_accounts (account_id));
@@ -329,6 +656,9 @@ diesel::joinable!(app_permission_conditions -> app_users (creator_user_id));
 diesel::joinable!(app_permissions -> app_accounts (account_id));
 diesel::joinable!(app_permissions -> app_users (creator_user_id));
 diesel::joinable!(app_phrase_translations -> app_phrases (phrase_id));
+diesel::joinable!(app_reading_positions -> app_accounts (account_id));
+diesel::joinable!(app_reading_positions -> quran_ayahs (ayah_id));
+diesel::joinable!(app_reading_positions -> quran_mushafs (mushaf_id));
 diesel::joinable!(app_tokens -> app_accounts (account_id));
 diesel::joinable!(app_user_names -> app_accounts (account_id));
 diesel::joinable!(app_user_names -> app_users (creator_user_id));
@@ -338,9 +668,29 @@ diesel::joinable!(quran_ayahs -> quran_surahs (surah_id));
 diesel::joinable!(quran_ayahs_breakers -> app_accounts (owner_account_id));
 diesel::joinable!(quran_ayahs_breakers -> app_users (creator_user_id));
 diesel::joinable!(quran_ayahs_breakers -> quran_ayahs (ayah_id));
+diesel::joinable!(quran_ayahs_relations -> app_users (creator_user_id));
 diesel::joinable!(quran_mushafs -> app_users (creator_user_id));
+diesel::joinable!(quran_mushafs_alignments -> app_users (creator_user_id));
+diesel::joinable!(quran_mushafs_checksums -> app_users (creator_user_id));
+diesel::joinable!(quran_mushafs_checksums -> quran_mushafs (mushaf_id));
+diesel::joinable!(quran_mushafs_lines -> app_users (creator_user_id));
+diesel::joinable!(quran_mushafs_lines -> quran_mushafs (mushaf_id));
+diesel::joinable!(quran_plans -> app_users (creator_user_id));
+diesel::joinable!(quran_plans -> quran_mushafs (mushaf_id));
+diesel::joinable!(quran_plans_days -> app_users (creator_user_id));
+diesel::joinable!(quran_plans_days -> quran_plans (plan_id));
+diesel::joinable!(quran_reciters -> app_users (creator_user_id));
+diesel::joinable!(quran_reciters -> quran_mushafs (mushaf_id));
+diesel::joinable!(quran_reciters_ayahs -> app_users (creator_user_id));
+diesel::joinable!(quran_reciters_ayahs -> quran_ayahs (ayah_id));
+diesel::joinable!(quran_reciters_ayahs -> quran_reciters (reciter_id));
+diesel::joinable!(quran_reciters_segments -> app_users (creator_user_id));
+diesel::joinable!(quran_reciters_segments -> quran_reciters_ayahs (reciter_ayah_id));
+diesel::joinable!(quran_reciters_segments -> quran_words (word_id));
 diesel::joinable!(quran_surahs -> app_users (creator_user_id));
 diesel::joinable!(quran_surahs -> quran_mushafs (mushaf_id));
+diesel::joinable!(quran_surahs_names -> app_users (creator_user_id));
+diesel::joinable!(quran_surahs_names -> quran_surahs (surah_id));
 diesel::joinable!(quran_translations -> app_accounts (translator_account_id));
 diesel::joinable!(quran_translations -> app_users (creator_user_id));
 diesel::joinable!(quran_translations -> quran_mushafs (mushaf_id));
@@ -352,28 +702,56 @@ diesel::joinable!(quran_words -> quran_ayahs (ayah_id));
 diesel::joinable!(quran_words_breakers -> app_accounts (owner_account_id));
 diesel::joinable!(quran_words_breakers -> app_users (creator_user_id));
 diesel::joinable!(quran_words_breakers -> quran_words (word_id));
+diesel::joinable!(quran_words_renditions -> app_users (creator_user_id));
+diesel::joinable!(quran_words_renditions -> quran_words (word_id));
+diesel::joinable!(quran_words_tajweed -> app_users (creator_user_id));
+diesel::joinable!(quran_words_tajweed -> quran_words (word_id));
+diesel::joinable!(quran_words_transliterations -> app_users (creator_user_id));
+diesel::joinable!(quran_words_transliterations -> quran_words (word_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
     app_accounts,
+    app_bookmarks,
+    app_changes,
+    app_collections,
+    app_collections_ayahs,
+    app_content_changes,
     app_emails,
     app_employees,
     app_error_logs,
+    app_hifz_mistakes,
+    app_hifz_ranges,
+    app_hifz_reviews,
     app_organization_names,
     app_organizations,
     app_permission_conditions,
     app_permissions,
     app_phrase_translations,
     app_phrases,
+    app_reading_positions,
     app_tokens,
     app_user_names,
     app_users,
     app_verify_codes,
     quran_ayahs,
     quran_ayahs_breakers,
+    quran_ayahs_relations,
     quran_mushafs,
+    quran_mushafs_alignments,
+    quran_mushafs_checksums,
+    quran_mushafs_lines,
+    quran_plans,
+    quran_plans_days,
+    quran_reciters,
+    quran_reciters_ayahs,
+    quran_reciters_segments,
     quran_surahs,
+    quran_surahs_names,
     quran_translations,
     quran_translations_ayahs,
     quran_words,
     quran_words_breakers,
+    quran_words_renditions,
+    quran_words_tajweed,
+    quran_words_transliterations,
 );
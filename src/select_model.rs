@@ -1,5 +1,5 @@
 use crate::{
-    models::{Organization, User},
+    models::{Collection, Organization, User},
     select_model, DbPool,
 };
 
@@ -7,6 +7,7 @@ use crate::{
 use actix_web::web::block;
 use diesel::prelude::*;
 use async_trait::async_trait;
+use uuid::Uuid;
 
 #[async_trait]
 pub trait SelectModel {
@@ -15,5 +16,33 @@ pub trait SelectModel {
         Self: Sized;
 }
 
+#[async_trait]
+pub trait SelectModelByUuid {
+    /// Returns None if the uuid is not valid or not found
+    async fn from_uuid(conn: DbPool, uuid: &str) -> Self
+    where
+        Self: Sized;
+}
+
 select_model!(Organization, app_organizations);
 select_model!(User, app_users);
+
+#[async_trait]
+impl SelectModelByUuid for Option<Collection> {
+    async fn from_uuid(conn: DbPool, uuid: &str) -> Self {
+        use crate::schema::app_collections::dsl::{app_collections, uuid as collection_uuid};
+
+        let target_uuid = Uuid::parse_str(uuid).ok()?;
+        let mut conn = conn.get().unwrap();
+
+        block(move || {
+            app_collections
+                .filter(collection_uuid.eq(target_uuid))
+                .get_result(&mut conn)
+                .optional()
+                .unwrap()
+        })
+        .await
+        .unwrap()
+    }
+}
@@ -0,0 +1,133 @@
+//! TF-IDF similarity of short texts, used to find the similar ayahs
+
+use std::collections::HashMap;
+
+/// Terms of more than this ratio of the documents are too common to relate them
+const MAX_DOCUMENT_FREQUENCY: f64 = 0.1;
+
+fn tokenize(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|token| token.chars().count() > 1)
+        .map(|token| token.to_lowercase())
+}
+
+/// Returns the `limit` most similar documents of every document
+///
+/// Result is `(document, similar document, score)` by the document index,
+/// scores are cosine similarity of the TF-IDF vectors and at least `min_score`
+pub fn similar_documents(
+    documents: &[&str],
+    limit: usize,
+    min_score: f64,
+) -> Vec<(usize, usize, f64)> {
+    let mut terms: HashMap<String, usize> = HashMap::new();
+    let mut document_frequency: Vec<usize> = vec![];
+
+    // Term frequency of every document
+    let counts: Vec<HashMap<usize, f64>> = documents
+        .iter()
+        .map(|document| {
+            let mut count: HashMap<usize, f64> = HashMap::new();
+
+            for token in tokenize(document) {
+                let next_id = terms.len();
+                let term = *terms.entry(token).or_insert(next_id);
+
+                if term == document_frequency.len() {
+                    document_frequency.push(0);
+                }
+
+                *count.entry(term).or_default() += 1.0;
+            }
+
+            for term in count.keys() {
+                document_frequency[*term] += 1;
+            }
+
+            count
+        })
+        .collect();
+
+    let total = documents.len() as f64;
+    let max_frequency = (total * MAX_DOCUMENT_FREQUENCY).max(2.0);
+
+    // Normalized weights of the documents, and the documents of every term
+    let mut postings: Vec<Vec<(usize, f64)>> = vec![vec![]; document_frequency.len()];
+    let mut vectors: Vec<Vec<(usize, f64)>> = Vec::with_capacity(counts.len());
+
+    for (document, count) in counts.into_iter().enumerate() {
+        let mut vector: Vec<(usize, f64)> = count
+            .into_iter()
+            .filter(|(term, _)| document_frequency[*term] as f64 <= max_frequency)
+            .map(|(term, tf)| (term, tf * (total / document_frequency[term] as f64).ln()))
+            .filter(|(_, weight)| *weight > 0.0)
+            .collect();
+
+        let norm = vector.iter().map(|(_, w)| w * w).sum::<f64>().sqrt();
+
+        for (term, weight) in vector.iter_mut() {
+            *weight /= norm;
+            postings[*term].push((document, *weight));
+        }
+
+        vectors.push(vector);
+    }
+
+    let mut result = vec![];
+
+    for (document, vector) in vectors.iter().enumerate() {
+        let mut scores: HashMap<usize, f64> = HashMap::new();
+
+        for (term, weight) in vector {
+            for (other, other_weight) in &postings[*term] {
+                if *other != document {
+                    *scores.entry(*other).or_default() += weight * other_weight;
+                }
+            }
+        }
+
+        let mut scores: Vec<(usize, f64)> = scores
+            .into_iter()
+            .filter(|(_, score)| *score >= min_score)
+            .collect();
+
+        scores.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+
+        result.extend(
+            scores
+                .into_iter()
+                .take(limit)
+                .map(|(other, score)| (document, other, score)),
+        );
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_similar_documents() {
+        let documents = [
+            "Indeed, Allah is Forgiving and Merciful",
+            "And Allah is Forgiving and Merciful to the believers",
+            "The fire of Hell and its punishment",
+            "Those who disbelieve will have the punishment of the fire",
+        ];
+
+        let similar = similar_documents(&documents, 1, 0.1);
+        let pairs: Vec<(usize, usize)> = similar.iter().map(|(a, b, _)| (*a, *b)).collect();
+
+        assert_eq!(pairs, vec![(0, 1), (1, 0), (2, 3), (3, 2)]);
+    }
+
+    #[test]
+    fn test_similar_documents_min_score() {
+        let documents = ["mercy and forgiveness", "the fire", "the garden"];
+
+        assert!(similar_documents(&documents, 5, 0.5).is_empty());
+        assert_eq!(similar_documents(&documents, 5, 0.1).len(), 2);
+    }
+}
@@ -0,0 +1,322 @@
+//! Word and letter statistics of the mushafs
+//!
+//! Statistics are computed from every word of the mushaf,
+//! so they are cached by the content version of the request (see `http_cache`)
+//!
+//! Words are counted by their letters, not by their roots. The words have no
+//! morphology data (roots or lemmas) and a root can't be derived from the text alone,
+//! so the root counts are left until the words have their roots.
+
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, HashMap},
+    sync::{Arc, Mutex},
+};
+
+use diesel::prelude::*;
+use serde::Serialize;
+
+use crate::verse_key::VerseKey;
+
+/// Returns true if the char is a letter, not a vowel or recitation mark
+fn is_letter(c: char) -> bool {
+    c.is_alphabetic()
+        && !matches!(c,
+            '\u{0610}'..='\u{061A}'
+            | '\u{0640}'
+            | '\u{064B}'..='\u{065F}'
+            | '\u{0670}'
+            | '\u{06D6}'..='\u{06ED}'
+            | '\u{08D3}'..='\u{08FF}'
+        )
+}
+
+/// Number of the letters of the word
+pub fn letter_count(word: &str) -> u32 {
+    word.chars().filter(|c| is_letter(*c)).count() as u32
+}
+
+/// The word without its marks, so the same words with different vowels are counted together
+pub fn normalize_word(word: &str) -> String {
+    word.chars().filter(|c| is_letter(*c)).collect()
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct AyahStatistics {
+    pub key: VerseKey,
+    pub words: u32,
+    pub letters: u32,
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct SurahStatistics {
+    pub number: i32,
+    pub ayahs: u32,
+    pub words: u32,
+    pub letters: u32,
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct WordFrequency {
+    pub word: String,
+    pub count: u32,
+}
+
+/// Statistics of the whole mushaf
+#[derive(Debug, Default)]
+pub struct MushafStatistics {
+    /// Ordered by the verse key
+    pub ayahs: Vec<AyahStatistics>,
+
+    /// Ordered by the surah number
+    pub surahs: Vec<SurahStatistics>,
+
+    /// Ordered by the count, most frequent first
+    pub words: Vec<WordFrequency>,
+}
+
+impl MushafStatistics {
+    /// Computes the statistics from the words of every ayah
+    pub fn compute(ayah_words: &BTreeMap<VerseKey, Vec<String>>) -> Self {
+        let mut ayahs = Vec::with_capacity(ayah_words.len());
+        let mut surahs: Vec<SurahStatistics> = vec![];
+        let mut frequency: HashMap<String, u32> = HashMap::new();
+
+        for (key, words) in ayah_words {
+            let ayah = AyahStatistics {
+                key: *key,
+                words: words.len() as u32,
+                letters: words.iter().map(|word| letter_count(word)).sum(),
+            };
+
+            match surahs.last_mut() {
+                Some(surah) if surah.number == key.surah => {
+                    surah.ayahs += 1;
+                    surah.words += ayah.words;
+                    surah.letters += ayah.letters;
+                }
+
+                _ => surahs.push(SurahStatistics {
+                    number: key.surah,
+                    ayahs: 1,
+                    words: ayah.words,
+                    letters: ayah.letters,
+                }),
+            }
+
+            for word in words {
+                let word = normalize_word(word);
+
+                if !word.is_empty() {
+                    *frequency.entry(word).or_default() += 1;
+                }
+            }
+
+            ayahs.push(ayah);
+        }
+
+        let mut words: Vec<WordFrequency> = frequency
+            .into_iter()
+            .map(|(word, count)| WordFrequency { word, count })
+            .collect();
+
+        words.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.word.cmp(&b.word)));
+
+        Self {
+            ayahs,
+            surahs,
+            words,
+        }
+    }
+
+    pub fn words(&self) -> u32 {
+        self.surahs.iter().map(|surah| surah.words).sum()
+    }
+
+    pub fn letters(&self) -> u32 {
+        self.surahs.iter().map(|surah| surah.letters).sum()
+    }
+
+    /// The longest ayahs by their words then letters
+    pub fn longest_ayahs(&self, limit: usize) -> Vec<AyahStatistics> {
+        let mut ayahs = self.ayahs.clone();
+        ayahs.sort_by_key(|ayah| (Reverse(ayah.words), Reverse(ayah.letters), ayah.key));
+        ayahs.truncate(limit);
+
+        ayahs
+    }
+
+    /// The shortest ayahs by their words then letters
+    pub fn shortest_ayahs(&self, limit: usize) -> Vec<AyahStatistics> {
+        let mut ayahs = self.ayahs.clone();
+        ayahs.sort_by_key(|ayah| (ayah.words, ayah.letters, ayah.key));
+        ayahs.truncate(limit);
+
+        ayahs
+    }
+
+    /// The longest surahs by their words
+    pub fn longest_surahs(&self, limit: usize) -> Vec<SurahStatistics> {
+        let mut surahs = self.surahs.clone();
+        surahs.sort_by_key(|surah| (Reverse(surah.words), Reverse(surah.letters), surah.number));
+        surahs.truncate(limit);
+
+        surahs
+    }
+
+    /// The shortest surahs by their words
+    pub fn shortest_surahs(&self, limit: usize) -> Vec<SurahStatistics> {
+        let mut surahs = self.surahs.clone();
+        surahs.sort_by_key(|surah| (surah.words, surah.letters, surah.number));
+        surahs.truncate(limit);
+
+        surahs
+    }
+
+    /// Statistics of the ayahs of the surah
+    pub fn surah_ayahs(&self, surah: i32) -> Vec<AyahStatistics> {
+        self.ayahs
+            .iter()
+            .filter(|ayah| ayah.key.surah == surah)
+            .cloned()
+            .collect()
+    }
+}
+
+/// Computed statistics by the mushaf id, with the content version they are computed at
+#[derive(Debug, Default)]
+pub struct StatisticsCache {
+    entries: Mutex<HashMap<i32, (i64, Arc<MushafStatistics>)>>,
+}
+
+impl StatisticsCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the cached statistics of the mushaf at the version, or computes them
+    ///
+    /// Requests without a version, or older than the cached statistics, are computed
+    /// and not cached. The lock is not held while computing, so two requests
+    /// may compute the same mushaf at the same time
+    pub fn get_or_compute<F, E>(
+        &self,
+        mushaf_id: i32,
+        version: Option<i64>,
+        compute: F,
+    ) -> Result<Arc<MushafStatistics>, E>
+    where
+        F: FnOnce() -> Result<MushafStatistics, E>,
+    {
+        let cached_version = match (version, self.entries.lock().unwrap().get(&mushaf_id)) {
+            (Some(version), Some((cached_version, statistics))) if *cached_version == version => {
+                return Ok(statistics.clone());
+            }
+
+            (_, cached) => cached.map(|(cached_version, _)| *cached_version),
+        };
+
+        let statistics = Arc::new(compute()?);
+
+        if let Some(version) = version {
+            if cached_version.is_none_or(|cached| cached < version) {
+                self.entries
+                    .lock()
+                    .unwrap()
+                    .insert(mushaf_id, (version, statistics.clone()));
+            }
+        }
+
+        Ok(statistics)
+    }
+}
+
+/// Returns the number of ayahs of every surah, with a single query
+pub fn surahs_ayah_count(
+    conn: &mut PgConnection,
+    surah_ids: &[i32],
+) -> QueryResult<HashMap<i32, i64>> {
+    use crate::schema::quran_ayahs::dsl::{id as ayah_id, quran_ayahs, surah_id};
+
+    let counts: Vec<(i32, i64)> = quran_ayahs
+        .filter(surah_id.eq_any(surah_ids))
+        .group_by(surah_id)
+        .select((surah_id, diesel::dsl::count(ayah_id)))
+        .get_results(conn)?;
+
+    Ok(counts.into_iter().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(surah: i32, ayah: i32) -> VerseKey {
+        VerseKey { surah, ayah }
+    }
+
+    #[test]
+    fn test_letter_count() {
+        assert_eq!(letter_count("بِسْمِ"), 3);
+        assert_eq!(letter_count("ٱلرَّحْمَٰنِ"), 6);
+        assert_eq!(normalize_word("ٱللَّهِ"), "ٱلله");
+    }
+
+    #[test]
+    fn test_compute_statistics() {
+        let mut ayahs = BTreeMap::new();
+        ayahs.insert(key(1, 1), vec!["بِسْمِ".to_string(), "ٱللَّهِ".to_string()]);
+        ayahs.insert(key(1, 2), vec!["ٱللَّهُ".to_string()]);
+        ayahs.insert(key(2, 1), vec!["الٓمٓ".to_string()]);
+
+        let statistics = MushafStatistics::compute(&ayahs);
+
+        assert_eq!(statistics.words(), 4);
+        assert_eq!(statistics.surahs.len(), 2);
+        assert_eq!(statistics.surahs[0].ayahs, 2);
+        assert_eq!(
+            statistics.words[0],
+            WordFrequency {
+                word: "ٱلله".to_string(),
+                count: 2
+            }
+        );
+        assert_eq!(statistics.longest_ayahs(1)[0].key, key(1, 1));
+        assert_eq!(statistics.shortest_surahs(1)[0].number, 2);
+        assert_eq!(statistics.surah_ayahs(1).len(), 2);
+    }
+
+    #[test]
+    fn test_cache_by_version() {
+        let cache = StatisticsCache::new();
+        let computed = |words: &str| {
+            let mut ayahs = BTreeMap::new();
+            ayahs.insert(key(1, 1), words.split(' ').map(str::to_string).collect());
+
+            Ok::<_, ()>(MushafStatistics::compute(&ayahs))
+        };
+
+        let first = cache.get_or_compute(1, Some(1), || computed("a")).unwrap();
+        assert_eq!(first.words(), 1);
+
+        // Same version is not computed again
+        let cached = cache
+            .get_or_compute(1, Some(1), || computed("a b"))
+            .unwrap();
+        assert_eq!(cached.words(), 1);
+
+        // A newer version replaces the cached statistics
+        let newer = cache
+            .get_or_compute(1, Some(2), || computed("a b"))
+            .unwrap();
+        assert_eq!(newer.words(), 2);
+
+        // Older or unversioned requests don't replace them
+        cache.get_or_compute(1, Some(1), || computed("a")).unwrap();
+        cache.get_or_compute(1, None, || computed("a")).unwrap();
+        let cached = cache
+            .get_or_compute(1, Some(2), || computed("a b c"))
+            .unwrap();
+        assert_eq!(cached.words(), 2);
+    }
+}
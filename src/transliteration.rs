@@ -0,0 +1,332 @@
+//! Rule based transliteration of the vocalised arabic text to latin
+//!
+//! example: `بِسْمِ ٱللَّهِ ٱلرَّحْمَٰنِ ٱلرَّحِيمِ` -> `bismi llāhi r-raḥmāni r-raḥīmi`
+
+const ALIF: char = 'ا';
+const ALIF_WASLA: char = 'ٱ';
+const ALIF_MADDA: char = 'آ';
+const ALIF_MAQSURA: char = 'ى';
+const LAM: char = 'ل';
+const WAW: char = 'و';
+const YA: char = 'ي';
+const TA_MARBUTA: char = 'ة';
+const SMALL_WAW: char = 'ۥ';
+const SMALL_YA: char = 'ۦ';
+
+/// Letters that the lam of the definite article assimilates into
+const SUN_LETTERS: [char; 14] = [
+    'ت', 'ث', 'د', 'ذ', 'ر', 'ز', 'س', 'ش', 'ص', 'ض', 'ط', 'ظ', 'ل', 'ن',
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Vowel {
+    Fatha,
+    Kasra,
+    Damma,
+    Fathatan,
+    Kasratan,
+    Dammatan,
+}
+
+/// A letter with its marks
+#[derive(Debug, Clone, Copy)]
+struct Letter {
+    letter: char,
+    vowel: Option<Vowel>,
+    shadda: bool,
+    sukun: bool,
+
+    /// Superscript (dagger) alif
+    dagger_alif: bool,
+
+    /// Marked with small high zero, not pronounced
+    silent: bool,
+}
+
+impl Letter {
+    fn new(letter: char) -> Self {
+        Self {
+            letter,
+            vowel: None,
+            shadda: false,
+            sukun: false,
+            dagger_alif: false,
+            silent: false,
+        }
+    }
+
+    /// Letter has no vowel of its own, so it can lengthen the previous one
+    fn is_bare(&self) -> bool {
+        self.vowel.is_none() && !self.shadda
+    }
+}
+
+fn consonant(letter: char) -> Option<&'static str> {
+    Some(match letter {
+        'ء' | 'أ' | 'إ' | 'ؤ' | 'ئ' => "ʾ",
+        'ب' => "b",
+        'ت' => "t",
+        'ث' => "th",
+        'ج' => "j",
+        'ح' => "ḥ",
+        'خ' => "kh",
+        'د' => "d",
+        'ذ' => "dh",
+        'ر' => "r",
+        'ز' => "z",
+        'س' => "s",
+        'ش' => "sh",
+        'ص' => "ṣ",
+        'ض' => "ḍ",
+        'ط' => "ṭ",
+        'ظ' => "ẓ",
+        'ع' => "ʿ",
+        'غ' => "gh",
+        'ف' => "f",
+        'ق' => "q",
+        'ك' | 'ک' => "k",
+        'ل' => "l",
+        'م' => "m",
+        'ن' => "n",
+        'ه' => "h",
+        'و' => "w",
+        'ي' | 'ی' | 'ى' => "y",
+        'ة' => "t",
+        _ => return None,
+    })
+}
+
+fn is_letter(c: char) -> bool {
+    consonant(c).is_some() || matches!(c, ALIF | ALIF_WASLA | ALIF_MADDA | SMALL_WAW | SMALL_YA)
+}
+
+/// Splits the word into letters and applies the marks to them
+fn parse(word: &str) -> Vec<Letter> {
+    let mut letters: Vec<Letter> = vec![];
+
+    for c in word.chars() {
+        if is_letter(c) {
+            letters.push(Letter::new(c));
+            continue;
+        }
+
+        let Some(last) = letters.last_mut() else {
+            continue;
+        };
+
+        match c {
+            '\u{064B}' | '\u{08F0}' => last.vowel = Some(Vowel::Fathatan),
+            '\u{064C}' | '\u{08F1}' => last.vowel = Some(Vowel::Dammatan),
+            '\u{064D}' | '\u{08F2}' => last.vowel = Some(Vowel::Kasratan),
+            '\u{064E}' => last.vowel = Some(Vowel::Fatha),
+            '\u{064F}' => last.vowel = Some(Vowel::Damma),
+            '\u{0650}' => last.vowel = Some(Vowel::Kasra),
+            '\u{0651}' => last.shadda = true,
+            '\u{0652}' | '\u{06E1}' => last.sukun = true,
+            '\u{0670}' => last.dagger_alif = true,
+            '\u{06DF}' | '\u{06E0}' => last.silent = true,
+
+            // Tatweel, madda, waqf and the other recitation marks
+            _ => {}
+        }
+    }
+
+    letters.retain(|l| !l.silent);
+
+    letters
+}
+
+/// Transliterates a single word
+///
+/// `utterance_start` is true if the word is read from its beginning,
+/// so the hamzat al-wasl of the word is pronounced
+pub fn transliterate_word(word: &str, utterance_start: bool) -> String {
+    let mut letters = parse(word);
+    let mut result = String::new();
+    let mut i = 0;
+
+    while i < letters.len() {
+        let letter = letters[i];
+        let next = letters.get(i + 1).copied();
+
+        match letter.letter {
+            ALIF_WASLA | ALIF
+                if letter.vowel.is_none()
+                    && next.is_some_and(|n| n.letter == LAM && n.is_bare())
+                    && letters
+                        .get(i + 2)
+                        .is_some_and(|l| consonant(l.letter).is_some())
+                    && (letter.letter == ALIF_WASLA || i == 0) =>
+            {
+                // The definite article
+                if i != 0 {
+                    result.push('-');
+                } else if utterance_start {
+                    result.push('a');
+                }
+
+                let is_name_of_allah = letters.len() == i + 4
+                    && letters[i + 2].letter == LAM
+                    && letters[i + 3].letter == 'ه';
+
+                let first = &mut letters[i + 2];
+                if is_name_of_allah {
+                    // The alif of the name of Allah is not always written
+                    first.shadda = true;
+                    first.dagger_alif = true;
+                } else if first.letter == LAM {
+                    // Lam of the article joins the lam without a hyphen
+                } else if SUN_LETTERS.contains(&first.letter) {
+                    result.push_str(consonant(first.letter).unwrap());
+                    result.push('-');
+                    first.shadda = false;
+                } else {
+                    result.push_str("l-");
+                }
+
+                i += 2;
+                continue;
+            }
+
+            ALIF_WASLA => {
+                if i == 0 && utterance_start {
+                    let third_vowel = letters.get(i + 2).and_then(|l| l.vowel);
+
+                    result.push(if next.is_some_and(|n| n.letter == LAM && n.shadda) {
+                        // Article written with a single lam, e.g. ٱلَّذِى
+                        'a'
+                    } else if third_vowel == Some(Vowel::Damma) {
+                        'u'
+                    } else {
+                        'i'
+                    });
+                }
+            }
+
+            ALIF_MADDA => {
+                result.push_str(if result.is_empty() { "ā" } else { "ʾā" });
+            }
+
+            // Alif that is not a lengthening of fatha, silent at the end of the word
+            ALIF if letter.vowel.is_none() => {
+                if i + 1 != letters.len() {
+                    result.push('ā');
+                }
+            }
+
+            SMALL_WAW | SMALL_YA => {}
+            ALIF_MAQSURA if letter.is_bare() => {}
+
+            c => {
+                let Some(sound) = consonant(c) else {
+                    i += 1;
+                    continue;
+                };
+
+                let is_hamza = sound == "ʾ";
+
+                // Hamza at the start of the word is not written
+                if !(is_hamza && result.is_empty()) {
+                    let sound = if c == TA_MARBUTA && letter.vowel.is_none() {
+                        "h"
+                    } else {
+                        sound
+                    };
+
+                    result.push_str(sound);
+
+                    if letter.shadda {
+                        result.push_str(sound);
+                    }
+                }
+
+                let vowel = match letter.vowel {
+                    None if c == 'إ' && !letter.sukun => Some(Vowel::Kasra),
+                    vowel => vowel,
+                };
+
+                // The letters which lengthen the vowel
+                let lengthened_by: &[char] = match vowel {
+                    Some(Vowel::Fatha) => &[ALIF, ALIF_MAQSURA],
+                    Some(Vowel::Kasra) => &[YA, ALIF_MAQSURA, SMALL_YA],
+                    Some(Vowel::Damma) => &[WAW, SMALL_WAW],
+                    _ => &[],
+                };
+
+                let is_long = next.is_some_and(|n| {
+                    lengthened_by.contains(&n.letter)
+                        && n.is_bare()
+                        && (!n.sukun || n.letter == YA || n.letter == WAW)
+                });
+
+                match vowel {
+                    _ if letter.dagger_alif => result.push('ā'),
+                    Some(Vowel::Fatha) if is_long => result.push('ā'),
+                    Some(Vowel::Kasra) if is_long => result.push('ī'),
+                    Some(Vowel::Damma) if is_long => result.push('ū'),
+                    Some(Vowel::Fatha) => result.push('a'),
+                    Some(Vowel::Kasra) => result.push('i'),
+                    Some(Vowel::Damma) => result.push('u'),
+                    Some(Vowel::Fathatan) => result.push_str("an"),
+                    Some(Vowel::Kasratan) => result.push_str("in"),
+                    Some(Vowel::Dammatan) => result.push_str("un"),
+                    None => {}
+                }
+
+                if is_long {
+                    i += 1;
+                }
+            }
+        }
+
+        i += 1;
+    }
+
+    result
+}
+
+/// Transliterates the words of an ayah, the ayah is read from its first word
+pub fn transliterate_ayah<'a, I>(words: I) -> Vec<String>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    words
+        .into_iter()
+        .enumerate()
+        .map(|(index, word)| transliterate_word(word, index == 0))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transliterate_bismillah() {
+        assert_eq!(
+            transliterate_ayah(["بِسْمِ", "ٱللَّهِ", "ٱلرَّحْمَٰنِ", "ٱلرَّحِيمِ"]),
+            vec!["bismi", "llāhi", "r-raḥmāni", "r-raḥīmi"]
+        );
+    }
+
+    #[test]
+    fn test_transliterate_article() {
+        assert_eq!(transliterate_word("ٱلْحَمْدُ", true), "al-ḥamdu");
+        assert_eq!(transliterate_word("ٱلْحَمْدُ", false), "l-ḥamdu");
+        assert_eq!(transliterate_word("وَٱلشَّمْسِ", true), "wa-sh-shamsi");
+        assert_eq!(transliterate_word("ٱلْكِتَٰبُ", true), "al-kitābu");
+        assert_eq!(transliterate_word("ٱللَّهُ", true), "allāhu");
+        assert_eq!(transliterate_word("ٱلَّيْلِ", true), "allayli");
+    }
+
+    #[test]
+    fn test_transliterate_vowels() {
+        assert_eq!(transliterate_word("مَٰلِكِ", true), "māliki");
+        assert_eq!(transliterate_word("إِيَّاكَ", true), "iyyāka");
+        assert_eq!(transliterate_word("عَلِيمًا", true), "ʿalīman");
+        assert_eq!(transliterate_word("قَالُوا۟", true), "qālū");
+        assert_eq!(transliterate_word("فِى", true), "fī");
+        assert_eq!(transliterate_word("رَحْمَةٌ", true), "raḥmatun");
+        assert_eq!(transliterate_word("ٱهْدِنَا", true), "ihdinā");
+    }
+}
@@ -0,0 +1,324 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Display,
+    str::FromStr,
+};
+
+use diesel::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::error::RouterError;
+
+/// The `surah:ayah` key of an ayah
+///
+/// example: `2:255`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct VerseKey {
+    pub surah: i32,
+    pub ayah: i32,
+}
+
+/// The `surah:ayah:word` key of a word, word is the position
+/// of the word in the ayah (starts from 1)
+///
+/// example: `2:255:1`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct WordKey {
+    pub surah: i32,
+    pub ayah: i32,
+    pub word: i32,
+}
+
+impl WordKey {
+    pub fn verse(&self) -> VerseKey {
+        VerseKey {
+            surah: self.surah,
+            ayah: self.ayah,
+        }
+    }
+}
+
+/// Parses exactly N `:` separated positive numbers
+fn parse_parts<const N: usize>(value: &str) -> Option<[i32; N]> {
+    value
+        .split(':')
+        .map(|part| part.trim().parse::<i32>().ok().filter(|n| *n > 0))
+        .collect::<Option<Vec<i32>>>()?
+        .try_into()
+        .ok()
+}
+
+fn key_not_valid(value: &str) -> RouterError {
+    RouterError::from_predefined_with_detail("VERSE_KEY_NOT_VALID", value)
+}
+
+impl FromStr for VerseKey {
+    type Err = RouterError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let [surah, ayah] = parse_parts(s).ok_or_else(|| key_not_valid(s))?;
+
+        Ok(Self { surah, ayah })
+    }
+}
+
+impl FromStr for WordKey {
+    type Err = RouterError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let [surah, ayah, word] = parse_parts(s).ok_or_else(|| key_not_valid(s))?;
+
+        Ok(Self { surah, ayah, word })
+    }
+}
+
+impl TryFrom<String> for VerseKey {
+    type Error = RouterError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl TryFrom<String> for WordKey {
+    type Error = RouterError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl Display for VerseKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.surah, self.ayah)
+    }
+}
+
+impl Display for WordKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}:{}", self.surah, self.ayah, self.word)
+    }
+}
+
+impl From<VerseKey> for String {
+    fn from(value: VerseKey) -> Self {
+        value.to_string()
+    }
+}
+
+impl From<WordKey> for String {
+    fn from(value: WordKey) -> Self {
+        value.to_string()
+    }
+}
+
+/// Mushaf and verse key of an ayah
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AyahPosition {
+    pub mushaf_id: i32,
+    pub key: VerseKey,
+}
+
+/// A range must be in a single mushaf and must not be reversed
+pub fn range_is_valid(first: AyahPosition, last: AyahPosition) -> bool {
+    first.mushaf_id == last.mushaf_id && first.key <= last.key
+}
+
+pub fn range_contains(first: AyahPosition, last: AyahPosition, ayah: AyahPosition) -> bool {
+    ayah.mushaf_id == first.mushaf_id && first.key <= ayah.key && ayah.key <= last.key
+}
+
+/// Returns the mushaf and the verse key of the ayahs by their id
+pub fn ayah_positions(
+    conn: &mut PgConnection,
+    ids: impl IntoIterator<Item = i32>,
+) -> QueryResult<HashMap<i32, AyahPosition>> {
+    use crate::schema::quran_ayahs::dsl::{ayah_number, id as ayah_id, quran_ayahs};
+    use crate::schema::quran_surahs::dsl::{mushaf_id, number as surah_number, quran_surahs};
+
+    Ok(quran_ayahs
+        .inner_join(quran_surahs)
+        .filter(ayah_id.eq_any(ids.into_iter().collect::<Vec<i32>>()))
+        .select((ayah_id, mushaf_id, surah_number, ayah_number))
+        .get_results::<(i32, i32, i32, i32)>(conn)?
+        .into_iter()
+        .map(|(id, mushaf, surah, ayah)| {
+            (
+                id,
+                AyahPosition {
+                    mushaf_id: mushaf,
+                    key: VerseKey { surah, ayah },
+                },
+            )
+        })
+        .collect())
+}
+
+/// Returns the ayah ids of the mushaf by their verse key
+pub fn mushaf_ayah_ids(
+    conn: &mut PgConnection,
+    target_mushaf_id: i32,
+) -> QueryResult<HashMap<VerseKey, i32>> {
+    use crate::schema::quran_ayahs::dsl::{ayah_number, id as ayah_id, quran_ayahs};
+    use crate::schema::quran_surahs::dsl::{mushaf_id, number as surah_number, quran_surahs};
+
+    let ayahs: Vec<(i32, i32, i32)> = quran_ayahs
+        .inner_join(quran_surahs)
+        .filter(mushaf_id.eq(target_mushaf_id))
+        .select((surah_number, ayah_number, ayah_id))
+        .get_results(conn)?;
+
+    Ok(ayahs
+        .into_iter()
+        .map(|(surah, ayah, id)| (VerseKey { surah, ayah }, id))
+        .collect())
+}
+
+/// Returns the word ids of the mushaf by their word key
+///
+/// Words of an ayah are positioned by their id
+pub fn mushaf_word_ids(
+    conn: &mut PgConnection,
+    target_mushaf_id: i32,
+) -> QueryResult<HashMap<WordKey, i32>> {
+    use crate::schema::quran_ayahs::dsl::{ayah_number, quran_ayahs};
+    use crate::schema::quran_surahs::dsl::{mushaf_id, number as surah_number, quran_surahs};
+    use crate::schema::quran_words::dsl::{id as word_id, quran_words};
+
+    let words: Vec<(i32, i32, i32)> = quran_words
+        .inner_join(quran_ayahs.inner_join(quran_surahs))
+        .filter(mushaf_id.eq(target_mushaf_id))
+        .order((surah_number.asc(), ayah_number.asc(), word_id.asc()))
+        .select((surah_number, ayah_number, word_id))
+        .get_results(conn)?;
+
+    let mut result = HashMap::with_capacity(words.len());
+    let mut position = 0;
+    let mut last_verse = None;
+
+    for (surah, ayah, id) in words {
+        let verse = VerseKey { surah, ayah };
+
+        if last_verse != Some(verse) {
+            last_verse = Some(verse);
+            position = 0;
+        }
+
+        position += 1;
+
+        result.insert(
+            WordKey {
+                surah,
+                ayah,
+                word: position,
+            },
+            id,
+        );
+    }
+
+    Ok(result)
+}
+
+/// Returns the words text of the mushaf ayahs in order
+pub fn mushaf_ayah_words(
+    conn: &mut PgConnection,
+    target_mushaf_id: i32,
+) -> QueryResult<BTreeMap<VerseKey, Vec<String>>> {
+    use crate::schema::quran_ayahs::dsl::{ayah_number, quran_ayahs};
+    use crate::schema::quran_surahs::dsl::{mushaf_id, number as surah_number, quran_surahs};
+    use crate::schema::quran_words::dsl::{id as word_id, quran_words, word};
+
+    let words: Vec<(i32, i32, String)> = quran_surahs
+        .inner_join(quran_ayahs.inner_join(quran_words))
+        .filter(mushaf_id.eq(target_mushaf_id))
+        .order((surah_number.asc(), ayah_number.asc(), word_id.asc()))
+        .select((surah_number, ayah_number, word))
+        .get_results(conn)?;
+
+    let mut ayahs: BTreeMap<VerseKey, Vec<String>> = BTreeMap::new();
+
+    for (surah, ayah, text) in words {
+        ayahs
+            .entry(VerseKey { surah, ayah })
+            .or_default()
+            .push(text);
+    }
+
+    Ok(ayahs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_verse_key() {
+        let key: VerseKey = "2:255".parse().unwrap();
+
+        assert_eq!(
+            key,
+            VerseKey {
+                surah: 2,
+                ayah: 255
+            }
+        );
+        assert_eq!(key.to_string(), "2:255");
+    }
+
+    #[test]
+    fn test_parse_word_key() {
+        let key: WordKey = "2:255:3".parse().unwrap();
+
+        assert_eq!(
+            key,
+            WordKey {
+                surah: 2,
+                ayah: 255,
+                word: 3
+            }
+        );
+        assert_eq!(
+            key.verse(),
+            VerseKey {
+                surah: 2,
+                ayah: 255
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_keys() {
+        assert_eq!(parse_parts::<2>("2"), None);
+        assert_eq!(parse_parts::<2>("2:255:1"), None);
+        assert_eq!(parse_parts::<2>("2:0"), None);
+        assert_eq!(parse_parts::<2>("a:b"), None);
+        assert_eq!(parse_parts::<3>("2:255"), None);
+    }
+
+    fn position(mushaf_id: i32, surah: i32, ayah: i32) -> AyahPosition {
+        AyahPosition {
+            mushaf_id,
+            key: VerseKey { surah, ayah },
+        }
+    }
+
+    #[test]
+    fn test_range_is_valid() {
+        assert!(range_is_valid(position(1, 2, 153), position(1, 2, 153)));
+        assert!(range_is_valid(position(1, 2, 286), position(1, 3, 1)));
+        assert!(!range_is_valid(position(1, 3, 1), position(1, 2, 286)));
+        assert!(!range_is_valid(position(1, 2, 153), position(2, 2, 155)));
+    }
+
+    #[test]
+    fn test_range_contains() {
+        let first = position(1, 2, 1);
+        let last = position(1, 2, 5);
+
+        assert!(range_contains(first, last, position(1, 2, 5)));
+        assert!(!range_contains(first, last, position(1, 2, 6)));
+        assert!(!range_contains(first, last, position(2, 2, 3)));
+    }
+}
@@ -0,0 +1,326 @@
+//! In-process cache of the read heavy Quran views
+//!
+//! Views are cached as their JSON by the content version of the request (see `http_cache`).
+//! Writes record their transaction, so a newer version drops every cached view.
+//! The version is read from the database, so the instances stay consistent without LISTEN/NOTIFY.
+
+use std::{env, fmt::Debug, sync::Mutex};
+
+use actix_web::{http::header::ContentType, web::Bytes, HttpResponse};
+use lru::LruCache;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::http_cache::ContentVersion;
+
+const DEFAULT_MAX_ENTRIES: usize = 1024;
+const DEFAULT_MAX_BYTES: usize = 64 * 1024 * 1024;
+
+/// Key of a cached view, every parameter that changes the view is a part of it
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ViewKey {
+    view: &'static str,
+    mushaf: Option<String>,
+    surah: Option<Uuid>,
+    format: Option<String>,
+    lang: Option<String>,
+
+    /// The other parameters, in the order they are added
+    options: Vec<(&'static str, String)>,
+}
+
+impl ViewKey {
+    pub fn new(view: &'static str) -> Self {
+        Self {
+            view,
+            mushaf: None,
+            surah: None,
+            format: None,
+            lang: None,
+            options: vec![],
+        }
+    }
+
+    pub fn mushaf(mut self, mushaf: &str) -> Self {
+        self.mushaf = Some(mushaf.to_string());
+        self
+    }
+
+    pub fn surah(mut self, surah: Uuid) -> Self {
+        self.surah = Some(surah);
+        self
+    }
+
+    pub fn format<T: Debug>(mut self, format: &T) -> Self {
+        self.format = Some(format!("{:?}", format));
+        self
+    }
+
+    pub fn lang(mut self, lang: Option<&str>) -> Self {
+        self.lang = lang.map(str::to_string);
+        self
+    }
+
+    pub fn option<T: Serialize>(mut self, name: &'static str, value: &T) -> Self {
+        self.options
+            .push((name, serde_json::to_string(value).unwrap_or_default()));
+        self
+    }
+}
+
+#[derive(Serialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct ViewCacheMetrics {
+    pub hits: u64,
+    pub misses: u64,
+
+    /// Views dropped to stay in the size limits
+    pub evictions: u64,
+
+    /// Times that a newer content version dropped the cached views
+    pub invalidations: u64,
+
+    pub entries: usize,
+    pub bytes: usize,
+    pub max_entries: usize,
+    pub max_bytes: usize,
+    pub version: Option<i64>,
+}
+
+#[derive(Debug)]
+struct Views {
+    version: Option<i64>,
+    views: LruCache<ViewKey, Bytes>,
+    bytes: usize,
+    metrics: ViewCacheMetrics,
+}
+
+/// Cached views, limited by the number and total bytes of the views
+#[derive(Debug)]
+pub struct ViewCache {
+    max_entries: usize,
+    max_bytes: usize,
+    views: Mutex<Views>,
+}
+
+impl ViewCache {
+    pub fn new(max_entries: usize, max_bytes: usize) -> Self {
+        Self {
+            max_entries,
+            max_bytes,
+            views: Mutex::new(Views {
+                version: None,
+                views: LruCache::unbounded(),
+                bytes: 0,
+                metrics: ViewCacheMetrics::default(),
+            }),
+        }
+    }
+
+    /// Limits are `VIEW_CACHE_MAX_ENTRIES` (1024 by default, 0 disables the cache)
+    /// and `VIEW_CACHE_MAX_BYTES` (64MiB by default)
+    pub fn from_env() -> Self {
+        let limit = |name: &str, default: usize| {
+            env::var(name)
+                .ok()
+                .and_then(|limit| limit.parse().ok())
+                .unwrap_or(default)
+        };
+
+        Self::new(
+            limit("VIEW_CACHE_MAX_ENTRIES", DEFAULT_MAX_ENTRIES),
+            limit("VIEW_CACHE_MAX_BYTES", DEFAULT_MAX_BYTES),
+        )
+    }
+
+    /// Moves the cache to the version, returns false if the version is older
+    /// than the cached views (the request is answered without the cache)
+    fn use_version(&self, views: &mut Views, version: i64) -> bool {
+        match views.version {
+            Some(current) if current > version => false,
+            Some(current) if current == version => true,
+
+            _ => {
+                if !views.views.is_empty() {
+                    views.metrics.invalidations += 1;
+                }
+
+                views.views.clear();
+                views.bytes = 0;
+                views.version = Some(version);
+
+                true
+            }
+        }
+    }
+
+    /// Returns the cached view
+    pub fn get(&self, version: i64, key: &ViewKey) -> Option<Bytes> {
+        if self.max_entries == 0 {
+            return None;
+        }
+
+        let mut views = self.views.lock().unwrap();
+
+        if !self.use_version(&mut views, version) {
+            return None;
+        }
+
+        let view = views.views.get(key).cloned();
+
+        match view {
+            Some(_) => views.metrics.hits += 1,
+            None => views.metrics.misses += 1,
+        }
+
+        view
+    }
+
+    /// Caches the view, the least recently used views are evicted to make room for it
+    pub fn insert(&self, version: i64, key: ViewKey, view: Bytes) {
+        if self.max_entries == 0 || view.len() > self.max_bytes {
+            return;
+        }
+
+        let mut views = self.views.lock().unwrap();
+
+        if !self.use_version(&mut views, version) {
+            return;
+        }
+
+        views.bytes += view.len();
+        if let Some(old) = views.views.put(key, view) {
+            views.bytes -= old.len();
+        }
+
+        while views.views.len() > self.max_entries || views.bytes > self.max_bytes {
+            let Some((_, evicted)) = views.views.pop_lru() else {
+                break;
+            };
+
+            views.bytes -= evicted.len();
+            views.metrics.evictions += 1;
+        }
+    }
+
+    /// Returns the cached response of the view, if the request has a content version
+    pub fn cached(&self, version: Option<&ContentVersion>, key: &ViewKey) -> Option<HttpResponse> {
+        let view = self.get(version?.0, key)?;
+
+        Some(json_response(view))
+    }
+
+    /// Caches the view and returns its response
+    pub fn store<T: Serialize>(
+        &self,
+        version: Option<&ContentVersion>,
+        key: ViewKey,
+        view: &T,
+    ) -> HttpResponse {
+        let view = match serde_json::to_vec(view) {
+            Ok(view) => Bytes::from(view),
+            Err(_) => return HttpResponse::Ok().json(view),
+        };
+
+        if let Some(version) = version {
+            self.insert(version.0, key, view.clone());
+        }
+
+        json_response(view)
+    }
+
+    pub fn metrics(&self) -> ViewCacheMetrics {
+        let views = self.views.lock().unwrap();
+
+        ViewCacheMetrics {
+            entries: views.views.len(),
+            bytes: views.bytes,
+            max_entries: self.max_entries,
+            max_bytes: self.max_bytes,
+            version: views.version,
+            ..views.metrics.clone()
+        }
+    }
+}
+
+fn json_response(view: Bytes) -> HttpResponse {
+    HttpResponse::Ok()
+        .content_type(ContentType::json())
+        .body(view)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(surah: u128) -> ViewKey {
+        ViewKey::new("surah_view")
+            .surah(Uuid::from_u128(surah))
+            .lang(Some("en"))
+    }
+
+    #[test]
+    fn test_version_invalidation() {
+        let cache = ViewCache::new(10, 1024);
+
+        cache.insert(1, key(1), Bytes::from_static(b"[1]"));
+        assert_eq!(cache.get(1, &key(1)), Some(Bytes::from_static(b"[1]")));
+        assert_eq!(cache.get(1, &key(2)), None);
+
+        // Older requests don't see or replace the newer views
+        cache.insert(2, key(1), Bytes::from_static(b"[2]"));
+        assert_eq!(cache.get(1, &key(1)), None);
+        cache.insert(1, key(2), Bytes::from_static(b"[1]"));
+        assert_eq!(cache.get(2, &key(2)), None);
+        assert_eq!(cache.get(2, &key(1)), Some(Bytes::from_static(b"[2]")));
+
+        let metrics = cache.metrics();
+        assert_eq!(metrics.hits, 2);
+        assert_eq!(metrics.misses, 2);
+        assert_eq!(metrics.invalidations, 1);
+        assert_eq!(metrics.version, Some(2));
+    }
+
+    #[test]
+    fn test_phrase_edit_evicts_views() {
+        // Surah names are translated by the phrases, their edits make a new content version
+        let migration =
+            include_str!("../migrations/2026-10-19-010000_create_app_content_changes/up.sql");
+        for table in ["app_phrases", "app_phrase_translations", "app_user_names"] {
+            assert!(migration.contains(&format!(
+                "CREATE TRIGGER {}_content_change AFTER INSERT OR UPDATE OR DELETE OR TRUNCATE ON {}\n",
+                table, table
+            )));
+        }
+
+        let cache = ViewCache::new(10, 1024);
+        cache.insert(1, key(1), Bytes::from_static(b"[1]"));
+
+        // The version after the phrase edit
+        assert_eq!(cache.get(2, &key(1)), None);
+        assert_eq!(cache.metrics().invalidations, 1);
+    }
+
+    #[test]
+    fn test_size_limits() {
+        let cache = ViewCache::new(2, 8);
+
+        cache.insert(1, key(1), Bytes::from_static(b"1111"));
+        cache.insert(1, key(2), Bytes::from_static(b"2222"));
+        cache.get(1, &key(1));
+
+        // Over the bytes, the least recently used is evicted
+        cache.insert(1, key(3), Bytes::from_static(b"33"));
+        assert!(cache.get(1, &key(2)).is_none());
+        assert!(cache.get(1, &key(1)).is_some());
+
+        // Larger than the whole cache
+        cache.insert(1, key(4), Bytes::from_static(b"444444444"));
+        assert!(cache.get(1, &key(4)).is_none());
+
+        let metrics = cache.metrics();
+        assert_eq!(metrics.entries, 2);
+        assert_eq!(metrics.bytes, 6);
+        assert_eq!(metrics.evictions, 1);
+        assert_ne!(key(1), key(1).option("script", &Some("uthmani")));
+    }
+}